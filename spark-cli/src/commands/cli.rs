@@ -1,26 +1,48 @@
 use crate::commands::{
     core::cli::CoreCommands, info::cli::InfoCommands, registry::cli::RegistryCommands,
 };
+use crate::rpc_headers::{parse_rpc_header, RpcHeader};
 use clap::{Args, Parser, Subcommand};
 
 #[derive(Parser)]
 #[command(about = "")] // TODO: about
 pub(crate) struct Cli {
+    /// Disable colored output, also disabled when stdout is not a terminal
+    #[clap(long, global = true)]
+    pub(crate) no_color: bool,
+
+    /// Print only the result of a command, e.g. the order id, without the call cost or progress
+    #[clap(long, global = true)]
+    pub(crate) quiet: bool,
+
+    /// Indent JSON output for reading, it is compact by default for piping
+    #[clap(long, global = true)]
+    pub(crate) json_pretty: bool,
+
+    /// Print the causes of an error and its backtrace, when RUST_BACKTRACE is set, not only its message
+    #[clap(long, global = true)]
+    pub(crate) verbose: bool,
+
+    /// Extra HTTP header sent to the RPC as "Key: Value", may be repeated
+    /// A bearer token can be given in the RPC_TOKEN environment variable instead
+    #[clap(long, global = true, value_parser = parse_rpc_header)]
+    pub(crate) rpc_header: Vec<RpcHeader>,
+
     #[command(subcommand)]
     pub(crate) command: Command,
 }
 
 #[derive(Clone, Subcommand)]
 pub(crate) enum Command {
-    ///
+    /// Interact with the market registry contract
     #[clap(short_flag = 'R')]
     Registry(Registry),
 
-    ///
+    /// Call state changing functions of a market contract
     #[clap(short_flag = 'C')]
     Core(Core),
 
-    ///
+    /// Query information from a market contract
     #[clap(short_flag = 'I')]
     Info(Info),
 }
@@ -33,6 +55,14 @@ pub(crate) struct Registry {
 
 #[derive(Args, Clone)]
 pub(crate) struct Core {
+    /// Print the estimated fee of each call and ask for confirmation before sending it
+    #[clap(long, global = true)]
+    pub(crate) confirm_fee: bool,
+
+    /// Answer yes to fee confirmations, sending calls without asking
+    #[clap(long, global = true)]
+    pub(crate) yes: bool,
+
     #[clap(subcommand)]
     pub(crate) commands: CoreCommands,
 }
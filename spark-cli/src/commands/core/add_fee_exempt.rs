@@ -0,0 +1,64 @@
+use crate::utils::{
+    fee_confirmation, print_info, print_success, setup, validate_deployed_contract_id, AccountType,
+};
+use clap::Args;
+use fuels::accounts::ViewOnlyAccount;
+use spark_market_sdk::SparkMarketContract;
+
+#[derive(Args, Clone)]
+#[command(about = "Exempt an account from the matcher and protocol fees")]
+pub(crate) struct AddFeeExemptCommand {
+    /// The b256 id of the account
+    #[clap(long)]
+    pub(crate) account_id: String,
+
+    /// The type of account
+    #[clap(long)]
+    pub(crate) account_type: AccountType,
+
+    /// Abort without sending the call when its estimated fee exceeds this amount of the fee asset
+    #[clap(long)]
+    pub(crate) max_fee: Option<u64>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl AddFeeExemptCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+
+        let account = self.account_type.identity(&self.account_id)?;
+
+        // Initial balance prior to contract call - used to calculate contract interaction cost
+        let balance = wallet
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
+            .await?;
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet.clone())
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
+
+        let _ = contract.add_fee_exempt(account).await?;
+
+        // Balance post-call
+        let new_balance = wallet
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
+            .await?;
+
+        print_success(format!("\n{} is exempt from fees", self.account_id));
+        print_info(format!("Contract call cost: {}", balance - new_balance));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use crate::utils::{
+    fee_confirmation, print_info, print_result, setup, validate_deployed_contract_id,
+};
+use clap::Args;
+use fuels::{accounts::ViewOnlyAccount, types::Bits256};
+use spark_market_sdk::SparkMarketContract;
+
+#[derive(Args, Clone)]
+#[command(about = "Cancels an order past its deadline and collects the keeper reward")]
+pub(crate) struct CancelExpiredCommand {
+    /// The b256 id of the order
+    #[clap(long)]
+    pub(crate) order_id: String,
+
+    /// Abort without sending the call when its estimated fee exceeds this amount of the fee asset
+    #[clap(long)]
+    pub(crate) max_fee: Option<u64>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl CancelExpiredCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+        let order_id = Bits256::from_hex_str(&self.order_id)?;
+
+        // Initial balance prior to contract call - used to calculate contract interaction cost
+        let balance = wallet
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
+            .await?;
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet.clone())
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
+
+        let keeper_reward = match contract.order_deadline(order_id).await?.value {
+            Some((_, keeper_reward)) => keeper_reward,
+            None => anyhow::bail!("Order {} has no deadline", self.order_id),
+        };
+
+        let _ = contract.cancel_expired(order_id).await?;
+
+        // Balance post-call
+        let new_balance = wallet
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
+            .await?;
+
+        print_info(format!("\nContract call cost: {}", balance - new_balance));
+        print_result(format!("Keeper reward: {}", keeper_reward), keeper_reward);
+
+        Ok(())
+    }
+}
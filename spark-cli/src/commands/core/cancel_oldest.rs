@@ -0,0 +1,64 @@
+use crate::utils::{
+    fee_confirmation, print_info, print_result, setup, validate_deployed_contract_id,
+};
+use clap::Args;
+use fuels::accounts::ViewOnlyAccount;
+use spark_market_sdk::SparkMarketContract;
+
+#[derive(Args, Clone)]
+#[command(about = "Cancels the signer's oldest open orders by order height")]
+pub(crate) struct CancelOldestCommand {
+    /// The number of orders to cancel, oldest first
+    #[clap(long)]
+    pub(crate) count: usize,
+
+    /// Abort without sending the call when its estimated fee exceeds this amount of the fee asset
+    #[clap(long)]
+    pub(crate) max_fee: Option<u64>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl CancelOldestCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+
+        // Initial balance prior to contract call - used to calculate contract interaction cost
+        let balance = wallet
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
+            .await?;
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet.clone())
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
+
+        let cancelled = contract.cancel_oldest(self.count).await?;
+
+        // Balance post-call
+        let new_balance = wallet
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
+            .await?;
+
+        print_info(format!("\nContract call cost: {}", balance - new_balance));
+        for order_id in &cancelled {
+            print_info(format!("Cancelled order: 0x{}", hex::encode(order_id.0)));
+        }
+        print_result(
+            format!("Cancelled orders: {}", cancelled.len()),
+            cancelled.len(),
+        );
+
+        Ok(())
+    }
+}
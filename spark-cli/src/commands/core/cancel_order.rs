@@ -1,14 +1,44 @@
-use crate::utils::{setup, validate_contract_id};
+use crate::utils::{
+    fee_confirmation, print_info, save_call, setup, validate_deployed_contract_id, OrderType,
+};
 use clap::Args;
-use fuels::{accounts::ViewOnlyAccount, types::Bits256};
-use spark_market_sdk::SparkMarketContract;
+use fuels::{
+    accounts::ViewOnlyAccount,
+    types::{Bits256, Identity},
+};
+use spark_market_sdk::{MarketCall, OrderType as ContractOrderType, SparkMarketContract};
+use std::path::PathBuf;
 
 #[derive(Args, Clone)]
 #[command(about = "Cancels an open order")]
 pub(crate) struct CancelCommand {
     /// The b256 id of the order
+    #[clap(long, required_unless_present = "price", conflicts_with = "price")]
+    pub(crate) order_id: Option<String>,
+
+    /// The type of order, to cancel by parameters instead of id
+    #[clap(long, requires = "price")]
+    pub(crate) order_type: Option<OrderType>,
+
+    /// The price of the order, to cancel by parameters instead of id
+    #[clap(long, requires_all = ["order_type", "block_height", "order_height"])]
+    pub(crate) price: Option<u64>,
+
+    /// The block height at which the order was opened, to cancel by parameters instead of id
+    #[clap(long, requires = "price")]
+    pub(crate) block_height: Option<u32>,
+
+    /// The order height of the order, to cancel by parameters instead of id
+    #[clap(long, requires = "price")]
+    pub(crate) order_height: Option<u64>,
+
+    /// Save the call to this file before submitting so it can be replayed
+    #[clap(long)]
+    pub(crate) save_call: Option<PathBuf>,
+
+    /// Abort without sending the call when its estimated fee exceeds this amount of the fee asset
     #[clap(long)]
-    pub(crate) order_id: String,
+    pub(crate) max_fee: Option<u64>,
 
     /// The contract id of the market
     #[clap(long)]
@@ -23,29 +53,56 @@ pub(crate) struct CancelCommand {
 impl CancelCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
         let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
-        let order_id = Bits256::from_hex_str(&self.order_id)?;
-
-        if self.order_id.len() as u64 != 64 {
-            anyhow::bail!("Invalid order id length");
-        }
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
 
         // Initial balance prior to contract call - used to calculate contract interaction cost
         let balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
         // Connect to the deployed contract via the rpc
-        let contract = SparkMarketContract::new(contract_id, wallet.clone()).await;
+        let contract = SparkMarketContract::new(contract_id, wallet.clone())
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
+
+        if let Some(order_id) = &self.order_id {
+            if order_id.len() as u64 != 64 {
+                anyhow::bail!("Invalid order id length");
+            }
+            let order_id = Bits256::from_hex_str(order_id)?;
+
+            save_call(&self.save_call, &MarketCall::CancelOrder { order_id })?;
+
+            let _ = contract.cancel_order(order_id).await?;
+        } else {
+            // Clap guarantees every parameter is present when the id is not
+            let order_type = ContractOrderType::from(self.order_type.clone().unwrap());
+            let (price, block_height, order_height) = (
+                self.price.unwrap(),
+                self.block_height.unwrap(),
+                self.order_height.unwrap(),
+            );
 
-        let _ = contract.cancel_order(order_id).await?;
+            let owner = Identity::Address(wallet.address().into());
+            let order_id =
+                contract.order_id_offline(order_type.clone(), owner, price, order_height);
+            print_info(format!("\nOrder ID: 0x{}", hex::encode(order_id.0)));
+
+            save_call(&self.save_call, &MarketCall::CancelOrder { order_id })?;
+
+            let _ = contract
+                .cancel_by_params(order_type, price, block_height, order_height)
+                .await?;
+        }
 
         // Balance post-call
         let new_balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
-        println!("\nContract call cost: {}", balance - new_balance);
+        print_info(format!("\nContract call cost: {}", balance - new_balance));
 
         Ok(())
     }
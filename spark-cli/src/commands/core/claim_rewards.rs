@@ -0,0 +1,62 @@
+use crate::utils::{
+    fee_confirmation, print_info, print_result, setup, validate_deployed_contract_id,
+};
+use clap::Args;
+use fuels::{accounts::ViewOnlyAccount, types::Identity};
+use spark_market_sdk::SparkMarketContract;
+
+#[derive(Args, Clone)]
+#[command(about = "Claims the keeper rewards accrued by the signer")]
+pub(crate) struct ClaimRewardsCommand {
+    /// Abort without sending the call when its estimated fee exceeds this amount of the fee asset
+    #[clap(long)]
+    pub(crate) max_fee: Option<u64>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl ClaimRewardsCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+
+        // Initial balance prior to contract call - used to calculate contract interaction cost
+        let balance = wallet
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
+            .await?;
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet.clone())
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
+
+        let rewards = contract
+            .keeper_rewards(Identity::Address(wallet.address().into()))
+            .await?
+            .value;
+        if rewards == 0 {
+            anyhow::bail!("No keeper rewards to claim");
+        }
+
+        let _ = contract.claim_keeper_rewards().await?;
+
+        // Balance post-call
+        let new_balance = wallet
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
+            .await?;
+
+        print_info(format!("\nContract call cost: {}", balance - new_balance));
+        print_result(format!("Claimed keeper rewards: {}", rewards), rewards);
+
+        Ok(())
+    }
+}
@@ -1,17 +1,43 @@
 use crate::commands::core::{
-    cancel_order::CancelCommand, deploy::DeployCommand, deposit::DepositCommand,
-    fulfill_many::FulfillManyCommand, match_many::MatchManyCommand, match_pair::MatchPairCommand,
-    open_order::OpenCommand, set_epoch::SetEpochCommand, set_matcher_fee::SetMatcherFeeCommand,
-    set_protocol_fee::SetProtocolFeeCommand, withdraw::WithdrawCommand,
+    add_fee_exempt::AddFeeExemptCommand, cancel_expired::CancelExpiredCommand,
+    cancel_oldest::CancelOldestCommand, cancel_order::CancelCommand,
+    claim_rewards::ClaimRewardsCommand, configure::ConfigureCommand, deploy::DeployCommand,
+    deposit::DepositCommand, fulfill_many::FulfillManyCommand, match_many::MatchManyCommand,
+    match_pair::MatchPairCommand, matcher::MatcherCommand, open_batch::OpenBatchCommand,
+    open_order::OpenCommand, remove_fee_exempt::RemoveFeeExemptCommand, replay::ReplayCommand,
+    set_dust_threshold::SetDustThresholdCommand, set_epoch::SetEpochCommand,
+    set_matcher_fee::SetMatcherFeeCommand, set_max_price_deviation::SetMaxPriceDeviationCommand,
+    set_protocol_fee::SetProtocolFeeCommand, set_tick_size::SetTickSizeCommand,
+    withdraw::WithdrawCommand,
 };
 use clap::Subcommand;
 
 #[derive(Clone, Subcommand)]
 pub(crate) enum CoreCommands {
+    /// Exempt an account from fees
+    #[clap(short_flag = 'X')]
+    AddFeeExempt(AddFeeExemptCommand),
+
     /// Cancel an open order
     #[clap(short_flag = 'C')]
     Cancel(CancelCommand),
 
+    /// Cancel an order past its deadline as a keeper
+    #[clap(short_flag = 'K')]
+    CancelExpired(CancelExpiredCommand),
+
+    /// Cancel the signer's oldest open orders
+    #[clap(short_flag = 'Q')]
+    CancelOldest(CancelOldestCommand),
+
+    /// Claim the keeper rewards accrued by the signer
+    #[clap(short_flag = 'L')]
+    ClaimRewards(ClaimRewardsCommand),
+
+    /// Apply market settings from a file
+    #[clap(short_flag = 'G')]
+    Configure(ConfigureCommand),
+
     /// Deploy a new market contract
     #[clap(short_flag = 'D')]
     Deploy(DeployCommand),
@@ -32,10 +58,30 @@ pub(crate) enum CoreCommands {
     #[clap(short_flag = 'A')]
     MatchPair(MatchPairCommand),
 
+    /// Keep matching the profitable crossed pairs of a set of orders
+    #[clap(short_flag = 'H')]
+    Matcher(MatcherCommand),
+
     /// Open an order
     #[clap(short_flag = 'O')]
     Open(OpenCommand),
 
+    /// Open the orders listed in a file
+    #[clap(short_flag = 'N')]
+    OpenBatch(OpenBatchCommand),
+
+    /// Remove an account from the fee exemptions
+    #[clap(short_flag = 'Z')]
+    RemoveFeeExempt(RemoveFeeExemptCommand),
+
+    /// Resubmit a saved call with a higher gas limit
+    #[clap(short_flag = 'R')]
+    Replay(ReplayCommand),
+
+    /// Set the dust threshold for the market
+    #[clap(short_flag = 'U')]
+    SetDustThreshold(SetDustThresholdCommand),
+
     /// Set a protocol fee
     #[clap(short_flag = 'E')]
     SetEpoch(SetEpochCommand),
@@ -48,6 +94,14 @@ pub(crate) enum CoreCommands {
     #[clap(short_flag = 'T')]
     SetMatcherFee(SetMatcherFeeCommand),
 
+    /// Set the maximum price deviation from the last trade for new orders
+    #[clap(short_flag = 'B')]
+    SetMaxPriceDeviation(SetMaxPriceDeviationCommand),
+
+    /// Set the tick size that new order prices must be a multiple of
+    #[clap(short_flag = 'I')]
+    SetTickSize(SetTickSizeCommand),
+
     /// Withdraw from the market contract
     #[clap(short_flag = 'W')]
     Withdraw(WithdrawCommand),
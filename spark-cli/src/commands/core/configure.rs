@@ -0,0 +1,340 @@
+use crate::utils::{
+    fee_confirmation, print_info, print_result, print_success, setup, validate_deployed_contract_id,
+};
+use clap::Args;
+use fuels::accounts::{Account, ViewOnlyAccount};
+use serde_json::Value;
+use spark_market_sdk::{ProtocolFee, SparkMarketContract};
+use std::{fmt::Debug, path::PathBuf};
+
+const SETTINGS: [&str; 7] = [
+    "matcher_fee",
+    "protocol_fee",
+    "epoch",
+    "epoch_duration",
+    "dust_threshold",
+    "max_price_deviation_bps",
+    "tick_size",
+];
+
+#[derive(Args, Clone)]
+#[command(about = "Apply the market settings in a JSON file, skipping values already in place")]
+pub(crate) struct ConfigureCommand {
+    /// Path to the JSON settings file
+    /// Ex. {"matcher_fee": 1000, "protocol_fee": [{"maker_fee": 10, "taker_fee": 15, "volume_threshold": 0}]}
+    #[clap(long)]
+    pub(crate) settings: PathBuf,
+
+    /// Abort without sending the call when its estimated fee exceeds this amount of the fee asset
+    #[clap(long)]
+    pub(crate) max_fee: Option<u64>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl ConfigureCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let settings = Settings::from_json(&std::fs::read_to_string(&self.settings)?)?;
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+
+        // Initial balance prior to contract call - used to calculate contract interaction cost
+        let balance = wallet
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
+            .await?;
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet.clone())
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
+
+        let applied = settings.apply(&contract).await?;
+
+        // Balance post-deployment
+        let new_balance = wallet
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
+            .await?;
+
+        print_info("");
+        for setting in &applied {
+            match setting.changed() {
+                true => print_success(format!(
+                    "{}: {} -> {}",
+                    setting.name, setting.before, setting.after
+                )),
+                false => print_info(format!("{}: {} (unchanged)", setting.name, setting.after)),
+            }
+        }
+        let changed = applied.iter().filter(|setting| setting.changed()).count();
+        print_result(
+            format!("\n{} of {} setting(s) changed", changed, applied.len()),
+            changed,
+        );
+        print_info(format!("Contract call cost: {}", balance - new_balance));
+
+        Ok(())
+    }
+}
+
+/// Market settings to apply, the ones left out of the file are not touched
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Settings {
+    matcher_fee: Option<u64>,
+    protocol_fee: Option<Vec<ProtocolFee>>,
+    epoch: Option<u64>,
+    epoch_duration: Option<u64>,
+    dust_threshold: Option<u64>,
+    max_price_deviation_bps: Option<u64>,
+    tick_size: Option<u64>,
+}
+
+/// A setting from the file with its value before and after it was applied
+pub(crate) struct AppliedSetting {
+    name: &'static str,
+    before: String,
+    after: String,
+}
+
+impl AppliedSetting {
+    fn new(name: &'static str, before: impl Debug, after: impl Debug) -> Self {
+        Self {
+            name,
+            before: format!("{:?}", before),
+            after: format!("{:?}", after),
+        }
+    }
+
+    fn changed(&self) -> bool {
+        self.before != self.after
+    }
+}
+
+impl Settings {
+    pub(crate) fn from_json(json: &str) -> anyhow::Result<Self> {
+        let value: Value = serde_json::from_str(json)?;
+        let object = value
+            .as_object()
+            .ok_or_else(|| anyhow::anyhow!("Settings must be a JSON object"))?;
+
+        // A misspelled setting would otherwise be silently left unapplied
+        if let Some(name) = object
+            .keys()
+            .find(|name| !SETTINGS.contains(&name.as_str()))
+        {
+            anyhow::bail!("Unknown setting: {}", name);
+        }
+
+        let u64_field = |name: &str| {
+            object
+                .get(name)
+                .map(|value| {
+                    value
+                        .as_u64()
+                        .ok_or_else(|| anyhow::anyhow!("Invalid setting: {}", name))
+                })
+                .transpose()
+        };
+        let protocol_fee = object
+            .get("protocol_fee")
+            .map(|value| {
+                value
+                    .as_array()
+                    .ok_or_else(|| anyhow::anyhow!("Invalid setting: protocol_fee"))?
+                    .iter()
+                    .map(|tier| {
+                        let field = |name: &str| {
+                            tier.get(name).and_then(Value::as_u64).ok_or_else(|| {
+                                anyhow::anyhow!("Invalid setting: protocol_fee.{}", name)
+                            })
+                        };
+                        Ok(ProtocolFee {
+                            maker_fee: field("maker_fee")?,
+                            taker_fee: field("taker_fee")?,
+                            volume_threshold: field("volume_threshold")?,
+                        })
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()
+            })
+            .transpose()?;
+
+        Ok(Self {
+            matcher_fee: u64_field("matcher_fee")?,
+            protocol_fee,
+            epoch: u64_field("epoch")?,
+            epoch_duration: u64_field("epoch_duration")?,
+            dust_threshold: u64_field("dust_threshold")?,
+            max_price_deviation_bps: u64_field("max_price_deviation_bps")?,
+            tick_size: u64_field("tick_size")?,
+        })
+    }
+
+    /// Sets every value that differs from the market and reports each setting of the file
+    pub(crate) async fn apply(
+        &self,
+        contract: &SparkMarketContract<impl Account>,
+    ) -> anyhow::Result<Vec<AppliedSetting>> {
+        let mut applied = Vec::new();
+
+        if let Some(fee) = self.matcher_fee {
+            let current = contract.matcher_fee().await?.value;
+            if current != fee {
+                contract.set_matcher_fee(fee).await?;
+            }
+            applied.push(AppliedSetting::new("Matcher Fee", current, fee));
+        }
+
+        if let Some(fee) = &self.protocol_fee {
+            let current = contract.protocol_fee().await?.value;
+            if current != *fee {
+                contract.set_protocol_fee(fee.clone()).await?;
+            }
+            applied.push(AppliedSetting::new("Protocol Fee", current, fee));
+        }
+
+        // The epoch and its duration are set together, the one left out keeps its value
+        if self.epoch.is_some() || self.epoch_duration.is_some() {
+            let current = contract.get_epoch().await?.value;
+            let epoch = (
+                self.epoch.unwrap_or(current.0),
+                self.epoch_duration.unwrap_or(current.1),
+            );
+            if current != epoch {
+                contract.set_epoch(epoch.0, epoch.1).await?;
+            }
+            applied.push(AppliedSetting::new("Epoch", current, epoch));
+        }
+
+        if let Some(amount) = self.dust_threshold {
+            let current = contract.dust_threshold().await?.value;
+            if current != amount {
+                contract.set_dust_threshold(amount).await?;
+            }
+            applied.push(AppliedSetting::new("Dust Threshold", current, amount));
+        }
+
+        if let Some(bps) = self.max_price_deviation_bps {
+            let current = contract.max_price_deviation_bps().await?.value;
+            if current != bps {
+                contract.set_max_price_deviation_bps(bps).await?;
+            }
+            applied.push(AppliedSetting::new(
+                "Max Price Deviation (bps)",
+                current,
+                bps,
+            ));
+        }
+
+        if let Some(tick) = self.tick_size {
+            let current = contract.tick_size().await?.value;
+            if current != tick {
+                contract.set_tick_size(tick).await?;
+            }
+            applied.push(AppliedSetting::new("Tick Size", current, tick));
+        }
+
+        Ok(applied)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuels::{
+        crypto::SecretKey,
+        prelude::{setup_single_asset_coins, setup_test_provider, WalletUnlocked},
+        types::AssetId,
+    };
+    use std::{
+        str::FromStr,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    #[tokio::test]
+    async fn applies_settings_once() -> anyhow::Result<()> {
+        let secret = "0x862512a2363db2b3a375c0d4bbbd27172180d89f23f2e259bac850ab02619301";
+        let mut wallet = WalletUnlocked::new_from_private_key(SecretKey::from_str(secret)?, None);
+        let coins =
+            setup_single_asset_coins(wallet.address(), AssetId::default(), 1, 1_000_000_000);
+        let provider = setup_test_provider(coins, vec![], None, None).await?;
+        wallet.set_provider(provider);
+        let market = SparkMarketContract::deploy(
+            AssetId::from([1; 32]),
+            9,
+            AssetId::from([2; 32]),
+            6,
+            wallet.clone(),
+            9,
+            0x000400,
+        )
+        .await?;
+
+        // The epoch has to end in the future, in TAI64 seconds
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let epoch = now + 37 + (1 << 62);
+        let settings = Settings::from_json(&format!(
+            r#"{{
+                "matcher_fee": 1000,
+                "protocol_fee": [
+                    {{"maker_fee": 10, "taker_fee": 15, "volume_threshold": 0}},
+                    {{"maker_fee": 5, "taker_fee": 10, "volume_threshold": 1000000}}
+                ],
+                "epoch": {},
+                "epoch_duration": 86400
+            }}"#,
+            epoch
+        ))?;
+
+        let applied = settings.apply(&market).await?;
+        assert_eq!(applied.len(), 3);
+        assert!(applied.iter().all(|setting| setting.changed()));
+
+        assert_eq!(market.matcher_fee().await?.value, 1000);
+        assert_eq!(
+            market.protocol_fee().await?.value,
+            vec![
+                ProtocolFee {
+                    maker_fee: 10,
+                    taker_fee: 15,
+                    volume_threshold: 0,
+                },
+                ProtocolFee {
+                    maker_fee: 5,
+                    taker_fee: 10,
+                    volume_threshold: 1_000_000,
+                },
+            ]
+        );
+        assert_eq!(market.get_epoch().await?.value, (epoch, 86400));
+
+        // Everything is in place already, so nothing is sent the second time
+        let provider = wallet.try_provider()?;
+        let height = provider.latest_block_height().await?;
+        let applied = settings.apply(&market).await?;
+        assert!(applied.iter().all(|setting| !setting.changed()));
+        assert_eq!(provider.latest_block_height().await?, height);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_unknown_settings() {
+        assert_eq!(
+            Settings::from_json(r#"{"tick_size": 10}"#).unwrap(),
+            Settings {
+                tick_size: Some(10),
+                ..Default::default()
+            }
+        );
+        assert!(Settings::from_json(r#"{"min_order_size": 10}"#).is_err());
+        assert!(Settings::from_json(r#"{"matcher_fee": -1}"#).is_err());
+    }
+}
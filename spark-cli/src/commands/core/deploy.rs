@@ -1,4 +1,4 @@
-use crate::utils::setup;
+use crate::utils::{print_info, print_result, setup};
 use clap::Args;
 use fuels::{accounts::ViewOnlyAccount, types::AssetId};
 use spark_market_sdk::SparkMarketContract;
@@ -50,7 +50,7 @@ impl DeployCommand {
 
         // Initial balance prior to contract call - used to calculate contract interaction cost
         let balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
         let version = SparkMarketContract::sdk_version();
@@ -69,18 +69,21 @@ impl DeployCommand {
 
         // Balance post-deployment
         let new_balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
-        println!(
-            "\nMarket version {} ({}) deployed to: 0x{}",
-            contract.contract_str_version().await?,
-            version,
-            contract.id()
+        print_result(
+            format!(
+                "\nMarket version {} ({}) deployed to: 0x{}",
+                contract.contract_str_version().await?,
+                version,
+                contract.id()
+            ),
+            format!("0x{}", contract.id()),
         );
-        println!("Deployment cost: {}", balance - new_balance);
-        println!("Owner address: {}", wallet.address());
-        println!("               0x{}", wallet.address().hash());
+        print_info(format!("Deployment cost: {}", balance - new_balance));
+        print_info(format!("Owner address: {}", wallet.address()));
+        print_info(format!("               0x{}", wallet.address().hash()));
 
         Ok(())
     }
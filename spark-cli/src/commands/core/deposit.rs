@@ -1,4 +1,7 @@
-use crate::utils::{setup, validate_contract_id, AssetType};
+use crate::utils::{
+    fee_confirmation, print_info, print_result, setup, validate_deployed_contract_id, AssetType,
+    CallCost,
+};
 use clap::Args;
 use fuels::accounts::ViewOnlyAccount;
 use spark_market_sdk::{AssetType as ContractAssetType, SparkMarketContract};
@@ -14,6 +17,10 @@ pub(crate) struct DepositCommand {
     #[clap(long)]
     pub(crate) asset_type: AssetType,
 
+    /// Abort without sending the call when its estimated fee exceeds this amount of the fee asset
+    #[clap(long)]
+    pub(crate) max_fee: Option<u64>,
+
     /// The contract id of the market
     #[clap(long)]
     pub(crate) contract_id: String,
@@ -27,44 +34,129 @@ pub(crate) struct DepositCommand {
 impl DepositCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
         let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
 
-        let asset_type = match self.asset_type {
-            AssetType::Base => ContractAssetType::Base,
-            AssetType::Quote => ContractAssetType::Quote,
-        };
+        let asset_type = ContractAssetType::from(self.asset_type.clone());
 
         // Initial balance prior to contract call - used to calculate contract interaction cost
         let balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
         // Connect to the deployed contract via the rpc
-        let contract = SparkMarketContract::new(contract_id, wallet.clone()).await;
+        let contract = SparkMarketContract::new(contract_id, wallet.clone())
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
 
-        let config = contract.config().await?.value;
+        let config = contract.config().await?;
         let asset = if asset_type == ContractAssetType::Base {
-            config.0
+            config.base_asset
         } else {
-            config.2
+            config.quote_asset
         };
         let asset_balance = wallet.get_asset_balance(&asset).await?;
 
-        let _ = contract.deposit(self.amount, asset).await?;
+        let response = contract.deposit(self.amount, asset).await?;
 
         // Balance post-call
         let new_balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
         let new_asset_balance = wallet.get_asset_balance(&asset).await?;
 
-        println!("\nContract call cost: {}", balance - new_balance);
-        println!(
-            "Deposited {} amount of asset {}",
+        let cost = CallCost::new(
+            wallet.provider().unwrap().base_asset_id(),
+            &asset,
+            balance,
+            new_balance,
+            response.gas_used,
+        );
+        // The fee is paid in the deposited asset too when it is the fee asset
+        let deposited = match cost {
+            CallCost::Fee(_) => asset_balance - new_asset_balance,
+            CallCost::Gas(_) => self.amount,
+        };
+
+        print_info(format!("\n{}", cost));
+        print_result(
+            format!("Deposited {} amount of asset {}", self.amount, deposited),
             self.amount,
-            asset_balance - new_asset_balance
         );
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuels::{
+        crypto::SecretKey,
+        prelude::{setup_single_asset_coins, setup_test_provider, NodeConfig, WalletUnlocked},
+        types::{AssetId, Identity},
+    };
+    use spark_market_sdk::FeeTooHigh;
+    use std::str::FromStr;
+
+    #[tokio::test]
+    async fn aborts_without_sending_when_the_fee_exceeds_max_fee() -> anyhow::Result<()> {
+        let secret = "0x862512a2363db2b3a375c0d4bbbd27172180d89f23f2e259bac850ab02619301";
+        let mut wallet = WalletUnlocked::new_from_private_key(SecretKey::from_str(secret)?, None);
+        let base = AssetId::from([1; 32]);
+        let mut coins =
+            setup_single_asset_coins(wallet.address(), AssetId::default(), 1, 1_000_000_000_000);
+        coins.extend(setup_single_asset_coins(
+            wallet.address(),
+            base,
+            1,
+            1_000_000,
+        ));
+        // A high gas price makes every call estimate well above the cap
+        let node_config = NodeConfig {
+            starting_gas_price: 1_000_000,
+            ..Default::default()
+        };
+        let provider = setup_test_provider(coins, vec![], Some(node_config), None).await?;
+        wallet.set_provider(provider.clone());
+        std::env::set_var("WALLET_SECRET", secret);
+
+        let market = SparkMarketContract::deploy(
+            base,
+            9,
+            AssetId::from([2; 32]),
+            6,
+            wallet.clone(),
+            9,
+            0x000400,
+        )
+        .await?;
+
+        let base_balance = wallet.get_asset_balance(&base).await?;
+        let error = DepositCommand {
+            amount: 1_000,
+            asset_type: AssetType::Base,
+            max_fee: Some(1),
+            contract_id: format!("0x{}", market.id()),
+            rpc: provider.url().to_string(),
+        }
+        .run()
+        .await
+        .unwrap_err();
+
+        assert!(error.to_string().starts_with("FeeTooHigh"));
+        let fee = error.downcast::<FeeTooHigh>()?;
+        assert!(fee.estimated > fee.limit);
+
+        // Nothing left the wallet and the market holds nothing for it
+        assert_eq!(wallet.get_asset_balance(&base).await?, base_balance);
+        let account = market
+            .account(Identity::Address(wallet.address().into()))
+            .await?
+            .value;
+        assert_eq!(account.liquid.base, 0);
+
+        Ok(())
+    }
+}
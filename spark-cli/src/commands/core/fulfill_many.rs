@@ -1,13 +1,14 @@
-use crate::utils::{setup, validate_contract_id, /*AssetType,*/ LimitType, OrderType};
-use clap::Args;
-use fuels::{
-    accounts::ViewOnlyAccount,
-    types::{Bits256, ContractId},
+use crate::utils::{
+    encode_b256, fee_confirmation, print_info, print_result, save_call, setup,
+    validate_deployed_contract_id, /*AssetType,*/ Encoding, LimitType, OrderType,
 };
+use clap::Args;
+use fuels::{accounts::ViewOnlyAccount, types::Bits256};
 use spark_market_sdk::{
-    /*AssetType as ContractAssetType,*/ LimitType as ContractLimitType,
+    /*AssetType as ContractAssetType,*/ LimitType as ContractLimitType, MarketCall,
     OrderType as ContractOrderType, SparkMarketContract,
 };
+use std::path::PathBuf;
 
 #[derive(Args, Clone)]
 #[command(about = "Fulfill a new order")]
@@ -40,6 +41,18 @@ pub(crate) struct FulfillManyCommand {
     #[clap(long)]
     pub(crate) orders: Vec<String>,
 
+    /// The encoding used to print b256 values
+    #[clap(long, value_enum, default_value_t)]
+    pub(crate) encoding: Encoding,
+
+    /// Save the call to this file before submitting so it can be replayed
+    #[clap(long)]
+    pub(crate) save_call: Option<PathBuf>,
+
+    /// Abort without sending the call when its estimated fee exceeds this amount of the fee asset
+    #[clap(long)]
+    pub(crate) max_fee: Option<u64>,
+
     /// The contract id of the market
     #[clap(long)]
     pub(crate) contract_id: String,
@@ -53,9 +66,10 @@ pub(crate) struct FulfillManyCommand {
 impl FulfillManyCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
         let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
 
-        if self.orders.len() == 0 {
+        if self.orders.is_empty() {
             anyhow::bail!("Invalid order array length == 0");
         }
 
@@ -68,18 +82,30 @@ impl FulfillManyCommand {
             LimitType::IOC => ContractLimitType::IOC,
             LimitType::FOK => ContractLimitType::FOK,
         };
-        let order_type = match self.order_type {
-            OrderType::Buy => ContractOrderType::Buy,
-            OrderType::Sell => ContractOrderType::Sell,
-        };
+        let order_type = ContractOrderType::from(self.order_type.clone());
 
         // Initial balance prior to contract call - used to calculate contract interaction cost
         let balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
         // Connect to the deployed contract via the rpc
-        let contract = SparkMarketContract::new(contract_id, wallet.clone()).await;
+        let contract = SparkMarketContract::new(contract_id, wallet.clone())
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
+
+        save_call(
+            &self.save_call,
+            &MarketCall::FulfillMany {
+                amount: self.amount,
+                order_type: order_type.clone(),
+                limit_type: limit_type.clone(),
+                price: self.price,
+                slippage: self.slippage,
+                orders: order_ids.clone(),
+            },
+        )?;
 
         let order_id = contract
             .fulfill_many(
@@ -95,11 +121,12 @@ impl FulfillManyCommand {
 
         // Balance post-call
         let new_balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
-        println!("\nContract call cost: {}", balance - new_balance);
-        println!("Order ID: {}", ContractId::from(order_id.0));
+        print_info(format!("\nContract call cost: {}", balance - new_balance));
+        let order_id = encode_b256(&order_id.0, &self.encoding);
+        print_result(format!("Order ID: {}", order_id), order_id);
 
         Ok(())
     }
@@ -1,7 +1,11 @@
-use crate::utils::{setup, validate_contract_id};
+use crate::utils::{
+    fee_confirmation, print_info, print_success, print_warning, save_call, setup,
+    validate_deployed_contract_id,
+};
 use clap::Args;
 use fuels::{accounts::ViewOnlyAccount, types::Bits256};
-use spark_market_sdk::SparkMarketContract;
+use spark_market_sdk::{MarketCall, MatchOutcome, SparkMarketContract};
+use std::path::PathBuf;
 
 #[derive(Args, Clone)]
 #[command(about = "Matches many orders")]
@@ -10,6 +14,18 @@ pub(crate) struct MatchManyCommand {
     #[clap(long)]
     pub(crate) orders: Vec<String>,
 
+    /// Simulate the match first and skip submitting it if the orders no longer cross
+    #[clap(long)]
+    pub(crate) check_first: bool,
+
+    /// Save the call to this file before submitting so it can be replayed
+    #[clap(long)]
+    pub(crate) save_call: Option<PathBuf>,
+
+    /// Abort without sending the call when its estimated fee exceeds this amount of the fee asset
+    #[clap(long)]
+    pub(crate) max_fee: Option<u64>,
+
     /// The contract id of the market
     #[clap(long)]
     pub(crate) contract_id: String,
@@ -23,7 +39,8 @@ pub(crate) struct MatchManyCommand {
 impl MatchManyCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
         let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
 
         if self.orders.len() < 2 {
             anyhow::bail!("Invalid order array length < 2");
@@ -36,23 +53,38 @@ impl MatchManyCommand {
 
         // Initial balance prior to contract call - used to calculate contract interaction cost
         let balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
         // Connect to the deployed contract via the rpc
-        let contract = SparkMarketContract::new(contract_id, wallet.clone()).await;
+        let contract = SparkMarketContract::new(contract_id, wallet.clone())
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
 
-        let _ = contract.match_order_many(order_ids).await?;
+        save_call(
+            &self.save_call,
+            &MarketCall::MatchOrderMany {
+                orders: order_ids.clone(),
+            },
+        )?;
+
+        if let MatchOutcome::NoLongerCrossing =
+            contract.match_orders(order_ids, self.check_first).await?
+        {
+            print_warning("Orders no longer cross, nothing was submitted");
+            return Ok(());
+        }
 
         // Balance post-call
         let new_balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
-        println!("Orders matched: {:?}", self.orders,);
+        print_success(format!("Orders matched: {:?}", self.orders));
 
         // TODO: replace println with tracing
-        println!("Contract call cost: {}", balance - new_balance);
+        print_info(format!("Contract call cost: {}", balance - new_balance));
 
         Ok(())
     }
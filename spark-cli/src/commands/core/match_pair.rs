@@ -1,7 +1,10 @@
-use crate::utils::{setup, validate_contract_id};
+use crate::utils::{
+    fee_confirmation, print_info, print_success, save_call, setup, validate_deployed_contract_id,
+};
 use clap::Args;
 use fuels::{accounts::ViewOnlyAccount, types::Bits256};
-use spark_market_sdk::SparkMarketContract;
+use spark_market_sdk::{MarketCall, SparkMarketContract};
+use std::path::PathBuf;
 
 #[derive(Args, Clone)]
 #[command(about = "Matches a pair of orders")]
@@ -10,6 +13,14 @@ pub(crate) struct MatchPairCommand {
     #[clap(long)]
     pub(crate) orders: Vec<String>,
 
+    /// Save the call to this file before submitting so it can be replayed
+    #[clap(long)]
+    pub(crate) save_call: Option<PathBuf>,
+
+    /// Abort without sending the call when its estimated fee exceeds this amount of the fee asset
+    #[clap(long)]
+    pub(crate) max_fee: Option<u64>,
+
     /// The contract id of the market
     #[clap(long)]
     pub(crate) contract_id: String,
@@ -23,7 +34,8 @@ pub(crate) struct MatchPairCommand {
 impl MatchPairCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
         let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
 
         if self.orders.len() != 2 {
             anyhow::bail!("Invalid order array length <> 2");
@@ -36,11 +48,22 @@ impl MatchPairCommand {
 
         // Initial balance prior to contract call - used to calculate contract interaction cost
         let balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
         // Connect to the deployed contract via the rpc
-        let contract = SparkMarketContract::new(contract_id, wallet.clone()).await;
+        let contract = SparkMarketContract::new(contract_id, wallet.clone())
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
+
+        save_call(
+            &self.save_call,
+            &MarketCall::MatchOrderPair {
+                order_id0: order_ids[0],
+                order_id1: order_ids[1],
+            },
+        )?;
 
         let _ = contract
             .match_order_pair(order_ids[0], order_ids[1])
@@ -48,16 +71,16 @@ impl MatchPairCommand {
 
         // Balance post-call
         let new_balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
-        println!(
+        print_success(format!(
             "Order pair matched: {} : {}",
             self.orders[0], self.orders[1]
-        );
+        ));
 
         // TODO: replace println with tracing
-        println!("Contract call cost: {}", balance - new_balance);
+        print_info(format!("Contract call cost: {}", balance - new_balance));
 
         Ok(())
     }
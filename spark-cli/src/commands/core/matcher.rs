@@ -0,0 +1,266 @@
+use crate::utils::{fee_confirmation, print_warning, setup, validate_deployed_contract_id};
+use clap::Args;
+use fuels::{
+    accounts::{Account, ViewOnlyAccount},
+    types::Bits256,
+};
+use spark_market_sdk::SparkMarketContract;
+use std::{collections::HashSet, future::Future, io::Write, time::Duration};
+
+#[derive(Args, Clone)]
+#[command(about = "Repeatedly matches the profitable crossed pairs among the provided orders")]
+pub(crate) struct MatcherCommand {
+    /// The b256 id of the order
+    #[clap(long)]
+    pub(crate) orders: Vec<String>,
+
+    /// Seconds to wait between scans of the book
+    #[clap(long, default_value_t = 5)]
+    pub(crate) poll_interval: u64,
+
+    /// Stop after this many scans instead of running until interrupted with Ctrl-C
+    #[clap(long)]
+    pub(crate) max_iterations: Option<usize>,
+
+    /// Quote units one unit of the fee asset is worth, used to weigh the fee of a match against
+    /// its matcher reward
+    #[clap(long, default_value_t = 0.0)]
+    pub(crate) fee_to_quote: f64,
+
+    /// Abort without sending the call when its estimated fee exceeds this amount of the fee asset
+    #[clap(long)]
+    pub(crate) max_fee: Option<u64>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+/// Running totals of a matcher session
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct MatcherTotals {
+    pub(crate) iterations: usize,
+    pub(crate) matches: usize,
+    pub(crate) base_traded: u64,
+    pub(crate) reward: u64,
+}
+
+impl MatcherCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+
+        if self.orders.is_empty() {
+            anyhow::bail!("Invalid order array length == 0");
+        }
+
+        let mut order_ids: Vec<Bits256> = Vec::new();
+        for order in self.orders.clone() {
+            order_ids.push(Bits256::from_hex_str(&order).expect("Invalid order_id"));
+        }
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet)
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
+
+        let shutdown = async {
+            let _ = tokio::signal::ctrl_c().await;
+        };
+        run_matcher(
+            &contract,
+            &order_ids,
+            self.fee_to_quote,
+            Duration::from_secs(self.poll_interval),
+            self.max_iterations,
+            &mut std::io::stdout(),
+            shutdown,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Scans `orders` for crossed pairs every `poll_interval` and matches the profitable ones, most
+/// profitable first, printing each match and the running totals. Stops after `max_iterations`
+/// scans or once `shutdown` resolves, finishing the match in flight
+pub(crate) async fn run_matcher<A: Account>(
+    contract: &SparkMarketContract<A>,
+    orders: &[Bits256],
+    fee_to_quote: f64,
+    poll_interval: Duration,
+    max_iterations: Option<usize>,
+    out: &mut impl Write,
+    shutdown: impl Future<Output = ()>,
+) -> anyhow::Result<MatcherTotals> {
+    tokio::pin!(shutdown);
+    let mut totals = MatcherTotals::default();
+    while max_iterations.map_or(true, |max| totals.iterations < max) {
+        if totals.iterations > 0 {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = tokio::time::sleep(poll_interval) => {}
+            }
+        }
+        totals.iterations += 1;
+
+        let crossed = contract.find_crossed_orders(orders.to_vec()).await?;
+        let profitable = contract
+            .profitable_matches(crossed, |fee| (fee as f64 * fee_to_quote) as u64)
+            .await?;
+
+        // A match changes both orders, so each order is matched once per scan and pairs it
+        // still crosses with are picked up by the next one
+        let mut matched = HashSet::new();
+        for pair in profitable {
+            let (id0, id1) = pair.order_ids;
+            if matched.contains(&id0.0) || matched.contains(&id1.0) {
+                continue;
+            }
+            matched.insert(id0.0);
+            matched.insert(id1.0);
+
+            let execution = match contract.match_order_pair_execution(id0, id1).await {
+                Ok(execution) => execution,
+                Err(error) => {
+                    // Another matcher may have filled or cancelled one of the orders
+                    print_warning(format!(
+                        "Match of 0x{} and 0x{} failed: {}",
+                        hex::encode(id0.0),
+                        hex::encode(id1.0),
+                        error
+                    ));
+                    continue;
+                }
+            };
+            totals.matches += 1;
+            totals.base_traded += execution.trade_size;
+            totals.reward += pair.reward;
+            writeln!(
+                out,
+                "Matched 0x{} with 0x{}: {} at {}, reward {}",
+                hex::encode(execution.sell_order_id.0),
+                hex::encode(execution.buy_order_id.0),
+                execution.trade_size,
+                execution.execution_price,
+                pair.reward
+            )?;
+        }
+
+        writeln!(
+            out,
+            "Scan {}: {} matches, {} base traded, {} reward in total",
+            totals.iterations, totals.matches, totals.base_traded, totals.reward
+        )?;
+        out.flush()?;
+    }
+
+    Ok(totals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuels::prelude::{
+        launch_custom_provider_and_get_wallets, AssetConfig, AssetId, WalletsConfig,
+    };
+    use spark_market_sdk::OrderType;
+
+    #[tokio::test]
+    async fn matches_the_crossed_pairs_of_the_book() -> anyhow::Result<()> {
+        let (base, quote) = (AssetId::from([1; 32]), AssetId::from([2; 32]));
+        let assets = [AssetId::default(), base, quote]
+            .into_iter()
+            .map(|id| AssetConfig {
+                id,
+                num_coins: 1,
+                coin_amount: 1_000_000_000_000,
+            })
+            .collect();
+        let mut wallets = launch_custom_provider_and_get_wallets(
+            WalletsConfig::new_multiple_assets(3, assets),
+            None,
+            None,
+        )
+        .await?;
+        let (buyer, seller, owner) = (
+            wallets.pop().unwrap(),
+            wallets.pop().unwrap(),
+            wallets.pop().unwrap(),
+        );
+
+        let market = SparkMarketContract::deploy(base, 9, quote, 6, owner, 9, 0x000400).await?;
+        // Orders pay the matcher a fee, so matching them is worth it when gas is free
+        market.set_matcher_fee(1_000).await?;
+
+        // Two crossed pairs of 1 base at 2 quote, and a buy below the asks that stays resting
+        let sell = market.with_account(&seller).await?;
+        sell.deposit(2_000_000_000, base).await?;
+        sell.deposit(10_000, quote).await?;
+        let buy = market.with_account(&buyer).await?;
+        buy.deposit(10_000_000, quote).await?;
+        let mut orders = Vec::new();
+        for _ in 0..2 {
+            orders.push(
+                sell.open_order(1_000_000_000, OrderType::Sell, 2_000_000_000)
+                    .await?
+                    .value
+                    .order_id,
+            );
+            orders.push(
+                buy.open_order(1_000_000_000, OrderType::Buy, 2_000_000_000)
+                    .await?
+                    .value
+                    .order_id,
+            );
+        }
+        let resting = buy
+            .open_order(1_000_000_000, OrderType::Buy, 1_000_000_000)
+            .await?
+            .value
+            .order_id;
+        orders.push(resting);
+
+        let mut out = Vec::new();
+        let totals = run_matcher(
+            &market,
+            &orders,
+            0.0,
+            Duration::from_millis(10),
+            Some(3),
+            &mut out,
+            std::future::pending(),
+        )
+        .await?;
+
+        assert_eq!(
+            totals,
+            MatcherTotals {
+                iterations: 3,
+                matches: 2,
+                base_traded: 2_000_000_000,
+                reward: 4_000,
+            }
+        );
+        let output = String::from_utf8(out)?;
+        assert_eq!(output.matches("Matched").count(), 2);
+        assert!(
+            output.ends_with("Scan 3: 2 matches, 2000000000 base traded, 4000 reward in total\n")
+        );
+
+        for order_id in &orders[..4] {
+            assert!(market.order(*order_id).await?.value.is_none());
+        }
+        assert!(market.order(resting).await?.value.is_some());
+
+        Ok(())
+    }
+}
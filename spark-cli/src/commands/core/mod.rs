@@ -1,12 +1,24 @@
+pub(crate) mod add_fee_exempt;
+pub(crate) mod cancel_expired;
+pub(crate) mod cancel_oldest;
 pub(crate) mod cancel_order;
+pub(crate) mod claim_rewards;
 pub(crate) mod cli;
+pub(crate) mod configure;
 pub(crate) mod deploy;
 pub(crate) mod deposit;
 pub(crate) mod fulfill_many;
 pub(crate) mod match_many;
 pub(crate) mod match_pair;
+pub(crate) mod matcher;
+pub(crate) mod open_batch;
 pub(crate) mod open_order;
+pub(crate) mod remove_fee_exempt;
+pub(crate) mod replay;
+pub(crate) mod set_dust_threshold;
 pub(crate) mod set_epoch;
 pub(crate) mod set_matcher_fee;
+pub(crate) mod set_max_price_deviation;
 pub(crate) mod set_protocol_fee;
+pub(crate) mod set_tick_size;
 pub(crate) mod withdraw;
@@ -0,0 +1,540 @@
+use crate::utils::{
+    encode_b256, fee_confirmation, print_info, print_result, setup, validate_deployed_contract_id,
+    Encoding,
+};
+use clap::Args;
+use fuels::{
+    accounts::{Account, ViewOnlyAccount},
+    types::{Bits256, Identity},
+};
+use serde_json::Value;
+use spark_market_sdk::{OrderType as ContractOrderType, SparkMarketContract};
+use std::{
+    collections::BTreeMap,
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Rows opened per transaction, one journal entry is written per landed chunk
+const CHUNK_SIZE: usize = 32;
+
+#[derive(Args, Clone)]
+#[command(about = "Open the orders listed in a JSON or CSV file")]
+pub(crate) struct OpenBatchCommand {
+    /// Path to the orders, a JSON array or CSV rows of order_type,price,amount[,client_id]
+    /// Ex. [{"order_type": "sell", "price": 70000000000000, "amount": 100000}]
+    #[clap(long)]
+    pub(crate) file: PathBuf,
+
+    /// Continue a batch that failed partway, skipping the rows its journal records as opened
+    #[clap(long)]
+    pub(crate) resume: bool,
+
+    /// The encoding used to print b256 values
+    #[clap(long, value_enum, default_value_t)]
+    pub(crate) encoding: Encoding,
+
+    /// Abort without sending a transaction when its estimated fee exceeds this amount of the fee
+    /// asset
+    #[clap(long)]
+    pub(crate) max_fee: Option<u64>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl OpenBatchCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        // Malformed rows are reported before anything is sent
+        let rows = BatchRow::from_file(&self.file)?;
+
+        let journal_path = Journal::path_for(&self.file);
+        let mut journal = Journal::load(&journal_path)?;
+        if !self.resume && !journal.is_empty() {
+            anyhow::bail!(
+                "A previous run of this batch opened {} row(s), see {}. Pass --resume to open the rest or delete the journal to start over",
+                journal.len(),
+                journal_path.display()
+            );
+        }
+        let pending: Vec<BatchRow> = rows
+            .iter()
+            .filter(|row| !journal.contains(&row.client_id))
+            .cloned()
+            .collect();
+        if self.resume {
+            print_info(format!(
+                "\nResuming: {} row(s) already opened, {} to open",
+                rows.len() - pending.len(),
+                pending.len()
+            ));
+        }
+
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+
+        // Initial balance prior to contract call - used to calculate contract interaction cost
+        let balance = wallet
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
+            .await?;
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet.clone())
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
+
+        let (base, quote) = required_deposits(&contract, &pending).await?;
+        print_info(format!(
+            "\nRequired deposit: {} base, {} quote",
+            base, quote
+        ));
+
+        open_rows(&contract, &pending, &mut journal, CHUNK_SIZE).await?;
+
+        // Balance post-call
+        let new_balance = wallet
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
+            .await?;
+
+        print_info(format!("Contract call cost: {}", balance - new_balance));
+        for row in &rows {
+            let order_id =
+                encode_b256(&journal.order_id(&row.client_id).unwrap().0, &self.encoding);
+            print_result(
+                format!(
+                    "Row {}: {:?} {} at {} -> {}",
+                    row.row, row.order_type, row.amount, row.price, order_id
+                ),
+                order_id,
+            );
+        }
+
+        // Every row is open, a later run of the same file starts a new batch
+        journal.remove()?;
+
+        Ok(())
+    }
+}
+
+/// An order of the batch file, `row` counts the file's orders from 1 and `client_id`, the row
+/// number unless the file names one, identifies the order across resumed runs
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct BatchRow {
+    pub(crate) row: usize,
+    pub(crate) client_id: String,
+    pub(crate) order_type: ContractOrderType,
+    pub(crate) price: u64,
+    pub(crate) amount: u64,
+}
+
+impl BatchRow {
+    /// Reads a `.json` file as JSON and any other file as CSV
+    pub(crate) fn from_file(path: &Path) -> anyhow::Result<Vec<Self>> {
+        let contents = std::fs::read_to_string(path)?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Self::from_json(&contents),
+            _ => Self::from_csv(&contents),
+        }
+    }
+
+    pub(crate) fn from_json(json: &str) -> anyhow::Result<Vec<Self>> {
+        let value: Value = serde_json::from_str(json)?;
+        let orders = value
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("Orders must be a JSON array"))?;
+
+        let fields = orders.iter().map(|order| {
+            let field = |name: &str| match order.get(name) {
+                Some(Value::String(value)) => value.clone(),
+                Some(value) => value.to_string(),
+                None => String::new(),
+            };
+            [
+                field("order_type"),
+                field("price"),
+                field("amount"),
+                field("client_id"),
+            ]
+        });
+        Self::validate(fields)
+    }
+
+    /// Rows of `order_type,price,amount[,client_id]`, a header row and blank lines are skipped
+    pub(crate) fn from_csv(csv: &str) -> anyhow::Result<Vec<Self>> {
+        let fields = csv
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with("order_type"))
+            .map(|line| {
+                let mut fields = line.split(',').map(|field| field.trim().to_string());
+                [(); 4].map(|_| fields.next().unwrap_or_default())
+            });
+        Self::validate(fields)
+    }
+
+    fn validate(rows: impl Iterator<Item = [String; 4]>) -> anyhow::Result<Vec<Self>> {
+        let mut valid = Vec::new();
+        let mut malformed = Vec::new();
+        let mut client_ids = BTreeMap::new();
+        for (index, [order_type, price, amount, client_id]) in rows.enumerate() {
+            let row = index + 1;
+            let client_id = match client_id.is_empty() {
+                true => row.to_string(),
+                false => client_id,
+            };
+            let client_id = match client_ids.get(&client_id) {
+                Some(first) => Err(format!(
+                    "client id {:?} already used by row {}",
+                    client_id, first
+                )),
+                None => {
+                    client_ids.insert(client_id.clone(), row);
+                    Ok(client_id)
+                }
+            };
+            let order_type = match order_type.to_lowercase().as_str() {
+                "buy" => Ok(ContractOrderType::Buy),
+                "sell" => Ok(ContractOrderType::Sell),
+                _ => Err(format!("invalid order type {:?}", order_type)),
+            };
+            let positive = |name: &str, value: &str| match value.parse::<u64>() {
+                Ok(0) => Err(format!("{} must be greater than zero", name)),
+                Ok(value) => Ok(value),
+                Err(_) => Err(format!("invalid {} {:?}", name, value)),
+            };
+            match (
+                order_type,
+                positive("price", &price),
+                positive("amount", &amount),
+                client_id,
+            ) {
+                (Ok(order_type), Ok(price), Ok(amount), Ok(client_id)) => valid.push(Self {
+                    row,
+                    client_id,
+                    order_type,
+                    price,
+                    amount,
+                }),
+                (order_type, price, amount, client_id) => {
+                    let errors: Vec<String> =
+                        [order_type.err(), price.err(), amount.err(), client_id.err()]
+                            .into_iter()
+                            .flatten()
+                            .collect();
+                    malformed.push(format!("row {}: {}", row, errors.join(", ")));
+                }
+            }
+        }
+
+        if !malformed.is_empty() {
+            anyhow::bail!("Malformed rows:\n  {}", malformed.join("\n  "));
+        }
+        if valid.is_empty() {
+            anyhow::bail!("Invalid order array length == 0");
+        }
+        Ok(valid)
+    }
+}
+
+/// The base and quote the rows lock in total, failing when the deposited balance does not
+/// cover them
+pub(crate) async fn required_deposits<A: Account>(
+    contract: &SparkMarketContract<A>,
+    rows: &[BatchRow],
+) -> anyhow::Result<(u64, u64)> {
+    let (mut base, mut quote) = (0_u64, 0_u64);
+    for row in rows {
+        let (_, deposit) = contract
+            .required_deposit(row.amount, row.order_type.clone(), row.price)
+            .await?;
+        match row.order_type {
+            ContractOrderType::Sell => base += deposit,
+            ContractOrderType::Buy => quote += deposit,
+        }
+    }
+
+    let user = Identity::Address(contract.get_instance().account().address().into());
+    let liquid = contract.account(user).await?.value.liquid;
+    if liquid.base < base || liquid.quote < quote {
+        anyhow::bail!(
+            "InsufficientBalance: the orders need {} base and {} quote, {} base and {} quote are deposited",
+            base,
+            quote,
+            liquid.base,
+            liquid.quote
+        );
+    }
+    Ok((base, quote))
+}
+
+/// Opens `rows` `chunk_size` at a time and journals each chunk once it lands, so a failure
+/// leaves the journal with exactly the rows that were opened
+pub(crate) async fn open_rows<A: Account>(
+    contract: &SparkMarketContract<A>,
+    rows: &[BatchRow],
+    journal: &mut Journal,
+    chunk_size: usize,
+) -> anyhow::Result<()> {
+    for chunk in rows.chunks(chunk_size) {
+        let order_ids = contract
+            .open_orders(
+                chunk
+                    .iter()
+                    .map(|row| (row.amount, row.order_type.clone(), row.price))
+                    .collect(),
+            )
+            .await?;
+        for (row, order_id) in chunk.iter().zip(order_ids) {
+            journal.record(&row.client_id, order_id)?;
+        }
+    }
+    Ok(())
+}
+
+/// The rows of a batch opened so far, by client id, kept next to the batch file as
+/// `client_id,order_id` lines
+pub(crate) struct Journal {
+    path: PathBuf,
+    opened: BTreeMap<String, Bits256>,
+}
+
+impl Journal {
+    /// `orders.csv` is journaled in `orders.csv.journal`
+    pub(crate) fn path_for(file: &Path) -> PathBuf {
+        let mut path = file.as_os_str().to_owned();
+        path.push(".journal");
+        PathBuf::from(path)
+    }
+
+    /// An empty journal when none was written yet
+    pub(crate) fn load(path: &Path) -> anyhow::Result<Self> {
+        let mut opened = BTreeMap::new();
+        if path.exists() {
+            for line in std::fs::read_to_string(path)?.lines() {
+                // Order ids never contain a comma, client ids might
+                let (client_id, order_id) = line
+                    .rsplit_once(',')
+                    .ok_or_else(|| anyhow::anyhow!("Invalid journal line {:?}", line))?;
+                opened.insert(client_id.to_string(), Bits256::from_hex_str(order_id)?);
+            }
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            opened,
+        })
+    }
+
+    pub(crate) fn record(&mut self, client_id: &str, order_id: Bits256) -> anyhow::Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{},{}", client_id, hex::encode(order_id.0))?;
+        self.opened.insert(client_id.to_string(), order_id);
+        Ok(())
+    }
+
+    pub(crate) fn contains(&self, client_id: &str) -> bool {
+        self.opened.contains_key(client_id)
+    }
+
+    pub(crate) fn order_id(&self, client_id: &str) -> Option<Bits256> {
+        self.opened.get(client_id).copied()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.opened.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.opened.is_empty()
+    }
+
+    pub(crate) fn remove(self) -> anyhow::Result<()> {
+        if self.path.exists() {
+            std::fs::remove_file(&self.path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::confirm_fee;
+    use fuels::prelude::{
+        launch_custom_provider_and_get_wallets, AssetConfig, AssetId, WalletsConfig,
+    };
+    use spark_market_sdk::{FeeConfirmation, FeeDeclined};
+    use std::sync::Arc;
+
+    async fn market() -> anyhow::Result<(SparkMarketContract, AssetId, AssetId)> {
+        let (base, quote) = (AssetId::from([1; 32]), AssetId::from([2; 32]));
+        let assets = [AssetId::default(), base, quote]
+            .into_iter()
+            .map(|id| AssetConfig {
+                id,
+                num_coins: 1,
+                coin_amount: 1_000_000_000_000,
+            })
+            .collect();
+        let wallet = launch_custom_provider_and_get_wallets(
+            WalletsConfig::new_multiple_assets(1, assets),
+            None,
+            None,
+        )
+        .await?
+        .pop()
+        .unwrap();
+
+        let market = SparkMarketContract::deploy(base, 8, quote, 6, wallet, 9, 0x000400).await?;
+        Ok((market, base, quote))
+    }
+
+    fn open_journal(name: &str) -> anyhow::Result<Journal> {
+        let dir = std::env::temp_dir().join(format!("spark-cli-open-batch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        Journal::load(&Journal::path_for(&dir.join(name)))
+    }
+
+    #[tokio::test]
+    async fn opens_every_row_of_the_file() -> anyhow::Result<()> {
+        let (market, base, quote) = market().await?;
+        market.deposit(1_000_000, base).await?;
+        market.deposit(1_000_000, quote).await?;
+
+        let rows = BatchRow::from_csv(
+            "order_type,price,amount
+            sell,72000000000000,100
+            sell,71000000000000,200
+            buy,69000000000000,300
+            buy,68000000000000,400
+            buy,67000000000000,500",
+        )?;
+        assert_eq!(rows.len(), 5);
+
+        // Sells lock their base amount, buys the quote they are worth
+        assert_eq!(
+            required_deposits(&market, &rows).await?,
+            (300, 300 * 690 + 400 * 680 + 500 * 670)
+        );
+        let mut journal = open_journal("orders.csv")?;
+        open_rows(&market, &rows, &mut journal, CHUNK_SIZE).await?;
+        assert_eq!(journal.len(), 5);
+        for row in &rows {
+            let order_id = journal.order_id(&row.client_id).unwrap();
+            let order = market.order(order_id).await?.value.unwrap();
+            assert_eq!(order.order_type, row.order_type);
+            assert_eq!(order.price, row.price);
+            assert_eq!(order.amount, row.amount);
+        }
+        journal.remove()?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn resumes_after_a_partial_failure() -> anyhow::Result<()> {
+        let (market, base, _) = market().await?;
+        let user = Identity::Address(market.get_instance().account().address().into());
+        let rows = BatchRow::from_csv(
+            "sell,71000000000000,100,a
+            sell,72000000000000,100,b
+            sell,73000000000000,100,c
+            sell,74000000000000,100,d",
+        )?;
+
+        // Only the first chunk of two rows is covered, the second fails to open
+        market.deposit(200, base).await?;
+        let mut journal = open_journal("resume.csv")?;
+        let error = open_rows(&market, &rows, &mut journal, 2)
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", error).contains("InsufficientBalance"));
+        assert_eq!(market.user_orders(user).await?.value.len(), 2);
+
+        // A rerun reads the opened rows back from the journal and opens the rest
+        let mut journal = open_journal("resume.csv")?;
+        assert_eq!(journal.len(), 2);
+        assert!(journal.contains("a") && journal.contains("b"));
+        let pending: Vec<BatchRow> = rows
+            .iter()
+            .filter(|row| !journal.contains(&row.client_id))
+            .cloned()
+            .collect();
+        assert_eq!(pending.len(), 2);
+        market.deposit(200, base).await?;
+        open_rows(&market, &pending, &mut journal, 2).await?;
+
+        // Every row is open exactly once
+        let orders = market.user_orders(user).await?.value;
+        assert_eq!(orders.len(), 4);
+        for row in &rows {
+            assert!(orders.contains(&journal.order_id(&row.client_id).unwrap()));
+        }
+        journal.remove()?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn declined_fee_opens_no_rows() -> anyhow::Result<()> {
+        let (market, base, _) = market().await?;
+        market.deposit(1_000, base).await?;
+        let user = Identity::Address(market.get_instance().account().address().into());
+
+        // The same prompt the --confirm-fee flag installs, answered with no
+        let declining: FeeConfirmation = Arc::new(|estimated: u64| {
+            confirm_fee(estimated, &mut "no\n".as_bytes(), &mut std::io::sink())
+        });
+        let market = market.with_fee_confirmation(Some(declining));
+
+        let rows = BatchRow::from_csv("sell,72000000000000,100\nsell,71000000000000,200")?;
+        let mut journal = open_journal("declined.csv")?;
+        let error = open_rows(&market, &rows, &mut journal, CHUNK_SIZE)
+            .await
+            .unwrap_err();
+
+        assert!(error.downcast_ref::<FeeDeclined>().is_some());
+        assert!(journal.is_empty());
+        assert!(market.user_orders(user).await?.value.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn reports_malformed_rows() {
+        let error = BatchRow::from_json(
+            r#"[
+                {"order_type": "sell", "price": 70000000000000, "amount": 100},
+                {"order_type": "hold", "price": 70000000000000, "amount": 100},
+                {"order_type": "buy", "price": 0, "amount": -5}
+            ]"#,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(error.starts_with("Malformed rows"));
+        assert!(!error.contains("row 1"));
+        assert!(error.contains(r#"row 2: invalid order type "hold""#));
+        assert!(error.contains(r#"row 3: price must be greater than zero, invalid amount "-5""#));
+
+        let error = BatchRow::from_csv("sell,70000000000000,100\nsell,abc")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains(r#"row 2: invalid price "abc", invalid amount """#));
+
+        let error = BatchRow::from_csv("sell,70000000000000,100,x\nbuy,69000000000000,100,x")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains(r#"row 2: client id "x" already used by row 1"#));
+    }
+}
@@ -1,9 +1,15 @@
-use crate::utils::{setup, validate_contract_id, /*AssetType,*/ OrderType};
+use crate::utils::{
+    encode_b256, fee_confirmation, print_info, print_result, save_call, setup,
+    validate_deployed_contract_id, /*AssetType,*/ CallCost, Encoding, OrderType,
+};
 use clap::Args;
-use fuels::{accounts::ViewOnlyAccount, types::ContractId};
+use fuels::{accounts::ViewOnlyAccount, types::Bits256};
 use spark_market_sdk::{
-    OrderType as ContractOrderType, /*AssetType as ContractAssetType,*/ SparkMarketContract,
+    DepositSource, MarketCall,
+    OrderType as ContractOrderType, /*AssetType as ContractAssetType,*/
+    SparkMarketContract,
 };
+use std::path::PathBuf;
 
 #[derive(Args, Clone)]
 #[command(about = "Opens a new order")]
@@ -24,6 +30,39 @@ pub(crate) struct OpenCommand {
     #[clap(long)]
     pub(crate) price: u64,
 
+    /// The encoding used to print b256 values
+    #[clap(long, value_enum, default_value_t)]
+    pub(crate) encoding: Encoding,
+
+    /// Reject the order instead of opening it if it would match any of the resting orders
+    #[clap(long, conflicts_with = "save_call")]
+    pub(crate) post_only: bool,
+
+    /// The b256 ids of the resting orders a post-only order is checked against. Only these are
+    /// checked, the order may still cross a resting order left out of the list
+    #[clap(long, requires = "post_only")]
+    pub(crate) resting_orders: Vec<String>,
+
+    /// The last block at which the order is valid, after which any keeper may cancel it
+    #[clap(long, conflicts_with_all = ["post_only", "save_call"])]
+    pub(crate) deadline: Option<u32>,
+
+    /// The quote amount paid to the keeper that cancels the order after the deadline
+    #[clap(long, default_value_t = 0, requires = "deadline")]
+    pub(crate) keeper_reward: u64,
+
+    /// Attach exactly the funds the order needs from the wallet instead of using the deposited balance
+    #[clap(long, conflicts_with_all = ["post_only", "deadline", "save_call"])]
+    pub(crate) attach_only: bool,
+
+    /// Save the call to this file before submitting so it can be replayed
+    #[clap(long)]
+    pub(crate) save_call: Option<PathBuf>,
+
+    /// Abort without sending the call when its estimated fee exceeds this amount of the fee asset
+    #[clap(long)]
+    pub(crate) max_fee: Option<u64>,
+
     /// The contract id of the market
     #[clap(long)]
     pub(crate) contract_id: String,
@@ -37,33 +76,90 @@ pub(crate) struct OpenCommand {
 impl OpenCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
         let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
 
-        let order_type = match self.order_type {
-            OrderType::Buy => ContractOrderType::Buy,
-            OrderType::Sell => ContractOrderType::Sell,
-        };
+        let order_type = ContractOrderType::from(self.order_type.clone());
 
         // Initial balance prior to contract call - used to calculate contract interaction cost
-        let balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
-            .await?;
+        let fee_asset = *wallet.try_provider()?.base_asset_id();
+        let balance = wallet.get_asset_balance(&fee_asset).await?;
 
         // Connect to the deployed contract via the rpc
-        let contract = SparkMarketContract::new(contract_id, wallet.clone()).await;
+        let contract = SparkMarketContract::new(contract_id, wallet.clone())
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
 
-        let order_id = contract
-            .open_order(self.amount, order_type.clone(), self.price)
-            .await?
-            .value;
+        save_call(
+            &self.save_call,
+            &MarketCall::OpenOrder {
+                amount: self.amount,
+                order_type: order_type.clone(),
+                price: self.price,
+            },
+        )?;
+
+        let (order_id, deposited, gas_used) = if let Some(deadline) = self.deadline {
+            let response = contract
+                .open_order_with_deadline(
+                    self.amount,
+                    order_type.clone(),
+                    self.price,
+                    deadline,
+                    self.keeper_reward,
+                )
+                .await?;
+            (response.value, None, response.gas_used)
+        } else if self.post_only {
+            let mut resting_orders: Vec<Bits256> = Vec::new();
+            for order in &self.resting_orders {
+                resting_orders.push(Bits256::from_hex_str(order).map_err(|error| {
+                    anyhow::anyhow!("Invalid resting order id {}: {}", order, error)
+                })?);
+            }
+            let response = contract
+                .open_order_post_only(self.amount, order_type.clone(), self.price, resting_orders)
+                .await?;
+            (response.value, None, response.gas_used)
+        } else {
+            let source = match self.attach_only {
+                true => DepositSource::Attached,
+                false => DepositSource::Balance,
+            };
+            let response = contract
+                .open_order_from(self.amount, order_type.clone(), self.price, source)
+                .await?;
+            let opened = response.value;
+            (
+                opened.order_id,
+                Some((opened.base_deposited, opened.quote_deposited)),
+                response.gas_used,
+            )
+        };
 
         // Balance post-call
-        let new_balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
-            .await?;
+        let new_balance = wallet.get_asset_balance(&fee_asset).await?;
 
-        println!("\nContract call cost: {}", balance - new_balance);
-        println!("Order ID: {}", ContractId::from(order_id.0));
+        // Funds attached from the wallet leave it with the fee, so a balance difference in the
+        // attached asset would count the deposit as cost
+        let cost = match self.attach_only {
+            true => {
+                let config = contract.config().await?;
+                let attached_asset = match order_type {
+                    ContractOrderType::Sell => config.base_asset,
+                    ContractOrderType::Buy => config.quote_asset,
+                };
+                CallCost::new(&fee_asset, &attached_asset, balance, new_balance, gas_used)
+            }
+            false => CallCost::Fee(balance - new_balance),
+        };
+        print_info(format!("\n{}", cost));
+        let order_id = encode_b256(&order_id.0, &self.encoding);
+        print_result(format!("Order ID: {}", order_id), order_id);
+        if let Some((base, quote)) = deposited {
+            print_info(format!("Deposit consumed: {} base, {} quote", base, quote));
+        }
 
         Ok(())
     }
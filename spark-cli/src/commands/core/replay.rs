@@ -0,0 +1,74 @@
+use crate::utils::{
+    encode_b256, fee_confirmation, print_info, print_result, print_success, setup,
+    validate_deployed_contract_id, Encoding,
+};
+use clap::Args;
+use fuels::accounts::ViewOnlyAccount;
+use spark_market_sdk::{MarketCall, SparkMarketContract};
+use std::path::PathBuf;
+
+#[derive(Args, Clone)]
+#[command(about = "Resubmits a saved call with a higher gas limit")]
+pub(crate) struct ReplayCommand {
+    /// The file the call was saved to with --save-call
+    #[clap(long)]
+    pub(crate) call_file: PathBuf,
+
+    /// The factor applied to the estimated gas of the call
+    #[clap(long, default_value_t = 1.5)]
+    pub(crate) gas_multiplier: f64,
+
+    /// The encoding used to print b256 values
+    #[clap(long, value_enum, default_value_t)]
+    pub(crate) encoding: Encoding,
+
+    /// Abort without sending the call when its estimated fee exceeds this amount of the fee asset
+    #[clap(long)]
+    pub(crate) max_fee: Option<u64>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl ReplayCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+
+        let call = MarketCall::from_json(&std::fs::read_to_string(&self.call_file)?)?;
+
+        // Initial balance prior to contract call - used to calculate contract interaction cost
+        let balance = wallet
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
+            .await?;
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet.clone())
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
+
+        let order_id = contract.replay(&call, self.gas_multiplier).await?;
+
+        // Balance post-call
+        let new_balance = wallet
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
+            .await?;
+
+        print_success(format!("\nReplayed: {:?}", call));
+        print_info(format!("Contract call cost: {}", balance - new_balance));
+        if let Some(order_id) = order_id {
+            let order_id = encode_b256(&order_id.0, &self.encoding);
+            print_result(format!("Order ID: {}", order_id), order_id);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use crate::utils::{
+    fee_confirmation, print_info, print_success, setup, validate_deployed_contract_id,
+};
+use clap::Args;
+use fuels::accounts::ViewOnlyAccount;
+use spark_market_sdk::SparkMarketContract;
+
+#[derive(Args, Clone)]
+#[command(about = "Change the dust threshold below which partially matched orders are cancelled")]
+pub(crate) struct SetDustThresholdCommand {
+    /// The threshold to set in base asset units
+    #[clap(long)]
+    pub(crate) amount: u64,
+
+    /// Abort without sending the call when its estimated fee exceeds this amount of the fee asset
+    #[clap(long)]
+    pub(crate) max_fee: Option<u64>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl SetDustThresholdCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+
+        // Initial balance prior to contract call - used to calculate contract interaction cost
+        let balance = wallet
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
+            .await?;
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet.clone())
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
+
+        let _ = contract.set_dust_threshold(self.amount).await?;
+
+        // Balance post-call
+        let new_balance = wallet
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
+            .await?;
+
+        print_success(format!(
+            "\nThe dust threshold has been set to: {}",
+            self.amount
+        ));
+        print_info(format!("Contract call cost: {}", balance - new_balance));
+
+        Ok(())
+    }
+}
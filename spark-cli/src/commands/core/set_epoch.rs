@@ -1,4 +1,6 @@
-use crate::utils::{setup, validate_contract_id};
+use crate::utils::{
+    fee_confirmation, print_info, print_success, setup, validate_deployed_contract_id,
+};
 use clap::Args;
 use fuels::accounts::ViewOnlyAccount;
 use spark_market_sdk::SparkMarketContract;
@@ -14,6 +16,10 @@ pub(crate) struct SetEpochCommand {
     #[clap(long)]
     pub(crate) epoch_duration: u64,
 
+    /// Abort without sending the call when its estimated fee exceeds this amount of the fee asset
+    #[clap(long)]
+    pub(crate) max_fee: Option<u64>,
+
     /// The contract id of the market
     #[clap(long)]
     pub(crate) contract_id: String,
@@ -27,28 +33,32 @@ pub(crate) struct SetEpochCommand {
 impl SetEpochCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
         let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
 
         // Initial balance prior to contract call - used to calculate contract interaction cost
         let balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
         // Connect to the deployed contract via the rpc
-        let contract = SparkMarketContract::new(contract_id, wallet.clone()).await;
+        let contract = SparkMarketContract::new(contract_id, wallet.clone())
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
 
         let _ = contract.set_epoch(self.epoch, self.epoch_duration).await?;
 
         // Balance post-deployment
         let new_balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
-        println!(
+        print_success(format!(
             "\nThe epoch and duration have been set to: {}, {}",
             self.epoch, self.epoch_duration
-        );
-        println!("Contract call cost: {}", balance - new_balance);
+        ));
+        print_info(format!("Contract call cost: {}", balance - new_balance));
 
         Ok(())
     }
@@ -1,4 +1,6 @@
-use crate::utils::{setup, validate_contract_id};
+use crate::utils::{
+    fee_confirmation, parse_amount, print_info, print_success, setup, validate_deployed_contract_id,
+};
 use clap::Args;
 use fuels::accounts::ViewOnlyAccount;
 use spark_market_sdk::SparkMarketContract;
@@ -7,8 +9,21 @@ use spark_market_sdk::SparkMarketContract;
 #[command(about = "Change the matcher fee for the market")]
 pub(crate) struct SetMatcherFeeCommand {
     /// The fee to set
+    #[clap(
+        long,
+        required_unless_present = "human_amount",
+        conflicts_with = "human_amount"
+    )]
+    pub(crate) amount: Option<u64>,
+
+    /// The fee to set in quote asset units, scaled by the quote decimals
+    /// Ex. 0.01
+    #[clap(long)]
+    pub(crate) human_amount: Option<String>,
+
+    /// Abort without sending the call when its estimated fee exceeds this amount of the fee asset
     #[clap(long)]
-    pub(crate) amount: u64,
+    pub(crate) max_fee: Option<u64>,
 
     /// The contract id of the market
     #[clap(long)]
@@ -23,25 +38,43 @@ pub(crate) struct SetMatcherFeeCommand {
 impl SetMatcherFeeCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
         let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
 
         // Initial balance prior to contract call - used to calculate contract interaction cost
         let balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
         // Connect to the deployed contract via the rpc
-        let contract = SparkMarketContract::new(contract_id, wallet.clone()).await;
+        let contract = SparkMarketContract::new(contract_id, wallet.clone())
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
+
+        let amount = match (&self.human_amount, self.amount) {
+            (Some(human_amount), _) => {
+                let quote_decimals = contract.config().await?.quote_decimals;
+                let amount = parse_amount(human_amount, quote_decimals)?;
+                print_info(format!(
+                    "\n{} at {} quote decimals is a raw fee of {}",
+                    human_amount, quote_decimals, amount
+                ));
+                amount
+            }
+            (None, Some(amount)) => amount,
+            (None, None) => anyhow::bail!("Either --amount or --human-amount is required"),
+        };
 
-        let _ = contract.set_matcher_fee(self.amount).await?;
+        let _ = contract.set_matcher_fee(amount).await?;
 
         // Balance post-deployment
         let new_balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
-        println!("\nThe matcher fee has been set to: {}", self.amount);
-        println!("Contract call cost: {}", balance - new_balance);
+        print_success(format!("\nThe matcher fee has been set to: {}", amount));
+        print_info(format!("Contract call cost: {}", balance - new_balance));
 
         Ok(())
     }
@@ -0,0 +1,61 @@
+use crate::utils::{
+    fee_confirmation, print_info, print_success, setup, validate_deployed_contract_id,
+};
+use clap::Args;
+use fuels::accounts::ViewOnlyAccount;
+use spark_market_sdk::SparkMarketContract;
+
+#[derive(Args, Clone)]
+#[command(about = "Change the maximum deviation of new order prices from the last traded price")]
+pub(crate) struct SetMaxPriceDeviationCommand {
+    /// The maximum deviation in basis points, 0 disables the price band
+    #[clap(long)]
+    pub(crate) bps: u64,
+
+    /// Abort without sending the call when its estimated fee exceeds this amount of the fee asset
+    #[clap(long)]
+    pub(crate) max_fee: Option<u64>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl SetMaxPriceDeviationCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+
+        // Initial balance prior to contract call - used to calculate contract interaction cost
+        let balance = wallet
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
+            .await?;
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet.clone())
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
+
+        let _ = contract.set_max_price_deviation_bps(self.bps).await?;
+
+        // Balance post-call
+        let new_balance = wallet
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
+            .await?;
+
+        print_success(format!(
+            "\nThe maximum price deviation has been set to: {} bps",
+            self.bps
+        ));
+        print_info(format!("Contract call cost: {}", balance - new_balance));
+
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
-use crate::utils::{setup, validate_contract_id};
+use crate::utils::{
+    fee_confirmation, print_info, print_success, setup, validate_deployed_contract_id,
+};
 use clap::Args;
 use fuels::accounts::ViewOnlyAccount;
 use spark_market_sdk::{ProtocolFee, SparkMarketContract};
@@ -10,6 +12,10 @@ pub(crate) struct SetProtocolFeeCommand {
     #[clap(long)]
     pub(crate) fee: Vec<String>,
 
+    /// Abort without sending the call when its estimated fee exceeds this amount of the fee asset
+    #[clap(long)]
+    pub(crate) max_fee: Option<u64>,
+
     /// The contract id of the market
     #[clap(long)]
     pub(crate) contract_id: String,
@@ -23,7 +29,8 @@ pub(crate) struct SetProtocolFeeCommand {
 impl SetProtocolFeeCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
         let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
 
         let mut protocol_fee: Vec<ProtocolFee> = Vec::new();
         for fee in self.fee.clone() {
@@ -43,21 +50,24 @@ impl SetProtocolFeeCommand {
 
         // Initial balance prior to contract call - used to calculate contract interaction cost
         let balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
         // Connect to the deployed contract via the rpc
-        let contract = SparkMarketContract::new(contract_id, wallet.clone()).await;
+        let contract = SparkMarketContract::new(contract_id, wallet.clone())
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
 
         let _ = contract.set_protocol_fee(protocol_fee).await?;
 
         // Balance post-deployment
         let new_balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
-        println!("\nThe global fee has been set to: {:?}", self.fee);
-        println!("Contract call cost: {}", balance - new_balance);
+        print_success(format!("\nThe global fee has been set to: {:?}", self.fee));
+        print_info(format!("Contract call cost: {}", balance - new_balance));
 
         Ok(())
     }
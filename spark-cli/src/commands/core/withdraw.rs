@@ -1,6 +1,11 @@
-use crate::utils::{setup, validate_contract_id, AssetType};
+use crate::utils::{
+    fee_confirmation, print_info, print_result, setup, validate_deployed_contract_id, AssetType,
+    CallCost,
+};
 use clap::Args;
+use fuels::accounts::Account;
 use fuels::accounts::ViewOnlyAccount;
+use fuels::types::Identity;
 use spark_market_sdk::{AssetType as ContractAssetType, SparkMarketContract};
 
 #[derive(Args, Clone)]
@@ -14,6 +19,15 @@ pub(crate) struct WithdrawCommand {
     #[clap(long)]
     pub(crate) asset_type: AssetType,
 
+    /// Only withdraw the liquid balance, refusing amounts locked behind open orders before
+    /// sending the call instead of letting it revert
+    #[clap(long)]
+    pub(crate) price_protection: bool,
+
+    /// Abort without sending the call when its estimated fee exceeds this amount of the fee asset
+    #[clap(long)]
+    pub(crate) max_fee: Option<u64>,
+
     /// The contract id of the market
     #[clap(long)]
     pub(crate) contract_id: String,
@@ -27,43 +41,191 @@ pub(crate) struct WithdrawCommand {
 impl WithdrawCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
         let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
-
-        let asset_type = match self.asset_type {
-            AssetType::Base => ContractAssetType::Base,
-            AssetType::Quote => ContractAssetType::Quote,
-        };
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
 
-        // Initial balance prior to contract call - used to calculate contract interaction cost
-        let balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
-            .await?;
+        let asset_type = ContractAssetType::from(self.asset_type.clone());
 
         // Connect to the deployed contract via the rpc
-        let contract = SparkMarketContract::new(contract_id, wallet.clone()).await;
-        let config = contract.config().await?.value;
-        let asset = if asset_type == ContractAssetType::Base {
-            config.0
-        } else {
-            config.2
-        };
-        let asset_balance = wallet.get_asset_balance(&asset).await?;
-
-        let _ = contract.withdraw(self.amount, asset_type.clone()).await?;
-
-        // Balance post-call
-        let new_balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
-            .await?;
-        let new_asset_balance = wallet.get_asset_balance(&asset).await?;
+        let contract = SparkMarketContract::new(contract_id, wallet.clone())
+            .await
+            .with_max_fee(self.max_fee)
+            .with_fee_confirmation(fee_confirmation());
+
+        if self.price_protection {
+            let owner = Identity::Address(wallet.address().into());
+            check_liquid(&contract, owner, self.amount, asset_type.clone()).await?;
+        }
 
-        println!("Contract call cost: {}", balance - new_balance);
-        println!(
-            "Withdrawn {} amount of {:?} asset",
-            new_asset_balance - asset_balance,
-            asset_type.clone()
+        let (cost, withdrawn) =
+            withdraw(&contract, &wallet, self.amount, asset_type.clone()).await?;
+
+        print_info(cost);
+        print_result(
+            format!("Withdrawn {} amount of {:?} asset", withdrawn, asset_type),
+            withdrawn,
         );
 
         Ok(())
     }
 }
+
+/// Fails when `amount` exceeds the liquid balance of the asset, naming the amount locked in orders
+async fn check_liquid<A: Account>(
+    contract: &SparkMarketContract<A>,
+    owner: Identity,
+    amount: u64,
+    asset_type: ContractAssetType,
+) -> anyhow::Result<()> {
+    let account = contract.account(owner).await?.value;
+    let (liquid, locked) = match asset_type {
+        ContractAssetType::Base => (account.liquid.base, account.locked.base),
+        ContractAssetType::Quote => (account.liquid.quote, account.locked.quote),
+    };
+    if amount > liquid {
+        anyhow::bail!(
+            "Cannot withdraw {} of {:?}: only {} is liquid and {} is locked in open orders, cancel orders first to unlock it",
+            amount,
+            asset_type,
+            liquid,
+            locked
+        );
+    }
+    Ok(())
+}
+
+/// Withdraws from the market and reports the call cost and the amount that reached the wallet
+async fn withdraw<A: Account>(
+    contract: &SparkMarketContract<A>,
+    wallet: &A,
+    amount: u64,
+    asset_type: ContractAssetType,
+) -> anyhow::Result<(CallCost, u64)> {
+    let fee_asset = *wallet.try_provider()?.base_asset_id();
+    let config = contract.config().await?;
+    let asset = if asset_type == ContractAssetType::Base {
+        config.base_asset
+    } else {
+        config.quote_asset
+    };
+
+    // Initial balances prior to contract call - used to calculate contract interaction cost
+    let fee_balance = wallet.get_asset_balance(&fee_asset).await?;
+    let asset_balance = wallet.get_asset_balance(&asset).await?;
+
+    let response = contract.withdraw(amount, asset_type).await?;
+
+    // Balances post-call
+    let new_fee_balance = wallet.get_asset_balance(&fee_asset).await?;
+    let new_asset_balance = wallet.get_asset_balance(&asset).await?;
+
+    let cost = CallCost::new(
+        &fee_asset,
+        &asset,
+        fee_balance,
+        new_fee_balance,
+        response.gas_used,
+    );
+    // The fee is paid out of the withdrawn coins when the market asset is the fee asset
+    let withdrawn = match cost {
+        CallCost::Fee(_) => new_asset_balance - asset_balance,
+        CallCost::Gas(_) => amount,
+    };
+
+    Ok((cost, withdrawn))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuels::prelude::{
+        launch_custom_provider_and_get_wallets, AssetConfig, AssetId, WalletsConfig,
+    };
+    use spark_market_sdk::OrderType;
+
+    #[tokio::test]
+    async fn cost_is_not_mixed_with_a_withdrawal_of_the_fee_asset() -> anyhow::Result<()> {
+        // The market quotes in the asset that also pays for gas
+        let (base, quote) = (AssetId::from([1; 32]), AssetId::default());
+        let assets = [quote, base]
+            .into_iter()
+            .map(|id| AssetConfig {
+                id,
+                num_coins: 1,
+                coin_amount: 1_000_000_000_000,
+            })
+            .collect();
+        let wallet = launch_custom_provider_and_get_wallets(
+            WalletsConfig::new_multiple_assets(1, assets),
+            None,
+            None,
+        )
+        .await?
+        .pop()
+        .unwrap();
+
+        let market =
+            SparkMarketContract::deploy(base, 9, quote, 9, wallet.clone(), 9, 0x000400).await?;
+        let amount = 1_000_000;
+        market.deposit(amount, quote).await?;
+        market.deposit(amount, base).await?;
+
+        let (cost, withdrawn) =
+            withdraw(&market, &wallet, amount, ContractAssetType::Quote).await?;
+        assert!(matches!(cost, CallCost::Gas(gas) if gas > 0));
+        assert_eq!(withdrawn, amount);
+
+        // Other assets still report the fee from the balance difference
+        let (cost, withdrawn) = withdraw(&market, &wallet, amount, ContractAssetType::Base).await?;
+        assert!(matches!(cost, CallCost::Fee(_)));
+        assert_eq!(withdrawn, amount);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn price_protection_refuses_locked_collateral() -> anyhow::Result<()> {
+        let (base, quote) = (AssetId::from([1; 32]), AssetId::from([2; 32]));
+        let assets = [AssetId::default(), base, quote]
+            .into_iter()
+            .map(|id| AssetConfig {
+                id,
+                num_coins: 1,
+                coin_amount: 1_000_000_000_000,
+            })
+            .collect();
+        let wallet = launch_custom_provider_and_get_wallets(
+            WalletsConfig::new_multiple_assets(1, assets),
+            None,
+            None,
+        )
+        .await?
+        .pop()
+        .unwrap();
+        let owner = Identity::Address(wallet.address().into());
+
+        let market =
+            SparkMarketContract::deploy(base, 9, quote, 6, wallet.clone(), 9, 0x000400).await?;
+        market.deposit(3_000_000_000, base).await?;
+        // Locks 1 of the 3 deposited base
+        market
+            .open_order(1_000_000_000, OrderType::Sell, 2_000_000_000)
+            .await?;
+
+        let error = check_liquid(&market, owner, 2_500_000_000, ContractAssetType::Base)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("only 2000000000 is liquid"));
+        assert!(error.contains("1000000000 is locked"));
+        assert!(error.contains("cancel orders first"));
+
+        // The liquid balance can still be withdrawn in full
+        check_liquid(&market, owner, 2_000_000_000, ContractAssetType::Base).await?;
+        let (_, withdrawn) =
+            withdraw(&market, &wallet, 2_000_000_000, ContractAssetType::Base).await?;
+        assert_eq!(withdrawn, 2_000_000_000);
+
+        Ok(())
+    }
+}
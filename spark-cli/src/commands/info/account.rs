@@ -1,9 +1,8 @@
-use crate::utils::{setup, validate_contract_id, AccountType};
+use crate::utils::{output_writer, setup, validate_deployed_contract_id, AccountType};
 use clap::Args;
 use fuels::accounts::ViewOnlyAccount;
-use fuels::types::{Address, ContractId, Identity};
 use spark_market_sdk::SparkMarketContract;
-use std::str::FromStr;
+use std::{io::Write, path::PathBuf};
 
 #[derive(Args, Clone)]
 #[command(about = "Query the account info for a user")]
@@ -16,6 +15,10 @@ pub(crate) struct AccountCommand {
     #[clap(long)]
     pub(crate) account_type: AccountType,
 
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
     /// The contract id of the market
     #[clap(long)]
     pub(crate) contract_id: String,
@@ -29,28 +32,27 @@ pub(crate) struct AccountCommand {
 impl AccountCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
         let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
 
         // Connect to the deployed contract via the rpc
         let contract = SparkMarketContract::new(contract_id, wallet.clone()).await;
 
-        let account = match self.account_type {
-            AccountType::Address => {
-                let address = Address::from_str(&self.account_id).expect("Invalid address");
-                contract.account(Identity::Address(address)).await?.value
-            }
-            AccountType::Contract => {
-                let address = ContractId::from_str(&self.account_id).expect("Invalid contract id");
-                contract.account(Identity::ContractId(address)).await?.value
-            }
-        };
+        let account = contract
+            .account(self.account_type.identity(&self.account_id)?)
+            .await?
+            .value;
 
         let balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
-        println!("\nContract base asset balance: {}", balance);
 
-        println!("\n{:#?}", account);
+        let mut out = output_writer(&self.output)?;
+        writeln!(out, "\nContract base asset balance: {}", balance)?;
+
+        writeln!(out, "\n{:#?}", account)?;
+
+        out.flush()?;
 
         Ok(())
     }
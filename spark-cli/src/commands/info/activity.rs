@@ -0,0 +1,76 @@
+use crate::utils::{output_writer, setup, validate_deployed_contract_id};
+use clap::Args;
+use fuels::accounts::ViewOnlyAccount;
+use spark_market_sdk::SparkMarketContract;
+use std::{io::Write, path::PathBuf};
+
+#[derive(Args, Clone)]
+#[command(about = "Report whether the market has traded recently and its trades per block")]
+pub(crate) struct ActivityCommand {
+    /// The number of latest blocks a trade must fall into for the market to count as active
+    #[clap(long, default_value_t = 1000)]
+    pub(crate) within: u32,
+
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl ActivityCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        if self.within == 0 {
+            anyhow::bail!("Within must be greater than zero");
+        }
+
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+        let tip = wallet.try_provider()?.latest_block_height().await?;
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet).await;
+
+        // Only the window is scanned, the market counts as active if it holds a trade
+        let last_trade = contract
+            .last_trade_block(tip.saturating_sub(self.within - 1))
+            .await?;
+
+        let mut out = output_writer(&self.output)?;
+        match last_trade {
+            Some(block) => writeln!(out, "\nLast trade block: {}", block)?,
+            None => writeln!(
+                out,
+                "\nLast trade block: none in the last {} blocks",
+                self.within
+            )?,
+        }
+        writeln!(
+            out,
+            "Active within {} blocks: {}",
+            self.within,
+            if last_trade.is_some() { "yes" } else { "no" }
+        )?;
+
+        let throughput = contract.trades_per_block(self.within).await?;
+        writeln!(
+            out,
+            "Trades per block: {:.4} ({} trades in {} blocks)",
+            throughput.per_block(),
+            throughput.trades,
+            throughput.blocks
+        )?;
+
+        out.flush()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use crate::utils::{output_writer, setup, validate_deployed_contract_id};
+use clap::Args;
+use fuels::accounts::ViewOnlyAccount;
+use fuels::types::Bits256;
+use spark_market_sdk::SparkMarketContract;
+use std::{io::Write, path::PathBuf};
+
+#[derive(Args, Clone)]
+#[command(about = "Report how long the provided orders have been resting in the market")]
+pub(crate) struct AgingCommand {
+    /// The b256 id of the order
+    #[clap(long)]
+    pub(crate) orders: Vec<String>,
+
+    /// The width of each histogram bucket in blocks
+    #[clap(long, default_value_t = 1000)]
+    pub(crate) bucket_size: u32,
+
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl AgingCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+
+        if self.orders.is_empty() {
+            anyhow::bail!("Invalid order array length == 0");
+        }
+
+        let mut order_ids: Vec<Bits256> = Vec::new();
+        for order in self.orders.clone() {
+            order_ids.push(Bits256::from_hex_str(&order).expect("Invalid order_id"));
+        }
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet).await;
+
+        let aging = contract.order_aging(order_ids, self.bucket_size).await?;
+
+        let mut out = output_writer(&self.output)?;
+        writeln!(out, "\nChain tip: {}", aging.tip)?;
+        writeln!(out, "Oldest order age: {} blocks", aging.oldest)?;
+        writeln!(out, "Median order age: {} blocks", aging.median)?;
+        writeln!(out, "Orders by age:")?;
+        for (start, count) in aging.buckets {
+            writeln!(
+                out,
+                "  {}-{} blocks: {}",
+                start,
+                start.saturating_add(self.bucket_size - 1),
+                count
+            )?;
+        }
+
+        out.flush()?;
+
+        Ok(())
+    }
+}
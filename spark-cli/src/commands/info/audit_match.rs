@@ -0,0 +1,90 @@
+use crate::utils::{
+    encode_b256, format_price, output_writer, setup, validate_deployed_contract_id, Encoding,
+};
+use clap::Args;
+use fuels::accounts::ViewOnlyAccount;
+use fuels::types::Bits256;
+use spark_market_sdk::SparkMarketContract;
+use std::{io::Write, path::PathBuf};
+
+#[derive(Args, Clone)]
+#[command(about = "Dry-run a match and print the maker orders it would fill in priority order")]
+pub(crate) struct AuditMatchCommand {
+    /// The b256 id of the taker order
+    #[clap(long)]
+    pub(crate) taker_id: String,
+
+    /// The b256 id of a resting maker order
+    #[clap(long)]
+    pub(crate) makers: Vec<String>,
+
+    /// The encoding used to print b256 values
+    #[clap(long, value_enum, default_value_t)]
+    pub(crate) encoding: Encoding,
+
+    /// Decimal places of the displayed prices, defaults to the market's price decimals
+    #[clap(long)]
+    pub(crate) price_precision: Option<u32>,
+
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl AuditMatchCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+
+        if self.makers.is_empty() {
+            anyhow::bail!("Invalid order array length == 0");
+        }
+
+        let taker_id = Bits256::from_hex_str(&self.taker_id).expect("Invalid order_id");
+        let mut makers: Vec<Bits256> = Vec::new();
+        for order in self.makers.clone() {
+            makers.push(Bits256::from_hex_str(&order).expect("Invalid order_id"));
+        }
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet).await;
+
+        let price_decimals = contract.config().await?.price_decimals;
+        let fills = contract.audit_match(taker_id, makers).await?;
+
+        let mut out = output_writer(&self.output)?;
+        if fills.is_empty() {
+            writeln!(out, "\nNo maker order crosses the taker")?;
+        } else {
+            writeln!(out, "\nFills in priority order:")?;
+        }
+        for (index, fill) in fills.iter().enumerate() {
+            writeln!(
+                out,
+                "{}. {} at {} for {}",
+                index + 1,
+                encode_b256(&fill.maker_id.0, &self.encoding),
+                format_price(
+                    fill.price,
+                    price_decimals,
+                    self.price_precision.unwrap_or(price_decimals)
+                ),
+                fill.amount
+            )?;
+        }
+
+        out.flush()?;
+
+        Ok(())
+    }
+}
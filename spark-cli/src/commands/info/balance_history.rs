@@ -0,0 +1,231 @@
+use crate::utils::{
+    output_writer, print_warning, setup, until_interrupted, validate_deployed_contract_id,
+    AccountType,
+};
+use clap::Args;
+use fuels::accounts::ViewOnlyAccount;
+use spark_market_sdk::{BalanceChange, BalanceChangeKind, SparkMarketContract};
+use std::{future::Future, io::Write, ops::RangeInclusive, path::PathBuf};
+
+#[derive(Args, Clone)]
+#[command(about = "Export the liquid balances of a trader after each change within a block range")]
+pub(crate) struct BalanceHistoryCommand {
+    /// The b256 id of the trader
+    #[clap(long)]
+    pub(crate) trader: String,
+
+    /// The type of account
+    #[clap(long)]
+    pub(crate) account_type: AccountType,
+
+    /// The first block to scan
+    #[clap(long)]
+    pub(crate) from_block: u32,
+
+    /// The last block to scan, defaults to the latest block
+    #[clap(long)]
+    pub(crate) to_block: Option<u32>,
+
+    /// Write the CSV to this file instead of stdout, an export interrupted with Ctrl-C keeps the
+    /// rows of the blocks scanned so far
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl BalanceHistoryCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+        let to_block = match self.to_block {
+            Some(to_block) => to_block,
+            None => wallet.try_provider()?.latest_block_height().await?,
+        };
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet).await;
+
+        let trader = self.account_type.identity(&self.trader)?;
+
+        let mut out = output_writer(&self.output)?;
+        let mut rows = 0;
+        write_header(&mut out)?;
+        let interrupted = until_interrupted(
+            export(
+                &mut out,
+                self.from_block..=to_block,
+                |height| contract.balance_history(trader, height, height),
+                &mut rows,
+            ),
+            async {
+                let _ = tokio::signal::ctrl_c().await;
+            },
+        )
+        .await?;
+        out.flush()?;
+
+        if interrupted {
+            print_warning(format!("Interrupted, {} row(s) written", rows));
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the rows of each block as soon as it is scanned, so an interrupted export still holds
+/// every block finished before it
+async fn export<F, Fut>(
+    out: &mut impl Write,
+    blocks: RangeInclusive<u32>,
+    mut scan: F,
+    rows: &mut usize,
+) -> anyhow::Result<()>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = anyhow::Result<Vec<BalanceChange>>>,
+{
+    for height in blocks {
+        for change in scan(height).await? {
+            write_row(out, &change)?;
+            *rows += 1;
+        }
+    }
+    Ok(())
+}
+
+fn write_header(out: &mut impl Write) -> anyhow::Result<()> {
+    writeln!(out, "block_height,tx_id,event,liquid_base,liquid_quote")?;
+    Ok(())
+}
+
+fn write_row(out: &mut impl Write, change: &BalanceChange) -> anyhow::Result<()> {
+    let event = match change.kind {
+        BalanceChangeKind::Deposit => "deposit",
+        BalanceChangeKind::Withdraw => "withdraw",
+        BalanceChangeKind::OpenOrder => "open_order",
+        BalanceChangeKind::CancelOrder => "cancel_order",
+        BalanceChangeKind::Trade => "trade",
+    };
+    writeln!(
+        out,
+        "{},0x{},{},{},{}",
+        change.block_height, change.tx_id, event, change.liquid_base, change.liquid_quote
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuels::types::Bytes32;
+    use tokio::sync::oneshot;
+
+    #[test]
+    fn writes_one_row_per_change() -> anyhow::Result<()> {
+        let tx_id = Bytes32::from([1; 32]);
+        let history = [
+            BalanceChange {
+                block_height: 3,
+                tx_id,
+                kind: BalanceChangeKind::Deposit,
+                liquid_base: 5,
+                liquid_quote: 0,
+            },
+            BalanceChange {
+                block_height: 5,
+                tx_id,
+                kind: BalanceChangeKind::Trade,
+                liquid_base: 3,
+                liquid_quote: 140,
+            },
+        ];
+
+        let mut out = Vec::new();
+        write_header(&mut out)?;
+        for change in &history {
+            write_row(&mut out, change)?;
+        }
+
+        let tx_id = "01".repeat(32);
+        assert_eq!(
+            String::from_utf8(out)?,
+            format!(
+                "block_height,tx_id,event,liquid_base,liquid_quote\n\
+                 3,0x{tx_id},deposit,5,0\n\
+                 5,0x{tx_id},trade,3,140\n"
+            )
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn interrupted_export_keeps_scanned_blocks() -> anyhow::Result<()> {
+        let output = std::env::temp_dir()
+            .join(format!("spark-cli-balance-history-{}", std::process::id()))
+            .join("history.csv");
+        let mut out = output_writer(&Some(output.clone()))?;
+
+        // Blocks 1 to 3 are scanned, the scan of block 4 never finishes and the user presses Ctrl-C
+        let (interrupt, interrupted) = oneshot::channel();
+        let mut interrupt = Some(interrupt);
+        let scan = |height: u32| {
+            let stalled = height > 3;
+            if stalled {
+                let _ = interrupt.take().map(|interrupt| interrupt.send(()));
+            }
+            async move {
+                if stalled {
+                    std::future::pending::<()>().await;
+                }
+                Ok(vec![BalanceChange {
+                    block_height: height,
+                    tx_id: Bytes32::from([1; 32]),
+                    kind: BalanceChangeKind::Deposit,
+                    liquid_base: height as u64,
+                    liquid_quote: 0,
+                }])
+            }
+        };
+
+        let mut rows = 0;
+        write_header(&mut out)?;
+        let was_interrupted = until_interrupted(export(&mut out, 1..=10, scan, &mut rows), async {
+            let _ = interrupted.await;
+        })
+        .await?;
+        out.flush()?;
+        drop(out);
+
+        assert!(was_interrupted);
+        assert_eq!(rows, 3);
+
+        // A header and one complete row per scanned block
+        let written = std::fs::read_to_string(&output)?;
+        let lines: Vec<_> = written.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0],
+            "block_height,tx_id,event,liquid_base,liquid_quote"
+        );
+        for (height, line) in (1..=3).zip(&lines[1..]) {
+            assert_eq!(
+                *line,
+                format!("{height},0x{},deposit,{height},0", "01".repeat(32))
+            );
+        }
+
+        std::fs::remove_dir_all(output.parent().unwrap())?;
+
+        Ok(())
+    }
+}
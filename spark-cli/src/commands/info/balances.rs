@@ -0,0 +1,87 @@
+use crate::utils::{output_writer, setup, validate_deployed_contract_id, AccountType};
+use clap::Args;
+use fuels::accounts::ViewOnlyAccount;
+use spark_market_sdk::SparkMarketContract;
+use std::{io::Write, path::PathBuf};
+
+#[derive(Args, Clone)]
+#[command(about = "Query the market balances of many users at once")]
+pub(crate) struct BalancesCommand {
+    /// The b256 ids of the accounts, comma separated
+    #[clap(long, value_delimiter = ',', required_unless_present = "file")]
+    pub(crate) account_ids: Vec<String>,
+
+    /// A file with one b256 account id per line, read in addition to `--account-ids`
+    #[clap(long)]
+    pub(crate) file: Option<PathBuf>,
+
+    /// The type of the accounts
+    #[clap(long)]
+    pub(crate) account_type: AccountType,
+
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl BalancesCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+
+        let mut account_ids = self.account_ids.clone();
+        if let Some(file) = &self.file {
+            account_ids.extend(
+                std::fs::read_to_string(file)?
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(String::from),
+            );
+        }
+        if account_ids.is_empty() {
+            anyhow::bail!("No account ids provided");
+        }
+
+        let mut users = Vec::with_capacity(account_ids.len());
+        for account_id in &account_ids {
+            users.push(self.account_type.identity(account_id)?);
+        }
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet).await;
+
+        let accounts = contract.accounts(users).await?;
+
+        let mut out = output_writer(&self.output)?;
+        writeln!(
+            out,
+            "\naccount,liquid_base,liquid_quote,locked_base,locked_quote"
+        )?;
+        for (account_id, account) in account_ids.iter().zip(accounts) {
+            writeln!(
+                out,
+                "{},{},{},{},{}",
+                account_id,
+                account.liquid.base,
+                account.liquid.quote,
+                account.locked.base,
+                account.locked.quote
+            )?;
+        }
+
+        out.flush()?;
+
+        Ok(())
+    }
+}
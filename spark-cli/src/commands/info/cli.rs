@@ -1,8 +1,16 @@
 use crate::commands::info::{
-    account::AccountCommand, config::ConfigCommand, epoch::EpochCommand,
-    matcher_fee::MatcherFeeCommand, order::OrderCommand, order_id::OrderIdCommand,
+    account::AccountCommand, activity::ActivityCommand, aging::AgingCommand,
+    audit_match::AuditMatchCommand, balance_history::BalanceHistoryCommand,
+    balances::BalancesCommand, compare_config::CompareConfigCommand, config::ConfigCommand,
+    contract_balances::ContractBalancesCommand, depth_chart::DepthChartCommand,
+    detect_frontrun::DetectFrontrunCommand, epoch::EpochCommand, fee_history::FeeHistoryCommand,
+    matcher_fee::MatcherFeeCommand, microstructure::MicrostructureCommand,
+    network_status::NetworkStatusCommand, order::OrderCommand, order_id::OrderIdCommand,
     protocol_fee::ProtocolFeeCommand, protocol_fee_user::ProtocolFeeUserCommand,
-    protocol_fee_user_amount::ProtocolFeeUserAmountCommand, user_orders::UserOrdersCommand,
+    protocol_fee_user_amount::ProtocolFeeUserAmountCommand,
+    required_deposit::RequiredDepositCommand, trade_history::TradeHistoryCommand,
+    user_orders::UserOrdersCommand, verify_account::VerifyAccountCommand,
+    verify_order_id::VerifyOrderIdCommand, whoami::WhoamiCommand,
 };
 use clap::Subcommand;
 
@@ -12,14 +20,53 @@ pub(crate) enum InfoCommands {
     #[clap(short_flag = 'A')]
     Account(AccountCommand),
 
+    /// Report whether the market has traded recently
+    #[clap(short_flag = 'Y')]
+    Activity(ActivityCommand),
+
+    /// Report the age of resting orders in the market contract
+    #[clap(short_flag = 'G')]
+    Aging(AgingCommand),
+
+    /// Dry-run a match and print the maker orders it would fill in priority order
+    #[clap(short_flag = 'P')]
+    AuditMatch(AuditMatchCommand),
+
+    /// Export the liquid balances of a trader after each change as CSV
+    #[clap(short_flag = 'N')]
+    BalanceHistory(BalanceHistoryCommand),
+
+    /// Query the market balances of many users
+    #[clap(short_flag = 'L')]
+    Balances(BalancesCommand),
+
+    /// Compare the configuration of two market contracts
+    #[clap(short_flag = 'D')]
+    CompareConfig(CompareConfigCommand),
+
     /// Query configuration information for a market contract
     #[clap(short_flag = 'C')]
     Config(ConfigCommand),
 
+    /// Query the base and quote held on-chain by a market contract
+    ContractBalances(ContractBalancesCommand),
+
+    /// Export cumulative depth per price level of a set of resting orders
+    #[clap(short_flag = 'H')]
+    DepthChart(DepthChartCommand),
+
+    /// Flag orders matched right after being opened against resting orders
+    #[clap(short_flag = 'Z')]
+    DetectFrontrun(DetectFrontrunCommand),
+
     /// Query epoch information of the market contract
     #[clap(short_flag = 'W')]
     Epoch(EpochCommand),
 
+    /// Query the matcher and protocol fee changes within a block range
+    #[clap(short_flag = 'Q')]
+    FeeHistory(FeeHistoryCommand),
+
     /// Query protocol fee information
     #[clap(short_flag = 'F')]
     ProtocolFee(ProtocolFeeCommand),
@@ -36,6 +83,14 @@ pub(crate) enum InfoCommands {
     #[clap(short_flag = 'M')]
     MatcherFee(MatcherFeeCommand),
 
+    /// Report mid-price, depth and imbalance of a set of resting orders
+    #[clap(short_flag = 'K')]
+    Microstructure(MicrostructureCommand),
+
+    /// Report the chain tip of the node and warn if it looks stale
+    #[clap(short_flag = 'J')]
+    NetworkStatus(NetworkStatusCommand),
+
     /// Calculate the order id given the provided arguments
     #[clap(short_flag = 'I')]
     OrderId(OrderIdCommand),
@@ -44,7 +99,27 @@ pub(crate) enum InfoCommands {
     #[clap(short_flag = 'O')]
     Order(OrderCommand),
 
+    /// Query the deposit an order needs before it is opened
+    #[clap(short_flag = 'R')]
+    RequiredDeposit(RequiredDepositCommand),
+
+    /// Query the fills of a trader
+    #[clap(short_flag = 'T')]
+    TradeHistory(TradeHistoryCommand),
+
     /// Query orders associated with an
     #[clap(short_flag = 'U')]
     UserOrders(UserOrdersCommand),
+
+    /// Check that the open orders of an account add up to its locked balance
+    #[clap(short_flag = 'X')]
+    VerifyAccount(VerifyAccountCommand),
+
+    /// Verify a calculated order id against the open order in the market
+    #[clap(short_flag = 'V')]
+    VerifyOrderId(VerifyOrderIdCommand),
+
+    /// Show the signer account and its balances
+    #[clap(short_flag = 'S')]
+    Whoami(WhoamiCommand),
 }
@@ -0,0 +1,196 @@
+use crate::utils::{output_writer, setup, validate_deployed_contract_id};
+use clap::Args;
+use fuels::accounts::{Account, ViewOnlyAccount};
+use spark_market_sdk::SparkMarketContract;
+use std::{io::Write, path::PathBuf};
+
+#[derive(Args, Clone)]
+#[command(about = "Compare the configuration of two markets and highlight the differences")]
+pub(crate) struct CompareConfigCommand {
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
+    /// The contract ids of the two markets
+    #[clap(long, num_args = 1, required = true)]
+    pub(crate) contract_id: Vec<String>,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl CompareConfigCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+
+        if self.contract_id.len() != 2 {
+            anyhow::bail!("Expected exactly two contract ids to compare");
+        }
+        let provider = wallet.try_provider()?;
+        let contract0 = SparkMarketContract::new(
+            validate_deployed_contract_id(&self.contract_id[0], provider).await?,
+            wallet.clone(),
+        )
+        .await;
+        let contract1 = SparkMarketContract::new(
+            validate_deployed_contract_id(&self.contract_id[1], provider).await?,
+            wallet.clone(),
+        )
+        .await;
+
+        let diff = compare(
+            &config_fields(&contract0).await?,
+            &config_fields(&contract1).await?,
+        );
+
+        let mut out = output_writer(&self.output)?;
+        writeln!(out, "\nMarket 0: {}", self.contract_id[0])?;
+        writeln!(out, "Market 1: {}", self.contract_id[1])?;
+
+        // Differing fields are marked with a leading `*`
+        let name_width = diff.iter().map(|row| row.0.len()).max().unwrap_or(0);
+        let value_width = diff.iter().map(|row| row.1.len()).max().unwrap_or(0);
+        let (field, market0) = ("Field", "Market 0");
+        writeln!(
+            out,
+            "\n  {field:name_width$}  {market0:value_width$}  Market 1"
+        )?;
+        for (name, value0, value1, differs) in &diff {
+            let marker = if *differs { "*" } else { " " };
+            writeln!(
+                out,
+                "{} {:name_width$}  {:value_width$}  {}",
+                marker, name, value0, value1
+            )?;
+        }
+        writeln!(
+            out,
+            "\n{} field(s) differ",
+            diff.iter().filter(|row| row.3).count()
+        )?;
+
+        out.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Reads every configuration value of a market as a display string, keyed by field name
+async fn config_fields(
+    contract: &SparkMarketContract<impl Account>,
+) -> anyhow::Result<Vec<(&'static str, String)>> {
+    let config = contract.config().await?;
+
+    // Markets deployed before these limits existed do not expose them
+    let optional = |value: anyhow::Result<u64>| match value {
+        Ok(value) => value.to_string(),
+        Err(_) => "n/a".to_string(),
+    };
+
+    Ok(vec![
+        ("Base Asset", format!("0x{}", config.base_asset)),
+        ("Base Asset Decimals", config.base_decimals.to_string()),
+        ("Quote Asset", format!("0x{}", config.quote_asset)),
+        ("Quote Asset Decimals", config.quote_decimals.to_string()),
+        ("Owner", format!("{:?}", config.owner)),
+        ("Price Decimals", config.price_decimals.to_string()),
+        (
+            "Version",
+            format!(
+                "{}.{}.{}",
+                (config.version & 0xFF0000) >> 16,
+                (config.version & 0xFF00) >> 8,
+                config.version & 0xFF
+            ),
+        ),
+        (
+            "Matcher Fee",
+            contract.matcher_fee().await?.value.to_string(),
+        ),
+        (
+            "Protocol Fee",
+            format!("{:?}", contract.protocol_fee().await?.value),
+        ),
+        (
+            "Epoch Duration",
+            contract.get_epoch().await?.value.1.to_string(),
+        ),
+        (
+            "Dust Threshold",
+            optional(contract.dust_threshold().await.map(|r| r.value)),
+        ),
+        (
+            "Max Price Deviation (bps)",
+            optional(contract.max_price_deviation_bps().await.map(|r| r.value)),
+        ),
+        (
+            "Tick Size",
+            optional(contract.tick_size().await.map(|r| r.value)),
+        ),
+    ])
+}
+
+/// Pairs up the fields of two markets and flags the ones whose values differ
+fn compare(
+    fields0: &[(&'static str, String)],
+    fields1: &[(&'static str, String)],
+) -> Vec<(&'static str, String, String, bool)> {
+    fields0
+        .iter()
+        .zip(fields1)
+        .map(|((name, value0), (_, value1))| {
+            (*name, value0.clone(), value1.clone(), value0 != value1)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuels::{
+        crypto::SecretKey,
+        prelude::{setup_single_asset_coins, setup_test_provider, WalletUnlocked},
+        types::AssetId,
+    };
+    use std::str::FromStr;
+
+    #[tokio::test]
+    async fn flags_only_the_differing_matcher_fee() -> anyhow::Result<()> {
+        let secret = "0x862512a2363db2b3a375c0d4bbbd27172180d89f23f2e259bac850ab02619301";
+        let mut wallet = WalletUnlocked::new_from_private_key(SecretKey::from_str(secret)?, None);
+        let coins =
+            setup_single_asset_coins(wallet.address(), AssetId::default(), 1, 1_000_000_000);
+        let provider = setup_test_provider(coins, vec![], None, None).await?;
+        wallet.set_provider(provider);
+
+        let deploy = || {
+            SparkMarketContract::deploy(
+                AssetId::from([1; 32]),
+                9,
+                AssetId::from([2; 32]),
+                6,
+                wallet.clone(),
+                9,
+                0x000400,
+            )
+        };
+        let market0 = deploy().await?;
+        let market1 = deploy().await?;
+        market1.set_matcher_fee(5).await?;
+
+        let diff = compare(
+            &config_fields(&market0).await?,
+            &config_fields(&market1).await?,
+        );
+        let differing: Vec<_> = diff.iter().filter(|row| row.3).collect();
+
+        assert_eq!(differing.len(), 1);
+        assert_eq!(differing[0].0, "Matcher Fee");
+        assert_eq!(differing[0].1, "0");
+        assert_eq!(differing[0].2, "5");
+
+        Ok(())
+    }
+}
@@ -1,10 +1,20 @@
-use crate::utils::{setup, validate_contract_id};
+use crate::utils::{output_writer, setup, validate_deployed_contract_id, SymbolMap};
 use clap::Args;
-use spark_market_sdk::SparkMarketContract;
+use fuels::{accounts::ViewOnlyAccount, types::AssetId};
+use spark_market_sdk::{MarketConfig, SparkMarketContract};
+use std::{io::Write, path::PathBuf};
 
 #[derive(Args, Clone)]
 #[command(about = "Query the market for its configurable variables")]
 pub(crate) struct ConfigCommand {
+    /// A JSON file of {"<asset id>": "<symbol>"} naming assets in the output
+    #[clap(long)]
+    pub(crate) symbols: Option<PathBuf>,
+
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
     /// The contract id of the market
     #[clap(long)]
     pub(crate) contract_id: String,
@@ -18,33 +28,113 @@ pub(crate) struct ConfigCommand {
 impl ConfigCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
         let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
 
         // Connect to the deployed contract via the rpc
         let contract = SparkMarketContract::new(contract_id, wallet).await;
 
-        let (
-            base_asset,
-            base_asset_decimals,
-            quote_asset,
-            quote_asset_decimals,
-            owner,
-            price_decimals,
-            version,
-        ) = contract.config().await?.value;
-
-        println!("\nBase Asset: 0x{}", base_asset);
-        println!("Base Asset Decimals: {}", base_asset_decimals);
-        println!("Quote Asset: 0x{}", quote_asset);
-        println!("Quote Asset Decimals: {}", quote_asset_decimals);
-        println!("Owner: 0x{:?}", owner);
-        println!("Price Decimals: {}", price_decimals);
-        println!(
-            "Version: {}.{}.{}",
-            (version & 0xFF0000) >> 16,
-            (version & 0xFF00) >> 8,
-            version & 0xFF
-        );
+        let symbols = SymbolMap::load(self.symbols.as_deref())?;
+        let config = contract.config().await?;
+        // Markets deployed before the tick size existed do not expose it
+        let tick_size = contract.tick_size().await.ok().map(|r| r.value);
+
+        let mut out = output_writer(&self.output)?;
+        write_config(&mut out, config, tick_size, &symbols)?;
+        out.flush()?;
+
+        Ok(())
+    }
+}
+
+fn write_config(
+    out: &mut impl Write,
+    config: MarketConfig,
+    tick_size: Option<u64>,
+    symbols: &SymbolMap,
+) -> std::io::Result<()> {
+    // The full id is kept since this is where ids are copied from
+    let asset = |asset: AssetId| match symbols.symbol(&asset) {
+        Some(symbol) => format!("0x{} ({})", asset, symbol),
+        None => format!("0x{}", asset),
+    };
+    writeln!(out, "\nBase Asset: {}", asset(config.base_asset))?;
+    writeln!(out, "Base Asset Decimals: {}", config.base_decimals)?;
+    writeln!(out, "Quote Asset: {}", asset(config.quote_asset))?;
+    writeln!(out, "Quote Asset Decimals: {}", config.quote_decimals)?;
+    writeln!(out, "Owner: 0x{:?}", config.owner)?;
+    writeln!(out, "Price Decimals: {}", config.price_decimals)?;
+    match tick_size {
+        Some(tick_size) => writeln!(out, "Tick Size: {}", tick_size)?,
+        None => writeln!(out, "Tick Size: n/a")?,
+    }
+    writeln!(
+        out,
+        "Version: {}.{}.{}",
+        (config.version & 0xFF0000) >> 16,
+        (config.version & 0xFF00) >> 8,
+        config.version & 0xFF
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuels::{
+        crypto::SecretKey,
+        prelude::{setup_single_asset_coins, setup_test_provider, WalletUnlocked},
+        types::AssetId,
+    };
+    use std::str::FromStr;
+
+    #[tokio::test]
+    async fn output_file_matches_stdout() -> anyhow::Result<()> {
+        let secret = "0x862512a2363db2b3a375c0d4bbbd27172180d89f23f2e259bac850ab02619301";
+        let mut wallet = WalletUnlocked::new_from_private_key(SecretKey::from_str(secret)?, None);
+        let coins =
+            setup_single_asset_coins(wallet.address(), AssetId::default(), 1, 1_000_000_000);
+        let provider = setup_test_provider(coins, vec![], None, None).await?;
+        wallet.set_provider(provider.clone());
+        std::env::set_var("WALLET_SECRET", secret);
+
+        let market = SparkMarketContract::deploy(
+            AssetId::from([1; 32]),
+            9,
+            AssetId::from([2; 32]),
+            6,
+            wallet,
+            9,
+            0x000400,
+        )
+        .await?;
+
+        let output = std::env::temp_dir()
+            .join(format!("spark-cli-config-{}", std::process::id()))
+            .join("nested")
+            .join("config.txt");
+        ConfigCommand {
+            symbols: None,
+            output: Some(output.clone()),
+            contract_id: format!("0x{}", market.id()),
+            rpc: provider.url().to_string(),
+        }
+        .run()
+        .await?;
+
+        let mut stdout = Vec::new();
+        let tick_size = market.tick_size().await.ok().map(|r| r.value);
+        write_config(
+            &mut stdout,
+            market.config().await?,
+            tick_size,
+            &SymbolMap::load(None)?,
+        )?;
+
+        let written = std::fs::read_to_string(&output)?;
+        assert_eq!(written, String::from_utf8(stdout)?);
+        assert!(written.contains("Quote Asset Decimals: 6"));
+
+        std::fs::remove_dir_all(output.parent().unwrap().parent().unwrap())?;
 
         Ok(())
     }
@@ -0,0 +1,43 @@
+use crate::utils::{output_writer, setup, validate_deployed_contract_id};
+use clap::Args;
+use fuels::accounts::ViewOnlyAccount;
+use spark_market_sdk::SparkMarketContract;
+use std::{io::Write, path::PathBuf};
+
+#[derive(Args, Clone)]
+#[command(about = "Query the base and quote the market contract holds on-chain")]
+pub(crate) struct ContractBalancesCommand {
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl ContractBalancesCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet).await;
+
+        let held = contract.contract_balances().await?;
+
+        let mut out = output_writer(&self.output)?;
+        writeln!(out, "\nHeld base: {}", held.base)?;
+        writeln!(out, "Held quote: {}", held.quote)?;
+
+        out.flush()?;
+
+        Ok(())
+    }
+}
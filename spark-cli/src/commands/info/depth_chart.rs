@@ -0,0 +1,197 @@
+use crate::utils::{
+    format_json, output_writer, render_table, setup, validate_deployed_contract_id, Align,
+    OutputFormat,
+};
+use clap::Args;
+use fuels::accounts::ViewOnlyAccount;
+use fuels::types::Bits256;
+use serde_json::json;
+use spark_market_sdk::{BookDepth, SparkMarketContract};
+use std::{collections::BTreeMap, io::Write, path::PathBuf};
+
+#[derive(Args, Clone)]
+#[command(about = "Export cumulative bid and ask volume per price level of the provided orders")]
+pub(crate) struct DepthChartCommand {
+    /// The b256 id of the order
+    #[clap(long)]
+    pub(crate) orders: Vec<String>,
+
+    /// Print the rows as a JSON array instead of CSV
+    #[clap(long)]
+    pub(crate) json: bool,
+
+    /// Print CSV rows, or a table with aligned columns
+    #[clap(long, value_enum, default_value_t, conflicts_with = "json")]
+    pub(crate) format: OutputFormat,
+
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl DepthChartCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+
+        if self.orders.is_empty() {
+            anyhow::bail!("Invalid order array length == 0");
+        }
+
+        let mut order_ids: Vec<Bits256> = Vec::new();
+        for order in self.orders.clone() {
+            order_ids.push(Bits256::from_hex_str(&order).expect("Invalid order_id"));
+        }
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet).await;
+
+        let rows = depth_chart(&contract.book_depth(order_ids).await?);
+
+        let mut out = output_writer(&self.output)?;
+        if self.json {
+            let rows: Vec<_> = rows
+                .iter()
+                .map(|row| {
+                    json!({
+                        "price": row.price,
+                        "cumulative_bid": row.cumulative_bid,
+                        "cumulative_ask": row.cumulative_ask,
+                    })
+                })
+                .collect();
+            writeln!(out, "{}", format_json(&json!(rows)))?;
+        } else if let OutputFormat::Table = self.format {
+            let rows: Vec<Vec<String>> = rows
+                .iter()
+                .map(|row| {
+                    vec![
+                        row.price.to_string(),
+                        row.cumulative_bid.to_string(),
+                        row.cumulative_ask.to_string(),
+                    ]
+                })
+                .collect();
+            let columns = [
+                ("Price", Align::Right),
+                ("Cumulative Bid", Align::Right),
+                ("Cumulative Ask", Align::Right),
+            ];
+            writeln!(out, "{}", render_table(&columns, &rows))?;
+        } else {
+            writeln!(out, "price,cumulative_bid,cumulative_ask")?;
+            for row in &rows {
+                writeln!(
+                    out,
+                    "{},{},{}",
+                    row.price, row.cumulative_bid, row.cumulative_ask
+                )?;
+            }
+        }
+
+        out.flush()?;
+
+        Ok(())
+    }
+}
+
+/// The volume available at a price or better on each side
+#[derive(Debug, PartialEq)]
+struct DepthRow {
+    price: u64,
+    /// Bid volume at this price or higher
+    cumulative_bid: u64,
+    /// Ask volume at this price or lower
+    cumulative_ask: u64,
+}
+
+/// Accumulates each side from its best price outward, with one row per price level of either
+/// side in ascending price order
+fn depth_chart(depth: &BookDepth) -> Vec<DepthRow> {
+    let mut rows: BTreeMap<u64, (u64, u64)> = BTreeMap::new();
+
+    let mut cumulative = 0;
+    for (price, amount) in &depth.bids {
+        cumulative += amount;
+        rows.entry(*price).or_default().0 = cumulative;
+    }
+    let mut cumulative = 0;
+    for (price, amount) in &depth.asks {
+        cumulative += amount;
+        rows.entry(*price).or_default().1 = cumulative;
+    }
+
+    // Fill in each side at the price levels that only exist on the other side
+    let mut bid = 0;
+    for (_, (cumulative_bid, _)) in rows.iter_mut().rev() {
+        bid = bid.max(*cumulative_bid);
+        *cumulative_bid = bid;
+    }
+    let mut ask = 0;
+    for (_, (_, cumulative_ask)) in rows.iter_mut() {
+        ask = ask.max(*cumulative_ask);
+        *cumulative_ask = ask;
+    }
+
+    rows.into_iter()
+        .map(|(price, (cumulative_bid, cumulative_ask))| DepthRow {
+            price,
+            cumulative_bid,
+            cumulative_ask,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cumulative_volume_grows_from_the_best_price_outward() {
+        let depth = BookDepth {
+            bids: vec![(69, 300), (68, 100), (66, 50)],
+            asks: vec![(71, 100), (72, 200), (75, 25)],
+        };
+
+        let rows = depth_chart(&depth);
+        let prices: Vec<_> = rows.iter().map(|row| row.price).collect();
+        assert_eq!(prices, vec![66, 68, 69, 71, 72, 75]);
+
+        // Bids accumulate downward and asks upward from the spread
+        assert!(rows
+            .windows(2)
+            .all(|pair| pair[0].cumulative_bid >= pair[1].cumulative_bid));
+        assert!(rows
+            .windows(2)
+            .all(|pair| pair[0].cumulative_ask <= pair[1].cumulative_ask));
+
+        assert_eq!(rows.first().unwrap().cumulative_bid, 450);
+        assert_eq!(rows.last().unwrap().cumulative_ask, 325);
+        assert_eq!(
+            rows[2],
+            DepthRow {
+                price: 69,
+                cumulative_bid: 300,
+                cumulative_ask: 0
+            }
+        );
+        assert_eq!(
+            rows[4],
+            DepthRow {
+                price: 72,
+                cumulative_bid: 0,
+                cumulative_ask: 300
+            }
+        );
+    }
+}
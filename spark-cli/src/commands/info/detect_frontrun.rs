@@ -0,0 +1,94 @@
+use crate::utils::{
+    encode_b256, format_price, output_writer, setup, validate_deployed_contract_id, Encoding,
+};
+use clap::Args;
+use fuels::{accounts::ViewOnlyAccount, types::Identity};
+use spark_market_sdk::SparkMarketContract;
+use std::{io::Write, path::PathBuf};
+
+#[derive(Args, Clone)]
+#[command(about = "Flag orders matched within a few blocks of being opened against resting orders")]
+pub(crate) struct DetectFrontrunCommand {
+    /// The first block to scan
+    #[clap(long)]
+    pub(crate) from_block: u32,
+
+    /// The last block to scan, defaults to the latest block
+    #[clap(long)]
+    pub(crate) to_block: Option<u32>,
+
+    /// How many blocks after being opened a match still counts as immediate
+    #[clap(long, default_value_t = 1)]
+    pub(crate) max_blocks: u32,
+
+    /// The encoding used to print b256 values
+    #[clap(long, value_enum, default_value_t)]
+    pub(crate) encoding: Encoding,
+
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl DetectFrontrunCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+        let to_block = match self.to_block {
+            Some(to_block) => to_block,
+            None => wallet.try_provider()?.latest_block_height().await?,
+        };
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet).await;
+
+        let price_decimals = contract.config().await?.price_decimals;
+        let suspects = contract
+            .frontrun_suspects(self.from_block, to_block, self.max_blocks)
+            .await?;
+
+        let mut out = output_writer(&self.output)?;
+        writeln!(
+            out,
+            "\nOrders matched within {} block(s) of opening between blocks {} and {}: {}",
+            self.max_blocks,
+            self.from_block,
+            to_block,
+            suspects.len()
+        )?;
+        for suspect in &suspects {
+            writeln!(
+                out,
+                "  {} by {}: opened at block {}, matched at block {} for {} at {} against {} by {}",
+                encode_b256(&suspect.order_id.0, &self.encoding),
+                self.identity(&suspect.owner),
+                suspect.opened_block,
+                suspect.matched_block,
+                suspect.trade_size,
+                format_price(suspect.trade_price, price_decimals, price_decimals),
+                encode_b256(&suspect.counterparty_id.0, &self.encoding),
+                self.identity(&suspect.counterparty)
+            )?;
+        }
+        out.flush()?;
+
+        Ok(())
+    }
+
+    fn identity(&self, identity: &Identity) -> String {
+        match identity {
+            Identity::Address(address) => encode_b256(address, &self.encoding),
+            Identity::ContractId(contract_id) => encode_b256(contract_id, &self.encoding),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use crate::utils::{output_writer, setup, validate_deployed_contract_id};
+use clap::Args;
+use fuels::accounts::ViewOnlyAccount;
+use spark_market_sdk::{FeeChange, FeeChangeKind, ProtocolFee, SparkMarketContract};
+use std::{io::Write, path::PathBuf};
+
+#[derive(Args, Clone)]
+#[command(about = "Query the matcher and protocol fee changes within a block range")]
+pub(crate) struct FeeHistoryCommand {
+    /// The first block to scan
+    #[clap(long)]
+    pub(crate) from_block: u32,
+
+    /// The last block to scan, defaults to the latest block
+    #[clap(long)]
+    pub(crate) to_block: Option<u32>,
+
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl FeeHistoryCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+        let to_block = match self.to_block {
+            Some(to_block) => to_block,
+            None => wallet.try_provider()?.latest_block_height().await?,
+        };
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet).await;
+
+        let history = contract
+            .fee_change_history(self.from_block, to_block)
+            .await?;
+
+        let mut out = output_writer(&self.output)?;
+        writeln!(
+            out,
+            "\nFee changes between blocks {} and {}: {}",
+            self.from_block,
+            to_block,
+            history.len()
+        )?;
+        for change in &history {
+            writeln!(out, "{}", change_line(change))?;
+        }
+        out.flush()?;
+
+        Ok(())
+    }
+}
+
+fn change_line(change: &FeeChange) -> String {
+    let (fee, old_fee, new_fee) = match &change.kind {
+        FeeChangeKind::Matcher { old_fee, new_fee } => {
+            ("Matcher", old_fee.to_string(), new_fee.to_string())
+        }
+        FeeChangeKind::Protocol { old_fee, new_fee } => {
+            ("Protocol", protocol_fee(old_fee), protocol_fee(new_fee))
+        }
+    };
+    format!(
+        "Block {}: {} fee {} -> {} by {:?}",
+        change.block_height, fee, old_fee, new_fee, change.changed_by
+    )
+}
+
+/// Renders the fee tiers as (maker_fee, taker_fee) from their volume threshold
+fn protocol_fee(tiers: &[ProtocolFee]) -> String {
+    let tiers: Vec<String> = tiers
+        .iter()
+        .map(|tier| {
+            format!(
+                "({}, {}) from {}",
+                tier.maker_fee, tier.taker_fee, tier.volume_threshold
+            )
+        })
+        .collect();
+    format!("[{}]", tiers.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuels::types::{Address, Bytes32, Identity};
+
+    #[test]
+    fn lines_show_old_and_new_fee() {
+        let owner = Identity::Address(Address::from([1; 32]));
+        let change = |block_height, kind| FeeChange {
+            block_height,
+            tx_id: Bytes32::zeroed(),
+            changed_by: owner,
+            kind,
+        };
+
+        assert_eq!(
+            change_line(&change(
+                10,
+                FeeChangeKind::Matcher {
+                    old_fee: 0,
+                    new_fee: 100,
+                }
+            )),
+            format!("Block 10: Matcher fee 0 -> 100 by {:?}", owner)
+        );
+        let tier = |maker_fee, taker_fee, volume_threshold| ProtocolFee {
+            maker_fee,
+            taker_fee,
+            volume_threshold,
+        };
+        assert_eq!(
+            change_line(&change(
+                12,
+                FeeChangeKind::Protocol {
+                    old_fee: vec![],
+                    new_fee: vec![tier(10, 15, 0), tier(5, 10, 1_000_000)],
+                }
+            )),
+            format!(
+                "Block 12: Protocol fee [] -> [(10, 15) from 0, (5, 10) from 1000000] by {:?}",
+                owner
+            )
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use crate::utils::{format_price, output_writer, setup, validate_deployed_contract_id};
+use clap::Args;
+use fuels::accounts::ViewOnlyAccount;
+use fuels::types::Bits256;
+use spark_market_sdk::SparkMarketContract;
+use std::{io::Write, path::PathBuf};
+
+#[derive(Args, Clone)]
+#[command(
+    about = "Report the mid-price, depth and imbalance of the book formed by the provided orders"
+)]
+pub(crate) struct MicrostructureCommand {
+    /// The b256 id of the order
+    #[clap(long)]
+    pub(crate) orders: Vec<String>,
+
+    /// The number of best price levels per side to include in depth and imbalance
+    #[clap(long, default_value_t = 5)]
+    pub(crate) levels: usize,
+
+    /// Decimal places of the displayed prices, defaults to the market's price decimals
+    #[clap(long)]
+    pub(crate) price_precision: Option<u32>,
+
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl MicrostructureCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+
+        if self.orders.is_empty() {
+            anyhow::bail!("Invalid order array length == 0");
+        }
+
+        let mut order_ids: Vec<Bits256> = Vec::new();
+        for order in self.orders.clone() {
+            order_ids.push(Bits256::from_hex_str(&order).expect("Invalid order_id"));
+        }
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet).await;
+
+        let price_decimals = contract.config().await?.price_decimals;
+        let book = contract.book_microstructure(order_ids, self.levels).await?;
+
+        let price = |value: Option<u64>| match value {
+            Some(value) => format_price(
+                value,
+                price_decimals,
+                self.price_precision.unwrap_or(price_decimals),
+            ),
+            None => "n/a".to_string(),
+        };
+
+        let mut out = output_writer(&self.output)?;
+        writeln!(out, "\nBest bid: {}", price(book.best_bid))?;
+        writeln!(out, "Best ask: {}", price(book.best_ask))?;
+        writeln!(out, "Mid-price: {}", price(book.mid_price))?;
+        writeln!(
+            out,
+            "Bid depth (top {} levels): {}",
+            self.levels, book.bid_depth
+        )?;
+        writeln!(
+            out,
+            "Ask depth (top {} levels): {}",
+            self.levels, book.ask_depth
+        )?;
+        match book.imbalance_bps {
+            Some(bps) => writeln!(out, "Imbalance: {}.{:02}% bids", bps / 100, bps % 100)?,
+            None => writeln!(out, "Imbalance: n/a")?,
+        }
+
+        out.flush()?;
+
+        Ok(())
+    }
+}
@@ -1,11 +1,28 @@
 pub(crate) mod account;
+pub(crate) mod activity;
+pub(crate) mod aging;
+pub(crate) mod audit_match;
+pub(crate) mod balance_history;
+pub(crate) mod balances;
 pub(crate) mod cli;
+pub(crate) mod compare_config;
 pub(crate) mod config;
+pub(crate) mod contract_balances;
+pub(crate) mod depth_chart;
+pub(crate) mod detect_frontrun;
 pub(crate) mod epoch;
+pub(crate) mod fee_history;
 pub(crate) mod matcher_fee;
+pub(crate) mod microstructure;
+pub(crate) mod network_status;
 pub(crate) mod order;
 pub(crate) mod order_id;
 pub(crate) mod protocol_fee;
 pub(crate) mod protocol_fee_user;
 pub(crate) mod protocol_fee_user_amount;
+pub(crate) mod required_deposit;
+pub(crate) mod trade_history;
 pub(crate) mod user_orders;
+pub(crate) mod verify_account;
+pub(crate) mod verify_order_id;
+pub(crate) mod whoami;
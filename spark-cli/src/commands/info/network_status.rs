@@ -0,0 +1,100 @@
+use crate::{
+    rpc_headers,
+    utils::{network_status, output_writer, print_warning, unix_now, NetworkStatus},
+};
+use clap::Args;
+use std::{io::Write, path::PathBuf};
+
+#[derive(Args, Clone)]
+#[command(about = "Report the chain tip of the node to confirm it is in sync before trading")]
+pub(crate) struct NetworkStatusCommand {
+    /// Warn when the latest block is older than this many seconds
+    #[clap(long, default_value_t = 60)]
+    pub(crate) max_lag: u64,
+
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl NetworkStatusCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        // Only chain state is read so no wallet is needed
+        let provider = rpc_headers::connect(&self.rpc).await?;
+        let status = network_status(&provider).await?;
+
+        let mut out = output_writer(&self.output)?;
+        write_status(&mut out, &status)?;
+        out.flush()?;
+
+        match status.lag(unix_now()?) {
+            Some(lag) if lag > self.max_lag as i64 => print_warning(format!(
+                "The latest block is {} seconds old, the node may be stale or on a fork",
+                lag
+            )),
+            None => print_warning("The node did not report a timestamp for the latest block"),
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+fn write_status(out: &mut dyn Write, status: &NetworkStatus) -> anyhow::Result<()> {
+    writeln!(out, "\nBlock height: {}", status.block_height)?;
+    writeln!(out, "Chain id: {}", status.chain_id)?;
+    match &status.latest_block_time {
+        Some((time, _)) => writeln!(out, "Latest block time: {}", time)?,
+        None => writeln!(out, "Latest block time: unknown")?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuels::{accounts::ViewOnlyAccount, test_helpers::launch_provider_and_get_wallet};
+
+    #[tokio::test]
+    async fn reports_the_local_chain_tip() -> anyhow::Result<()> {
+        let wallet = launch_provider_and_get_wallet().await?;
+        let provider = wallet.try_provider()?;
+        provider.produce_blocks(3, None).await?;
+
+        let status = network_status(provider).await?;
+        assert_eq!(status.block_height, provider.latest_block_height().await?);
+        assert!(status.block_height >= 3);
+        assert_eq!(status.chain_id, u64::from(provider.chain_id()));
+        // A freshly produced block is close to wall-clock time
+        assert!(status.lag(unix_now()?).unwrap().abs() < 60);
+
+        let output = std::env::temp_dir().join(format!(
+            "spark-cli-network-status-{}.txt",
+            std::process::id()
+        ));
+        NetworkStatusCommand {
+            max_lag: 60,
+            output: Some(output.clone()),
+            rpc: provider.url().to_string(),
+        }
+        .run()
+        .await?;
+
+        let written = std::fs::read_to_string(&output)?;
+        let height: u32 = written
+            .lines()
+            .find_map(|line| line.strip_prefix("Block height: "))
+            .unwrap()
+            .parse()?;
+        assert!(height >= status.block_height);
+        assert!(written.contains(&format!("Chain id: {}", status.chain_id)));
+        std::fs::remove_file(output)?;
+
+        Ok(())
+    }
+}
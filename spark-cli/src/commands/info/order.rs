@@ -1,7 +1,11 @@
-use crate::utils::{setup, validate_contract_id};
+use crate::utils::{
+    format_amount, output_writer, print_warning, setup, validate_deployed_contract_id, DecimalsArgs,
+};
 use clap::Args;
-use fuels::types::Bits256;
-use spark_market_sdk::SparkMarketContract;
+use fuels::accounts::ViewOnlyAccount;
+use fuels::{accounts::Account, types::Bits256};
+use spark_market_sdk::{Order, OrderType, SparkMarketContract};
+use std::{io::Write, path::PathBuf};
 
 #[derive(Args, Clone)]
 #[command(about = "Query the market for information about a specific open order")]
@@ -10,6 +14,21 @@ pub(crate) struct OrderCommand {
     #[clap(long)]
     pub(crate) order_id: String,
 
+    /// The label printed after base amounts
+    #[clap(long, default_value = "base")]
+    pub(crate) base_symbol: String,
+
+    /// The label printed after quote amounts
+    #[clap(long, default_value = "quote")]
+    pub(crate) quote_symbol: String,
+
+    #[clap(flatten)]
+    pub(crate) decimals: DecimalsArgs,
+
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
     /// The contract id of the market
     #[clap(long)]
     pub(crate) contract_id: String,
@@ -23,25 +42,199 @@ pub(crate) struct OrderCommand {
 impl OrderCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
         let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
         let order_id = Bits256::from_hex_str(&self.order_id)?;
 
         if self.order_id.len() as u64 != 64 {
             anyhow::bail!("Invalid order id length");
         }
 
+        let tip = wallet.try_provider()?.latest_block_height().await?;
+
         // Connect to the deployed contract via the rpc
         let contract = SparkMarketContract::new(contract_id, wallet).await;
 
         let order = contract.order(order_id).await?.value;
 
+        let mut out = output_writer(&self.output)?;
         match order {
             Some(order) => {
-                println!("{:#?}", order);
+                writeln!(out, "{:#?}", order)?;
+                writeln!(
+                    out,
+                    "Size: {}",
+                    size(
+                        &contract,
+                        &order,
+                        &self.base_symbol,
+                        &self.quote_symbol,
+                        &self.decimals
+                    )
+                    .await?
+                )?;
+                if let Some(size) = contract.order_size(order_id).await? {
+                    writeln!(out, "Original amount: {}", size.original)?;
+                    writeln!(out, "Remaining amount: {}", size.remaining)?;
+                }
+                // Markets deployed before deadlines existed cannot report one
+                if let Ok(deadline) = contract.order_deadline(order_id).await {
+                    let (line, warning) = expiry(deadline.value.map(|d| d.0), tip);
+                    writeln!(out, "{}", line)?;
+                    if let Some(warning) = warning {
+                        print_warning(warning);
+                    }
+                }
             }
-            None => println!("No order found for id: {}", self.order_id),
+            None => writeln!(out, "No order found for id: {}", self.order_id)?,
         }
 
+        out.flush()?;
+
         Ok(())
     }
 }
+
+/// The remaining size in base, and for buys also the quote it is worth since that is what was
+/// deposited
+async fn size(
+    contract: &SparkMarketContract<impl Account>,
+    order: &Order,
+    base_symbol: &str,
+    quote_symbol: &str,
+    decimals: &DecimalsArgs,
+) -> anyhow::Result<String> {
+    let (base_decimals, quote_decimals) = decimals.resolve(&contract.config().await?);
+    let base = format!(
+        "{} {}",
+        format_amount(order.amount, base_decimals),
+        base_symbol
+    );
+
+    Ok(match order.order_type {
+        OrderType::Sell => base,
+        OrderType::Buy => {
+            let quote = contract.quote_trade(order.amount, order.price).await?;
+            format!(
+                "{} ({} {})",
+                base,
+                format_amount(quote, quote_decimals),
+                quote_symbol
+            )
+        }
+    })
+}
+
+/// The expiry line of an order and a warning once `tip` is past its deadline, the last block at
+/// which it is valid
+fn expiry(deadline: Option<u32>, tip: u32) -> (String, Option<String>) {
+    match deadline {
+        None => ("Expiry: GTC".to_string(), None),
+        Some(deadline) if tip > deadline => (
+            format!("Expiry: block {} (expired)", deadline),
+            Some(format!(
+                "Warning: the order expired {} block(s) ago and may be cancelled by any keeper",
+                tip - deadline
+            )),
+        ),
+        Some(deadline) => (
+            format!(
+                "Expiry: block {} ({} block(s) remaining)",
+                deadline,
+                deadline - tip
+            ),
+            None,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::DecimalsSource;
+    use fuels::prelude::{
+        launch_custom_provider_and_get_wallets, AssetConfig, AssetId, WalletsConfig,
+    };
+
+    #[tokio::test]
+    async fn buy_size_shows_its_quote_value() -> anyhow::Result<()> {
+        let (base, quote) = (AssetId::from([1; 32]), AssetId::from([2; 32]));
+        let assets = [AssetId::default(), base, quote]
+            .into_iter()
+            .map(|id| AssetConfig {
+                id,
+                num_coins: 1,
+                coin_amount: 1_000_000_000_000,
+            })
+            .collect();
+        let mut wallets = launch_custom_provider_and_get_wallets(
+            WalletsConfig::new_multiple_assets(1, assets),
+            None,
+            None,
+        )
+        .await?;
+        let wallet = wallets.pop().unwrap();
+        let market = SparkMarketContract::deploy(base, 9, quote, 6, wallet, 9, 0x000400).await?;
+
+        // 5 BTC at 50,000 USDC
+        let (amount, price) = (5_000_000_000, 50_000_000_000_000);
+        let buy_id = market
+            .deposit_and_open(OrderType::Buy, price, amount)
+            .await?
+            .value
+            .order_id;
+        let sell_id = market
+            .deposit_and_open(OrderType::Sell, price * 2, amount)
+            .await?
+            .value
+            .order_id;
+
+        let chain = DecimalsArgs::default();
+        let buy = market.order(buy_id).await?.value.unwrap();
+        assert_eq!(
+            size(&market, &buy, "BTC", "USDC", &chain).await?,
+            "5 BTC (250000 USDC)"
+        );
+        let sell = market.order(sell_id).await?.value.unwrap();
+        assert_eq!(size(&market, &sell, "BTC", "USDC", &chain).await?, "5 BTC");
+
+        // The same raw amounts in other units
+        let overridden = DecimalsArgs {
+            decimals_source: DecimalsSource::Override,
+            base_decimals: Some(8),
+            quote_decimals: Some(3),
+        };
+        assert_eq!(
+            size(&market, &buy, "BTC", "USDC", &overridden).await?,
+            "50 BTC (250000000 USDC)"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn expiry_counts_down_to_the_deadline() {
+        assert_eq!(expiry(None, 100), ("Expiry: GTC".to_string(), None));
+
+        // Opened at block 100 to expire after block 110, then the chain advances
+        let deadline = Some(110);
+        assert_eq!(
+            expiry(deadline, 100),
+            (
+                "Expiry: block 110 (10 block(s) remaining)".to_string(),
+                None
+            )
+        );
+        assert_eq!(
+            expiry(deadline, 110),
+            ("Expiry: block 110 (0 block(s) remaining)".to_string(), None)
+        );
+
+        let (line, warning) = expiry(deadline, 113);
+        assert_eq!(line, "Expiry: block 110 (expired)");
+        assert_eq!(
+            warning.unwrap(),
+            "Warning: the order expired 3 block(s) ago and may be cancelled by any keeper"
+        );
+    }
+}
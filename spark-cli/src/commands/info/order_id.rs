@@ -1,8 +1,13 @@
-use crate::utils::{setup, validate_contract_id, AccountType, OrderType};
+use crate::utils::{
+    encode_b256, output_writer, setup, validate_contract_id, validate_deployed_contract_id,
+    AccountType, Encoding, OrderType,
+};
 use clap::Args;
-use fuels::types::{Address, ContractId, Identity};
-use spark_market_sdk::{OrderType as ContractOrderType, /*AssetType,*/ SparkMarketContract};
-use std::str::FromStr;
+use fuels::accounts::{Account, ViewOnlyAccount};
+use spark_market_sdk::{
+    market_order_id, OrderType as ContractOrderType, /*AssetType,*/ SparkMarketContract,
+};
+use std::{io::Write, path::PathBuf};
 
 #[derive(Args, Clone)]
 #[command(about = "Create a sha256 hash (order id) of the provided information")]
@@ -31,9 +36,22 @@ pub(crate) struct OrderIdCommand {
     #[clap(long)]
     pub(crate) block_height: u32,
 
-    /// The price of the order
+    /// The order height of the order, the market's next order height when omitted
+    #[clap(long)]
+    pub(crate) order_height: Option<u64>,
+
+    /// Compute the id locally, hashing in the market id like the market does, without connecting
+    /// to the node
+    #[clap(long, requires = "order_height")]
+    pub(crate) offline: bool,
+
+    /// The encoding used to print b256 values
+    #[clap(long, value_enum, default_value_t)]
+    pub(crate) encoding: Encoding,
+
+    /// Write the result to this file instead of stdout
     #[clap(long)]
-    pub(crate) order_height: u64,
+    pub(crate) output: Option<PathBuf>,
 
     /// The contract id of the market
     #[clap(long)]
@@ -41,46 +59,218 @@ pub(crate) struct OrderIdCommand {
 
     /// The URL to query
     /// Ex. testnet.fuel.network
-    #[clap(long)]
-    pub(crate) rpc: String,
+    #[clap(long, required_unless_present = "offline")]
+    pub(crate) rpc: Option<String>,
 }
 
 impl OrderIdCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
-        let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
+        let order_type = ContractOrderType::from(self.order_type.clone());
+        let account = self.account_type.identity(&self.owner)?;
 
-        let order_type = match self.order_type {
-            OrderType::Buy => ContractOrderType::Buy,
-            OrderType::Sell => ContractOrderType::Sell,
-        };
+        let (contract_id, order_height, hash) = match self.offline {
+            true => {
+                // Every input is given, so the node is never contacted
+                let contract_id = validate_contract_id(&self.contract_id)?;
+                let order_height = self.order_height.unwrap();
+                let hash =
+                    market_order_id(contract_id, order_type, account, self.price, order_height);
+                (contract_id, order_height, hash)
+            }
+            false => {
+                let wallet = setup(self.rpc.as_ref().unwrap()).await?;
+                let contract_id =
+                    validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?)
+                        .await?;
 
-        // Connect to the deployed contract via the rpc
-        let contract = SparkMarketContract::new(contract_id, wallet).await;
+                // Connect to the deployed contract via the rpc
+                let contract = SparkMarketContract::new(contract_id, wallet).await;
 
-        let account = match &self.account_type {
-            AccountType::Address => {
-                let address = Address::from_str(&self.owner).expect("Invalid address");
-                Identity::Address(address)
-            }
-            AccountType::Contract => {
-                let address = ContractId::from_str(&self.owner).expect("Invalid contract id");
-                Identity::ContractId(address)
+                let order_height = resolve_order_height(&contract, self.order_height).await?;
+                let hash = contract
+                    .order_id(
+                        order_type,
+                        account,
+                        self.price,
+                        self.block_height,
+                        order_height,
+                    )
+                    .await?
+                    .value;
+                (contract_id, order_height, hash)
             }
         };
 
-        let hash = contract
+        let mut out = output_writer(&self.output)?;
+        match self.order_height {
+            Some(_) => writeln!(out, "\nOrder height: {}", order_height)?,
+            None => writeln!(out, "\nOrder height: {} (next)", order_height)?,
+        }
+        // Ids are only unique within the market they are hashed for
+        writeln!(out, "Market: {}", encode_b256(&contract_id, &self.encoding))?;
+        writeln!(out, "Order ID: {}", encode_b256(&hash.0, &self.encoding))?;
+
+        out.flush()?;
+
+        Ok(())
+    }
+}
+
+/// An explicit order height, or else the height the market assigns to the next order opened
+pub(crate) async fn resolve_order_height<A: Account>(
+    contract: &SparkMarketContract<A>,
+    order_height: Option<u64>,
+) -> anyhow::Result<u64> {
+    match order_height {
+        Some(order_height) => Ok(order_height),
+        None => contract.next_order_height().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuels::{
+        prelude::{
+            launch_custom_provider_and_get_wallets, AssetConfig, AssetId, ContractId, WalletsConfig,
+        },
+        types::Identity,
+    };
+
+    #[tokio::test]
+    async fn auto_height_predicts_the_next_order() -> anyhow::Result<()> {
+        let (base, quote) = (AssetId::from([1; 32]), AssetId::from([2; 32]));
+        let assets = [AssetId::default(), base, quote]
+            .into_iter()
+            .map(|id| AssetConfig {
+                id,
+                num_coins: 1,
+                coin_amount: 1_000_000_000_000,
+            })
+            .collect();
+        let wallet = launch_custom_provider_and_get_wallets(
+            WalletsConfig::new_multiple_assets(1, assets),
+            None,
+            None,
+        )
+        .await?
+        .pop()
+        .unwrap();
+        let owner = Identity::Address(wallet.address().into());
+        let market =
+            SparkMarketContract::deploy(base, 9, quote, 6, wallet.clone(), 9, 0x000400).await?;
+        let price = 70_000_000_000_000;
+
+        // An order is already open, so the next height is no longer 0
+        market
+            .deposit_and_open(ContractOrderType::Sell, price, 1_000)
+            .await?;
+
+        let order_height = resolve_order_height(&market, None).await?;
+        assert_eq!(order_height, 1);
+        let block_height = wallet.try_provider()?.latest_block_height().await?;
+        let expected = market
             .order_id(
-                order_type,
-                account,
-                self.price,
-                self.block_height,
-                self.order_height,
+                ContractOrderType::Sell,
+                owner,
+                price,
+                block_height,
+                order_height,
             )
             .await?
             .value;
 
-        println!("\nOrder ID: {}", ContractId::from(hash.0));
+        let order_id = market
+            .deposit_and_open(ContractOrderType::Sell, price, 1_000)
+            .await?
+            .value
+            .order_id;
+        assert_eq!(order_id, expected);
+        assert_eq!(
+            market.order(order_id).await?.value.unwrap().order_height,
+            order_height
+        );
+
+        // An explicit height is used as given
+        assert_eq!(resolve_order_height(&market, Some(7)).await?, 7);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn offline_id_is_separated_by_market() -> anyhow::Result<()> {
+        let (base, quote) = (AssetId::from([1; 32]), AssetId::from([2; 32]));
+        let assets = [AssetId::default(), base, quote]
+            .into_iter()
+            .map(|id| AssetConfig {
+                id,
+                num_coins: 1,
+                coin_amount: 1_000_000_000_000,
+            })
+            .collect();
+        let wallet = launch_custom_provider_and_get_wallets(
+            WalletsConfig::new_multiple_assets(1, assets),
+            None,
+            None,
+        )
+        .await?
+        .pop()
+        .unwrap();
+        let owner = Identity::Address(wallet.address().into());
+        let price = 70_000_000_000_000;
+
+        let mut ids = Vec::new();
+        for _ in 0..2 {
+            let market =
+                SparkMarketContract::deploy(base, 9, quote, 6, wallet.clone(), 9, 0x000400).await?;
+            let expected = market
+                .order_id(ContractOrderType::Sell, owner, price, 1, 0)
+                .await?
+                .value;
+            let market_id = ContractId::from(*market.id());
+            assert_eq!(
+                market_order_id(market_id, ContractOrderType::Sell, owner, price, 0),
+                expected
+            );
+            ids.push(expected);
+        }
+        assert_ne!(ids[0], ids[1]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn offline_id_needs_no_node() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join(format!("order-id-offline-{}", std::process::id()));
+        let output = dir.join("order_id.txt");
+        let owner = format!("0x{}", "11".repeat(32));
+        let contract_id = ContractId::from([7; 32]);
+        let command = OrderIdCommand {
+            order_type: OrderType::Sell,
+            owner: owner.clone(),
+            account_type: AccountType::Address,
+            price: 70_000_000_000_000,
+            block_height: 1,
+            order_height: Some(3),
+            offline: true,
+            encoding: Encoding::default(),
+            output: Some(output.clone()),
+            contract_id: format!("0x{}", hex::encode(*contract_id)),
+            rpc: None,
+        };
+        command.run().await?;
+
+        let expected = market_order_id(
+            contract_id,
+            ContractOrderType::Sell,
+            AccountType::Address.identity(&owner)?,
+            70_000_000_000_000,
+            3,
+        );
+        let printed = std::fs::read_to_string(&output)?;
+        std::fs::remove_dir_all(&dir)?;
+        assert!(printed.contains("Order height: 3\n"));
+        assert!(printed.contains(&encode_b256(&expected.0, &Encoding::default())));
 
         Ok(())
     }
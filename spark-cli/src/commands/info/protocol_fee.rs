@@ -1,10 +1,16 @@
-use crate::utils::{setup, validate_contract_id};
+use crate::utils::{output_writer, setup, validate_deployed_contract_id};
 use clap::Args;
+use fuels::accounts::ViewOnlyAccount;
 use spark_market_sdk::SparkMarketContract;
+use std::{io::Write, path::PathBuf};
 
 #[derive(Args, Clone)]
 #[command(about = "Query the protocol fee")]
 pub(crate) struct ProtocolFeeCommand {
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
     /// The contract id of the market
     #[clap(long)]
     pub(crate) contract_id: String,
@@ -18,14 +24,18 @@ pub(crate) struct ProtocolFeeCommand {
 impl ProtocolFeeCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
         let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
 
         // Connect to the deployed contract via the rpc
         let contract = SparkMarketContract::new(contract_id, wallet).await;
 
         let protocol_fee = contract.protocol_fee().await?.value;
 
-        println!("Protocol Fee: {:?}", protocol_fee);
+        let mut out = output_writer(&self.output)?;
+        writeln!(out, "Protocol Fee: {:?}", protocol_fee)?;
+
+        out.flush()?;
 
         Ok(())
     }
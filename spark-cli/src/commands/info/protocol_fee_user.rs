@@ -1,8 +1,8 @@
-use crate::utils::{setup, validate_contract_id, AccountType};
+use crate::utils::{output_writer, setup, validate_deployed_contract_id, AccountType};
 use clap::Args;
-use fuels::types::{Address, ContractId, Identity};
+use fuels::accounts::ViewOnlyAccount;
 use spark_market_sdk::SparkMarketContract;
-use std::str::FromStr;
+use std::{io::Write, path::PathBuf};
 
 #[derive(Args, Clone)]
 #[command(about = "Query the protocol fee user")]
@@ -15,6 +15,10 @@ pub(crate) struct ProtocolFeeUserCommand {
     #[clap(long)]
     pub(crate) account_type: AccountType,
 
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
     /// The contract id of the market
     #[clap(long)]
     pub(crate) contract_id: String,
@@ -28,28 +32,24 @@ pub(crate) struct ProtocolFeeUserCommand {
 impl ProtocolFeeUserCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
         let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
 
         // Connect to the deployed contract via the rpc
         let contract = SparkMarketContract::new(contract_id, wallet).await;
 
-        let account = match self.account_type {
-            AccountType::Address => {
-                let address = Address::from_str(&self.account_id).expect("Invalid address");
-                Identity::Address(address)
-            }
-            AccountType::Contract => {
-                let address = ContractId::from_str(&self.account_id).expect("Invalid contract id");
-                Identity::ContractId(address)
-            }
-        };
+        let account = self.account_type.identity(&self.account_id)?;
 
         let protocol_fee_user = contract.protocol_fee_user(account).await?.value;
 
-        println!(
+        let mut out = output_writer(&self.output)?;
+        writeln!(
+            out,
             "Protocol Fee: for {:?} (maker_fee, taker_fee) ({}, {})",
             account, protocol_fee_user.0, protocol_fee_user.1
-        );
+        )?;
+
+        out.flush()?;
 
         Ok(())
     }
@@ -1,8 +1,8 @@
-use crate::utils::{setup, validate_contract_id, AccountType};
+use crate::utils::{output_writer, setup, validate_deployed_contract_id, AccountType};
 use clap::Args;
-use fuels::types::{Address, ContractId, Identity};
+use fuels::accounts::ViewOnlyAccount;
 use spark_market_sdk::SparkMarketContract;
-use std::str::FromStr;
+use std::{io::Write, path::PathBuf};
 
 #[derive(Args, Clone)]
 #[command(about = "Query the protocol fee user amount")]
@@ -19,6 +19,10 @@ pub(crate) struct ProtocolFeeUserAmountCommand {
     #[clap(long)]
     pub(crate) account_type: AccountType,
 
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
     /// The contract id of the market
     #[clap(long)]
     pub(crate) contract_id: String,
@@ -32,31 +36,27 @@ pub(crate) struct ProtocolFeeUserAmountCommand {
 impl ProtocolFeeUserAmountCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
         let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
 
         // Connect to the deployed contract via the rpc
         let contract = SparkMarketContract::new(contract_id, wallet).await;
 
-        let account = match self.account_type {
-            AccountType::Address => {
-                let address = Address::from_str(&self.account_id).expect("Invalid address");
-                Identity::Address(address)
-            }
-            AccountType::Contract => {
-                let address = ContractId::from_str(&self.account_id).expect("Invalid contract id");
-                Identity::ContractId(address)
-            }
-        };
+        let account = self.account_type.identity(&self.account_id)?;
 
         let protocol_fee_user_amount = contract
             .protocol_fee_user_amount(self.amount, account)
             .await?
             .value;
 
-        println!(
+        let mut out = output_writer(&self.output)?;
+        writeln!(
+            out,
             "Protocol Fee Amount: for {:?} of {} (maker_fee, taker_fee) ({}, {})",
             account, self.amount, protocol_fee_user_amount.0, protocol_fee_user_amount.1
-        );
+        )?;
+
+        out.flush()?;
 
         Ok(())
     }
@@ -0,0 +1,162 @@
+use crate::utils::{
+    format_amount, print_result, setup, validate_deployed_contract_id, DecimalsArgs, OrderType,
+};
+use clap::Args;
+use fuels::accounts::{Account, ViewOnlyAccount};
+use spark_market_sdk::{OrderType as ContractOrderType, SparkMarketContract};
+
+#[derive(Args, Clone)]
+#[command(about = "Query the deposit an order needs before it is opened")]
+pub(crate) struct RequiredDepositCommand {
+    /// The type of order
+    #[clap(long)]
+    pub(crate) order_type: OrderType,
+
+    /// The price of the order
+    #[clap(long)]
+    pub(crate) price: u64,
+
+    /// The amount of asset
+    #[clap(long)]
+    pub(crate) amount: u64,
+
+    #[clap(flatten)]
+    pub(crate) decimals: DecimalsArgs,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl RequiredDepositCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+
+        let order_type = ContractOrderType::from(self.order_type.clone());
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet).await;
+
+        let (line, deposit) = required_deposit(
+            &contract,
+            self.amount,
+            order_type,
+            self.price,
+            &self.decimals,
+        )
+        .await?;
+        print_result(line, deposit);
+
+        Ok(())
+    }
+}
+
+/// The deposit for the signer to open the order, as a line naming the asset and the raw amount
+async fn required_deposit<A: Account>(
+    contract: &SparkMarketContract<A>,
+    amount: u64,
+    order_type: ContractOrderType,
+    price: u64,
+    decimals: &DecimalsArgs,
+) -> anyhow::Result<(String, u64)> {
+    let config = contract.config().await?;
+    let (asset, deposit) = contract.required_deposit(amount, order_type, price).await?;
+
+    let (base_decimals, quote_decimals) = decimals.resolve(&config);
+    let (asset_type, decimals) = match asset == config.base_asset {
+        true => ("Base", base_decimals),
+        false => ("Quote", quote_decimals),
+    };
+    let line = format!(
+        "Required Deposit: {} ({}) of {} asset 0x{}",
+        deposit,
+        format_amount(deposit, decimals),
+        asset_type,
+        asset
+    );
+
+    Ok((line, deposit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::DecimalsSource;
+    use fuels::prelude::{
+        launch_custom_provider_and_get_wallets, AssetConfig, AssetId, WalletsConfig,
+    };
+
+    #[tokio::test]
+    async fn buy_deposit_is_the_quote_value_of_the_amount() -> anyhow::Result<()> {
+        let (base, quote) = (AssetId::from([1; 32]), AssetId::from([2; 32]));
+        let wallet = launch_custom_provider_and_get_wallets(
+            WalletsConfig::new_multiple_assets(
+                1,
+                vec![AssetConfig {
+                    id: AssetId::default(),
+                    num_coins: 1,
+                    coin_amount: 1_000_000_000,
+                }],
+            ),
+            None,
+            None,
+        )
+        .await?
+        .pop()
+        .unwrap();
+        let market = SparkMarketContract::deploy(base, 8, quote, 6, wallet, 9, 0x000400).await?;
+
+        // 0.001 BTC at 70,000$ takes 70$ of quote
+        let amount = 100_000;
+        let price = 70_000_000_000_000;
+        let chain = DecimalsArgs::default();
+        let (line, deposit) =
+            required_deposit(&market, amount, ContractOrderType::Buy, price, &chain).await?;
+
+        assert_eq!(deposit, amount * price / 10_u64.pow(8 + 9 - 6));
+        assert_eq!(
+            line,
+            format!("Required Deposit: 70000000 (70) of Quote asset 0x{}", quote)
+        );
+
+        let (line, deposit) =
+            required_deposit(&market, amount, ContractOrderType::Sell, price, &chain).await?;
+        assert_eq!(deposit, amount);
+        assert_eq!(
+            line,
+            format!("Required Deposit: 100000 (0.001) of Base asset 0x{}", base)
+        );
+
+        // Overridden decimals change how the same raw deposit is printed, not the deposit
+        let overridden = DecimalsArgs {
+            decimals_source: DecimalsSource::Override,
+            base_decimals: Some(5),
+            quote_decimals: Some(9),
+        };
+        let (line, deposit) =
+            required_deposit(&market, amount, ContractOrderType::Sell, price, &overridden).await?;
+        assert_eq!(deposit, amount);
+        assert_eq!(
+            line,
+            format!("Required Deposit: 100000 (1) of Base asset 0x{}", base)
+        );
+        let (line, _) =
+            required_deposit(&market, amount, ContractOrderType::Buy, price, &overridden).await?;
+        assert_eq!(
+            line,
+            format!(
+                "Required Deposit: 70000000 (0.07) of Quote asset 0x{}",
+                quote
+            )
+        );
+
+        Ok(())
+    }
+}
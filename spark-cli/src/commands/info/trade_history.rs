@@ -0,0 +1,397 @@
+use crate::utils::{
+    format_json, format_price, output_writer, setup, validate_deployed_contract_id, AccountType,
+};
+use clap::Args;
+use fuels::{
+    accounts::{provider::Provider, Account, ViewOnlyAccount},
+    types::Identity,
+};
+use serde_json::json;
+use spark_market_sdk::{SparkMarketContract, TradeOrderEvent};
+use std::{future::Future, io::Write, path::PathBuf, time::Duration};
+
+/// How often follow mode checks for new blocks
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Args, Clone)]
+#[command(about = "Query the market for the fills of a trader within a block range")]
+pub(crate) struct TradeHistoryCommand {
+    /// The b256 id of the trader
+    #[clap(long)]
+    pub(crate) trader: String,
+
+    /// The type of account
+    #[clap(long)]
+    pub(crate) account_type: AccountType,
+
+    /// The first block to scan
+    #[clap(long)]
+    pub(crate) from_block: u32,
+
+    /// The last block to scan, defaults to the latest block
+    #[clap(long, conflicts_with = "follow")]
+    pub(crate) to_block: Option<u32>,
+
+    /// Keep printing new fills as they land until interrupted with Ctrl-C
+    #[clap(long)]
+    pub(crate) follow: bool,
+
+    /// Print the fills as a JSON array
+    #[clap(long, conflicts_with = "csv")]
+    pub(crate) json: bool,
+
+    /// Print the fills as CSV rows
+    #[clap(long)]
+    pub(crate) csv: bool,
+
+    /// Decimal places of the prices in the text output, defaults to the market's price decimals
+    #[clap(long)]
+    pub(crate) price_precision: Option<u32>,
+
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl TradeHistoryCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let provider = wallet.try_provider()?.clone();
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+        let to_block = match self.to_block {
+            Some(to_block) => to_block,
+            None => provider.latest_block_height().await?,
+        };
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet).await;
+
+        let trader = self.account_type.identity(&self.trader)?;
+
+        let price_decimals = contract.config().await?.price_decimals;
+        let events = contract.trade_events(self.from_block, to_block).await?;
+        let fills = fills(&events, &trader);
+
+        let mut out = output_writer(&self.output)?;
+        if self.json && !self.follow {
+            let rows: Vec<_> = fills.iter().map(fill_json).collect();
+            writeln!(out, "{}", format_json(&json!(rows)))?;
+        } else {
+            if self.csv {
+                writeln!(out, "block_height,side,price,amount,counterparty")?;
+            } else if !self.json {
+                writeln!(
+                    out,
+                    "\nFills between blocks {} and {}: {}",
+                    self.from_block,
+                    to_block,
+                    fills.len()
+                )?;
+            }
+            for fill in &fills {
+                self.write_fill(&mut out, fill, price_decimals)?;
+            }
+        }
+        out.flush()?;
+
+        if self.follow {
+            let shutdown = async {
+                let _ = tokio::signal::ctrl_c().await;
+            };
+            self.follow(
+                &contract,
+                &provider,
+                &trader,
+                to_block + 1,
+                price_decimals,
+                POLL_INTERVAL,
+                &mut out,
+                shutdown,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Polls for blocks after `next_block` and prints the trader's fills in them until `shutdown`
+    /// resolves; each block is scanned once so the last printed block is never repeated
+    #[allow(clippy::too_many_arguments)]
+    async fn follow(
+        &self,
+        contract: &SparkMarketContract<impl Account>,
+        provider: &Provider,
+        trader: &Identity,
+        mut next_block: u32,
+        price_decimals: u32,
+        poll_interval: Duration,
+        out: &mut impl Write,
+        shutdown: impl Future<Output = ()>,
+    ) -> anyhow::Result<()> {
+        tokio::pin!(shutdown);
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = tokio::time::sleep(poll_interval) => {}
+            }
+
+            let latest = provider.latest_block_height().await?;
+            if latest < next_block {
+                continue;
+            }
+            let events = contract.trade_events(next_block, latest).await?;
+            for fill in &fills(&events, trader) {
+                self.write_fill(out, fill, price_decimals)?;
+            }
+            out.flush()?;
+            next_block = latest + 1;
+        }
+
+        Ok(())
+    }
+
+    /// Writes a single fill in the selected format; JSON fills are written one object per line
+    /// and keep the price in market units like CSV rows
+    fn write_fill(
+        &self,
+        out: &mut impl Write,
+        fill: &Fill,
+        price_decimals: u32,
+    ) -> anyhow::Result<()> {
+        if self.json {
+            writeln!(out, "{}", fill_json(fill))?;
+        } else if self.csv {
+            writeln!(
+                out,
+                "{},{},{},{},{}",
+                fill.block_height,
+                fill.side,
+                fill.price,
+                fill.amount,
+                identity_hex(&fill.counterparty)
+            )?;
+        } else {
+            writeln!(
+                out,
+                "  Block {}: {} {} at {} against {}",
+                fill.block_height,
+                fill.side,
+                fill.amount,
+                format_price(
+                    fill.price,
+                    price_decimals,
+                    self.price_precision.unwrap_or(price_decimals)
+                ),
+                identity_hex(&fill.counterparty)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A trade seen from the trader's side
+#[derive(Debug, PartialEq)]
+struct Fill {
+    block_height: u32,
+    side: &'static str,
+    price: u64,
+    amount: u64,
+    counterparty: Identity,
+}
+
+/// Keeps the trades where the trader was the seller or the buyer
+fn fills(events: &[TradeOrderEvent], trader: &Identity) -> Vec<Fill> {
+    events
+        .iter()
+        .filter_map(|event| {
+            let (side, counterparty) = if event.order_seller == *trader {
+                ("sell", event.order_buyer)
+            } else if event.order_buyer == *trader {
+                ("buy", event.order_seller)
+            } else {
+                return None;
+            };
+            Some(Fill {
+                block_height: event.block_height,
+                side,
+                price: event.trade_price,
+                amount: event.trade_size,
+                counterparty,
+            })
+        })
+        .collect()
+}
+
+fn fill_json(fill: &Fill) -> serde_json::Value {
+    json!({
+        "block_height": fill.block_height,
+        "side": fill.side,
+        "price": fill.price,
+        "amount": fill.amount,
+        "counterparty": identity_hex(&fill.counterparty),
+    })
+}
+
+fn identity_hex(identity: &Identity) -> String {
+    match identity {
+        Identity::Address(address) => format!("0x{}", hex::encode(**address)),
+        Identity::ContractId(contract_id) => format!("0x{}", hex::encode(**contract_id)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuels::{
+        prelude::{
+            launch_custom_provider_and_get_wallets, AssetConfig, AssetId, WalletUnlocked,
+            WalletsConfig,
+        },
+        types::Bits256,
+    };
+    use spark_market_sdk::OrderType;
+
+    // 1 base at a price of 2 quote
+    const AMOUNT: u64 = 1_000_000_000;
+    const PRICE: u64 = 2_000_000_000;
+
+    async fn market() -> anyhow::Result<(SparkMarketContract, WalletUnlocked, WalletUnlocked)> {
+        let (base, quote) = (AssetId::from([1; 32]), AssetId::from([2; 32]));
+        let assets = [AssetId::default(), base, quote]
+            .into_iter()
+            .map(|id| AssetConfig {
+                id,
+                num_coins: 1,
+                coin_amount: 1_000_000_000_000,
+            })
+            .collect();
+        let mut wallets = launch_custom_provider_and_get_wallets(
+            WalletsConfig::new_multiple_assets(3, assets),
+            None,
+            None,
+        )
+        .await?;
+        let (buyer, seller, owner) = (
+            wallets.pop().unwrap(),
+            wallets.pop().unwrap(),
+            wallets.pop().unwrap(),
+        );
+
+        let market = SparkMarketContract::deploy(base, 9, quote, 6, owner, 9, 0x000400).await?;
+        Ok((market, seller, buyer))
+    }
+
+    async fn trade(
+        market: &SparkMarketContract,
+        seller: &WalletUnlocked,
+        buyer: &WalletUnlocked,
+    ) -> anyhow::Result<()> {
+        let (base, quote) = (AssetId::from([1; 32]), AssetId::from([2; 32]));
+        let sell = market.with_account(seller).await?;
+        sell.deposit(AMOUNT, base).await?;
+        let sell_id: Bits256 = sell
+            .open_order(AMOUNT, OrderType::Sell, PRICE)
+            .await?
+            .value
+            .order_id;
+        let buy = market.with_account(buyer).await?;
+        buy.deposit(2_000_000, quote).await?;
+        let buy_id = buy
+            .open_order(AMOUNT, OrderType::Buy, PRICE)
+            .await?
+            .value
+            .order_id;
+        market.match_order_pair(sell_id, buy_id).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn history_contains_the_matched_fill() -> anyhow::Result<()> {
+        let (market, seller, buyer) = market().await?;
+        trade(&market, &seller, &buyer).await?;
+
+        let height = seller.try_provider()?.latest_block_height().await?;
+        let events = market.trade_events(0, height).await?;
+        let seller_identity = Identity::Address(seller.address().into());
+        let buyer_identity = Identity::Address(buyer.address().into());
+
+        let history = fills(&events, &seller_identity);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].side, "sell");
+        assert_eq!(history[0].price, PRICE);
+        assert_eq!(history[0].amount, AMOUNT);
+        assert_eq!(history[0].counterparty, buyer_identity);
+
+        assert_eq!(fills(&events, &buyer_identity)[0].side, "buy");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn follow_prints_only_new_fills() -> anyhow::Result<()> {
+        let (market, seller, buyer) = market().await?;
+        let provider = seller.try_provider()?.clone();
+
+        // This fill is part of the history printed before following starts
+        trade(&market, &seller, &buyer).await?;
+        let to_block = provider.latest_block_height().await?;
+
+        let command = TradeHistoryCommand {
+            trader: String::new(),
+            account_type: AccountType::Address,
+            from_block: 0,
+            to_block: None,
+            follow: true,
+            price_precision: None,
+            json: false,
+            csv: true,
+            output: None,
+            contract_id: String::new(),
+            rpc: String::new(),
+        };
+        let seller_identity = Identity::Address(seller.address().into());
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let mut out = Vec::new();
+
+        let follow = command.follow(
+            &market,
+            &provider,
+            &seller_identity,
+            to_block + 1,
+            9,
+            Duration::from_millis(50),
+            &mut out,
+            async {
+                let _ = stopped.await;
+            },
+        );
+        let live = async {
+            trade(&market, &seller, &buyer).await?;
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            let _ = stop.send(());
+            anyhow::Ok(())
+        };
+        let (followed, traded) = tokio::join!(follow, live);
+        followed?;
+        traded?;
+
+        let out = String::from_utf8(out)?;
+        let rows: Vec<_> = out.lines().collect();
+        assert_eq!(rows.len(), 1);
+        let block_height: u32 = rows[0].split(',').next().unwrap().parse()?;
+        assert!(block_height > to_block);
+        assert!(rows[0].contains(&format!(",sell,{PRICE},{AMOUNT},")));
+
+        Ok(())
+    }
+}
@@ -1,8 +1,12 @@
-use crate::utils::{setup, validate_contract_id, AccountType};
+use crate::utils::{
+    encode_b256, output_writer, print_warning, render_table, setup, validate_deployed_contract_id,
+    AccountType, Align, Encoding, OutputFormat,
+};
 use clap::Args;
-use fuels::types::{Address, ContractId, Identity};
-use spark_market_sdk::SparkMarketContract;
-use std::str::FromStr;
+use fuels::accounts::ViewOnlyAccount;
+use fuels::core::codec::DecoderConfig;
+use spark_market_sdk::{OrderDetails, SparkMarketContract};
+use std::{io::Write, path::PathBuf};
 
 #[derive(Args, Clone)]
 #[command(about = "Query the market for the currently open orders for the user")]
@@ -15,6 +19,22 @@ pub(crate) struct UserOrdersCommand {
     #[clap(long)]
     pub(crate) account_type: AccountType,
 
+    /// The number of order ids fetched per call if the user has too many to fetch at once
+    #[clap(long, default_value_t = 1000)]
+    pub(crate) page_size: u64,
+
+    /// The encoding used to print b256 values
+    #[clap(long, value_enum, default_value_t)]
+    pub(crate) encoding: Encoding,
+
+    /// Print the ids only, or a table with the type, price and amount of each order
+    #[clap(long, value_enum, default_value_t)]
+    pub(crate) format: OutputFormat,
+
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
     /// The contract id of the market
     #[clap(long)]
     pub(crate) contract_id: String,
@@ -28,32 +48,110 @@ pub(crate) struct UserOrdersCommand {
 impl UserOrdersCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
         let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
 
         // Connect to the deployed contract via the rpc
         let contract = SparkMarketContract::new(contract_id, wallet).await;
 
-        let account = match self.account_type {
-            AccountType::Address => {
-                let address = Address::from_str(&self.account_id).expect("Invalid address");
-                Identity::Address(address)
-            }
-            AccountType::Contract => {
-                let address = ContractId::from_str(&self.account_id).expect("Invalid contract id");
-                Identity::ContractId(address)
-            }
-        };
+        let account = self.account_type.identity(&self.account_id)?;
+
+        let orders = contract
+            .all_user_orders(account, self.page_size, DecoderConfig::default())
+            .await?;
+
+        if let Some(warning) = orders.warning {
+            print_warning(format!("Warning: {}", warning));
+        }
 
-        let orders = contract.user_orders(account).await?.value;
+        let orders = orders.orders;
 
         if orders.is_empty() {
             anyhow::bail!("User has no open orders");
         }
 
-        for order in orders {
-            println!("{:?}", Address::new(order.0));
+        let mut out = output_writer(&self.output)?;
+        match self.format {
+            OutputFormat::Plain => {
+                for order in orders {
+                    writeln!(out, "{}", encode_b256(&order.0, &self.encoding))?;
+                }
+            }
+            OutputFormat::Table => {
+                let mut details = Vec::new();
+                for order in orders {
+                    // Orders filled or cancelled since the ids were fetched are left out
+                    if let Some(order) = contract.order_by_id(order).await? {
+                        details.push(order);
+                    }
+                }
+                writeln!(out, "{}", orders_table(&details, &self.encoding))?;
+            }
         }
 
+        out.flush()?;
+
         Ok(())
     }
 }
+
+fn orders_table(orders: &[OrderDetails], encoding: &Encoding) -> String {
+    let rows: Vec<Vec<String>> = orders
+        .iter()
+        .map(|order| {
+            vec![
+                encode_b256(&order.id.0, encoding),
+                format!("{:?}", order.order_type),
+                order.price.to_string(),
+                order.amount.to_string(),
+            ]
+        })
+        .collect();
+    let columns = [
+        ("Order ID", Align::Left),
+        ("Type", Align::Left),
+        ("Price", Align::Right),
+        ("Amount", Align::Right),
+    ];
+    render_table(&columns, &rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuels::types::{Address, Bits256, Identity};
+    use spark_market_sdk::OrderType;
+
+    #[test]
+    fn table_has_headers_and_a_row_per_order() {
+        let order = |id, order_type, price, amount| OrderDetails {
+            id: Bits256([id; 32]),
+            owner: Identity::Address(Address::from([9; 32])),
+            order_type,
+            price,
+            amount,
+            block_height: 1,
+            order_height: 0,
+        };
+        let table = orders_table(
+            &[
+                order(1, OrderType::Sell, 70_000_000_000_000, 100_000),
+                order(2, OrderType::Buy, 69_500_000_000_000, 5),
+            ],
+            &Encoding::Hex,
+        );
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 6);
+        assert!(lines[1].starts_with("| Order ID "));
+        assert!(lines[1].contains("| Type |"));
+        assert!(lines[1].ends_with("| Amount |"));
+        assert_eq!(
+            lines[3],
+            format!("| {} | Sell | 70000000000000 | 100000 |", "01".repeat(32))
+        );
+        // Numbers are right-justified
+        assert!(lines[4].ends_with("| Buy  | 69500000000000 |      5 |"));
+        assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+    }
+}
@@ -0,0 +1,78 @@
+use crate::utils::{output_writer, setup, validate_deployed_contract_id, AccountType};
+use clap::Args;
+use fuels::accounts::ViewOnlyAccount;
+use spark_market_sdk::SparkMarketContract;
+use std::{io::Write, path::PathBuf};
+
+#[derive(Args, Clone)]
+#[command(about = "Check that the open orders of an account add up to its locked balance")]
+pub(crate) struct VerifyAccountCommand {
+    /// The b256 id of the account
+    #[clap(long)]
+    pub(crate) account_id: String,
+
+    /// The type of account
+    #[clap(long)]
+    pub(crate) account_type: AccountType,
+
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl VerifyAccountCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet).await;
+
+        let account = self.account_type.identity(&self.account_id)?;
+
+        let reconciliation = contract.reconcile_locked(account).await?;
+
+        let mut out = output_writer(&self.output)?;
+        writeln!(
+            out,
+            "\nLocked base: {} (open orders {}, delta {})",
+            reconciliation.reported.base,
+            reconciliation.expected.base,
+            reconciliation.base_delta()
+        )?;
+        writeln!(
+            out,
+            "Locked quote: {} (open orders {}, delta {})",
+            reconciliation.reported.quote,
+            reconciliation.expected.quote,
+            reconciliation.quote_delta()
+        )?;
+        writeln!(
+            out,
+            "Verification: {}",
+            if reconciliation.is_balanced() {
+                "PASS"
+            } else {
+                "FAIL"
+            }
+        )?;
+
+        out.flush()?;
+
+        if !reconciliation.is_balanced() {
+            anyhow::bail!("Locked balance mismatch: {}", reconciliation);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use crate::utils::{
+    encode_b256, output_writer, setup, validate_deployed_contract_id, AccountType, Encoding,
+    OrderType,
+};
+use clap::Args;
+use fuels::accounts::ViewOnlyAccount;
+use spark_market_sdk::{OrderType as ContractOrderType, SparkMarketContract};
+use std::{io::Write, path::PathBuf};
+
+#[derive(Args, Clone)]
+#[command(about = "Compute an order id and verify it against the open order in the market")]
+pub(crate) struct VerifyOrderIdCommand {
+    /// The type of order
+    #[clap(long)]
+    pub(crate) order_type: OrderType,
+
+    /// The b256 id of the account
+    #[clap(long)]
+    pub(crate) owner: String,
+
+    /// The type of account
+    #[clap(long)]
+    pub(crate) account_type: AccountType,
+
+    /// The price of the order
+    #[clap(long)]
+    pub(crate) price: u64,
+
+    /// The block height the order was opened at
+    #[clap(long)]
+    pub(crate) block_height: u32,
+
+    /// The order height assigned by the market
+    #[clap(long)]
+    pub(crate) order_height: u64,
+
+    /// The encoding used to print b256 values
+    #[clap(long, value_enum, default_value_t)]
+    pub(crate) encoding: Encoding,
+
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl VerifyOrderIdCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+
+        let order_type = ContractOrderType::from(self.order_type.clone());
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet).await;
+
+        let account = self.account_type.identity(&self.owner)?;
+
+        let hash = contract
+            .order_id(
+                order_type.clone(),
+                account,
+                self.price,
+                self.block_height,
+                self.order_height,
+            )
+            .await?
+            .value;
+
+        let verified = contract
+            .verify_order_id(
+                order_type,
+                account,
+                self.price,
+                self.block_height,
+                self.order_height,
+            )
+            .await?;
+
+        let mut out = output_writer(&self.output)?;
+        writeln!(out, "\nOrder ID: {}", encode_b256(&hash.0, &self.encoding))?;
+        writeln!(
+            out,
+            "Verification: {}",
+            if verified { "PASS" } else { "FAIL" }
+        )?;
+
+        out.flush()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use crate::utils::{output_writer, setup, validate_deployed_contract_id, SymbolMap};
+use clap::Args;
+use fuels::{
+    accounts::ViewOnlyAccount,
+    types::{Address, Identity},
+};
+use spark_market_sdk::SparkMarketContract;
+use std::{io::Write, path::PathBuf};
+
+#[derive(Args, Clone)]
+#[command(about = "Show the account the CLI signs with and its balances for the market")]
+pub(crate) struct WhoamiCommand {
+    /// A JSON file of {"<asset id>": "<symbol>"} naming assets in the output
+    #[clap(long)]
+    pub(crate) symbols: Option<PathBuf>,
+
+    /// Write the result to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
+    /// The contract id of the market
+    #[clap(long)]
+    pub(crate) contract_id: String,
+
+    /// The URL to query
+    /// Ex. testnet.fuel.network
+    #[clap(long)]
+    pub(crate) rpc: String,
+}
+
+impl WhoamiCommand {
+    pub(crate) async fn run(&self) -> anyhow::Result<()> {
+        let wallet = setup(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
+        let provider = wallet.try_provider()?;
+
+        // Connect to the deployed contract via the rpc
+        let contract = SparkMarketContract::new(contract_id, wallet.clone()).await;
+
+        let symbols = SymbolMap::load(self.symbols.as_deref())?;
+        let config = contract.config().await?;
+        let (base_asset, quote_asset) = (config.base_asset, config.quote_asset);
+        let fee_asset = *provider.base_asset_id();
+
+        let account = contract
+            .account(Identity::Address(wallet.address().into()))
+            .await?
+            .value;
+
+        let mut out = output_writer(&self.output)?;
+        writeln!(out, "\nSigner: {}", signer_address(&wallet))?;
+        writeln!(out, "RPC: {}", provider.url())?;
+
+        writeln!(out, "\nWallet balances")?;
+        writeln!(
+            out,
+            "  Base asset ({}): {}",
+            symbols.label(&base_asset),
+            wallet.get_asset_balance(&base_asset).await?
+        )?;
+        writeln!(
+            out,
+            "  Quote asset ({}): {}",
+            symbols.label(&quote_asset),
+            wallet.get_asset_balance(&quote_asset).await?
+        )?;
+        writeln!(
+            out,
+            "  Fee asset ({}): {}",
+            symbols.label(&fee_asset),
+            wallet.get_asset_balance(&fee_asset).await?
+        )?;
+
+        writeln!(out, "\nMarket account")?;
+        writeln!(out, "  Liquid base: {}", account.liquid.base)?;
+        writeln!(out, "  Liquid quote: {}", account.liquid.quote)?;
+        writeln!(out, "  Locked base: {}", account.locked.base)?;
+        writeln!(out, "  Locked quote: {}", account.locked.quote)?;
+
+        out.flush()?;
+
+        Ok(())
+    }
+}
+
+pub(crate) fn signer_address(wallet: &impl ViewOnlyAccount) -> String {
+    format!("0x{}", hex::encode(*Address::from(wallet.address())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuels::{
+        crypto::SecretKey, prelude::WalletUnlocked, test_helpers::launch_provider_and_get_wallet,
+    };
+    use std::str::FromStr;
+
+    #[tokio::test]
+    async fn prints_the_wallet_loaded_by_setup() -> anyhow::Result<()> {
+        let node_wallet = launch_provider_and_get_wallet().await?;
+        let rpc = node_wallet.try_provider()?.url().to_string();
+
+        let secret = "0x862512a2363db2b3a375c0d4bbbd27172180d89f23f2e259bac850ab02619301";
+        std::env::set_var("WALLET_SECRET", secret);
+
+        let wallet = setup(&rpc).await?;
+        let expected = WalletUnlocked::new_from_private_key(SecretKey::from_str(secret)?, None);
+
+        assert_eq!(signer_address(&wallet), signer_address(&expected));
+
+        Ok(())
+    }
+}
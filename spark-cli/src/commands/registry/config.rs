@@ -1,5 +1,6 @@
-use crate::utils::{setup, validate_contract_id};
+use crate::utils::{setup_wallet, validate_deployed_contract_id};
 use clap::Args;
+use fuels::accounts::ViewOnlyAccount;
 use spark_registry_sdk::SparkRegistryContract;
 
 #[derive(Args, Clone)]
@@ -17,8 +18,9 @@ pub(crate) struct ConfigCommand {
 
 impl ConfigCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
-        let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
+        let wallet = setup_wallet(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
 
         // Connect to the deployed contract via the rpc
         let contract = SparkRegistryContract::new(contract_id, wallet).await;
@@ -1,4 +1,4 @@
-use crate::utils::setup;
+use crate::utils::{print_info, print_result, setup_wallet};
 use clap::Args;
 use fuels::accounts::ViewOnlyAccount;
 use spark_registry_sdk::SparkRegistryContract;
@@ -14,11 +14,11 @@ pub(crate) struct DeployCommand {
 
 impl DeployCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
-        let wallet = setup(&self.rpc).await?;
+        let wallet = setup_wallet(&self.rpc).await?;
 
         // Initial balance prior to contract call - used to calculate contract interaction cost
         let balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
         let version = SparkRegistryContract::sdk_version();
@@ -28,18 +28,21 @@ impl DeployCommand {
 
         // Balance post-deployment
         let new_balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
-        println!(
-            "\n MarketRegistry version {} ({}) deployed to: 0x{}",
-            contract.contract_str_version().await?,
-            version,
-            contract.id()
+        print_result(
+            format!(
+                "\n MarketRegistry version {} ({}) deployed to: 0x{}",
+                contract.contract_str_version().await?,
+                version,
+                contract.id()
+            ),
+            format!("0x{}", contract.id()),
         );
-        println!("Deployment cost: {}", balance - new_balance);
-        println!("Owner address: {}", wallet.address());
-        println!("               0x{}", wallet.address().hash());
+        print_info(format!("Deployment cost: {}", balance - new_balance));
+        print_info(format!("Owner address: {}", wallet.address()));
+        print_info(format!("               0x{}", wallet.address().hash()));
 
         Ok(())
     }
@@ -1,5 +1,6 @@
-use crate::utils::{setup, validate_contract_id};
+use crate::utils::{setup_wallet, validate_deployed_contract_id};
 use clap::Args;
+use fuels::accounts::ViewOnlyAccount;
 use fuels::types::AssetId;
 use spark_registry_sdk::SparkRegistryContract;
 use std::str::FromStr;
@@ -27,14 +28,14 @@ pub(crate) struct MarketsCommand {
 
 impl MarketsCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
-        let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
+        let wallet = setup_wallet(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
 
-        let mut asset_ids: Vec<(AssetId, AssetId)> = Vec::new();
-        asset_ids.push((
+        let asset_ids: Vec<(AssetId, AssetId)> = vec![(
             AssetId::from_str(&self.base).expect("Invalid asset"),
             AssetId::from_str(&self.quote).expect("Invalid asset"),
-        ));
+        )];
 
         // Connect to the deployed contract via the rpc
         let contract = SparkRegistryContract::new(contract_id, wallet).await;
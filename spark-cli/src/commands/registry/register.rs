@@ -1,4 +1,4 @@
-use crate::utils::{setup, validate_contract_id};
+use crate::utils::{print_info, setup_wallet, validate_contract_id, validate_deployed_contract_id};
 use clap::Args;
 use fuels::accounts::ViewOnlyAccount;
 use spark_registry_sdk::SparkRegistryContract;
@@ -22,13 +22,14 @@ pub(crate) struct RegisterCommand {
 
 impl RegisterCommand {
     pub(crate) async fn run(&self) -> anyhow::Result<()> {
-        let wallet = setup(&self.rpc).await?;
-        let contract_id = validate_contract_id(&self.contract_id)?;
+        let wallet = setup_wallet(&self.rpc).await?;
+        let contract_id =
+            validate_deployed_contract_id(&self.contract_id, wallet.try_provider()?).await?;
         let market = validate_contract_id(&self.market)?;
 
         // Initial balance prior to contract call - used to calculate contract interaction cost
         let balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
         // Connect to the deployed contract via the rpc
@@ -38,10 +39,10 @@ impl RegisterCommand {
 
         // Balance post-call
         let new_balance = wallet
-            .get_asset_balance(&wallet.provider().unwrap().base_asset_id())
+            .get_asset_balance(wallet.provider().unwrap().base_asset_id())
             .await?;
 
-        println!("\nContract call cost: {}", balance - new_balance);
+        print_info(format!("\nContract call cost: {}", balance - new_balance));
 
         Ok(())
     }
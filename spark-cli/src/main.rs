@@ -1,4 +1,5 @@
 mod commands;
+mod rpc_headers;
 mod utils;
 
 use clap::Parser;
@@ -9,17 +10,30 @@ use commands::{
     registry::cli::RegistryCommands,
 };
 use dotenv::dotenv;
+use rpc_headers::init_rpc_headers;
+use utils::{
+    error_message, exit_code, init_color, init_fee_confirmation, init_json_style, init_verbosity,
+    print_error, print_info,
+};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let version: String = env!("CARGO_PKG_VERSION").into();
-    println!("Spark CLI v{}", version);
-
     dotenv().ok();
 
     let cli = Cli::parse();
+    init_color(cli.no_color);
+    init_verbosity(cli.quiet);
+    init_json_style(cli.json_pretty);
+    let verbose = cli.verbose;
 
-    match cli.command {
+    let version: String = env!("CARGO_PKG_VERSION").into();
+    print_info(format!("Spark CLI v{}", version));
+    if let Err(error) = init_rpc_headers(cli.rpc_header) {
+        print_error(error_message(&error, verbose));
+        std::process::exit(exit_code(&error) as i32);
+    }
+
+    let result = match cli.command {
         Command::Registry(args) => match args.commands {
             RegistryCommands::Config(args) => args.run().await,
             RegistryCommands::Deploy(args) => args.run().await,
@@ -27,30 +41,69 @@ async fn main() -> anyhow::Result<()> {
             RegistryCommands::Register(args) => args.run().await,
             RegistryCommands::Unregister(args) => args.run().await,
         },
-        Command::Core(args) => match args.commands {
-            CoreCommands::Cancel(args) => args.run().await,
-            CoreCommands::Deploy(args) => args.run().await,
-            CoreCommands::Deposit(args) => args.run().await,
-            CoreCommands::FulfillMany(args) => args.run().await,
-            CoreCommands::Open(args) => args.run().await,
-            CoreCommands::MatchMany(args) => args.run().await,
-            CoreCommands::MatchPair(args) => args.run().await,
-            CoreCommands::SetEpoch(args) => args.run().await,
-            CoreCommands::SetProtocolFee(args) => args.run().await,
-            CoreCommands::SetMatcherFee(args) => args.run().await,
-            CoreCommands::Withdraw(args) => args.run().await,
-        },
+        Command::Core(args) => {
+            init_fee_confirmation(args.confirm_fee, args.yes);
+            match args.commands {
+                CoreCommands::AddFeeExempt(args) => args.run().await,
+                CoreCommands::Cancel(args) => args.run().await,
+                CoreCommands::CancelExpired(args) => args.run().await,
+                CoreCommands::CancelOldest(args) => args.run().await,
+                CoreCommands::ClaimRewards(args) => args.run().await,
+                CoreCommands::Configure(args) => args.run().await,
+                CoreCommands::Deploy(args) => args.run().await,
+                CoreCommands::Deposit(args) => args.run().await,
+                CoreCommands::FulfillMany(args) => args.run().await,
+                CoreCommands::Open(args) => args.run().await,
+                CoreCommands::OpenBatch(args) => args.run().await,
+                CoreCommands::MatchMany(args) => args.run().await,
+                CoreCommands::MatchPair(args) => args.run().await,
+                CoreCommands::Matcher(args) => args.run().await,
+                CoreCommands::RemoveFeeExempt(args) => args.run().await,
+                CoreCommands::Replay(args) => args.run().await,
+                CoreCommands::SetDustThreshold(args) => args.run().await,
+                CoreCommands::SetEpoch(args) => args.run().await,
+                CoreCommands::SetProtocolFee(args) => args.run().await,
+                CoreCommands::SetMatcherFee(args) => args.run().await,
+                CoreCommands::SetMaxPriceDeviation(args) => args.run().await,
+                CoreCommands::SetTickSize(args) => args.run().await,
+                CoreCommands::Withdraw(args) => args.run().await,
+            }
+        }
         Command::Info(args) => match args.commands {
             InfoCommands::Account(args) => args.run().await,
+            InfoCommands::Activity(args) => args.run().await,
+            InfoCommands::Aging(args) => args.run().await,
+            InfoCommands::AuditMatch(args) => args.run().await,
+            InfoCommands::BalanceHistory(args) => args.run().await,
+            InfoCommands::Balances(args) => args.run().await,
+            InfoCommands::CompareConfig(args) => args.run().await,
             InfoCommands::Config(args) => args.run().await,
+            InfoCommands::ContractBalances(args) => args.run().await,
+            InfoCommands::DepthChart(args) => args.run().await,
+            InfoCommands::DetectFrontrun(args) => args.run().await,
             InfoCommands::Epoch(args) => args.run().await,
             InfoCommands::ProtocolFee(args) => args.run().await,
             InfoCommands::ProtocolFeeUser(args) => args.run().await,
             InfoCommands::ProtocolFeeUserAmount(args) => args.run().await,
+            InfoCommands::FeeHistory(args) => args.run().await,
             InfoCommands::MatcherFee(args) => args.run().await,
+            InfoCommands::Microstructure(args) => args.run().await,
+            InfoCommands::NetworkStatus(args) => args.run().await,
             InfoCommands::OrderId(args) => args.run().await,
             InfoCommands::Order(args) => args.run().await,
+            InfoCommands::RequiredDeposit(args) => args.run().await,
+            InfoCommands::TradeHistory(args) => args.run().await,
             InfoCommands::UserOrders(args) => args.run().await,
+            InfoCommands::VerifyAccount(args) => args.run().await,
+            InfoCommands::VerifyOrderId(args) => args.run().await,
+            InfoCommands::Whoami(args) => args.run().await,
         },
+    };
+
+    if let Err(error) = result {
+        print_error(error_message(&error, verbose));
+        std::process::exit(exit_code(&error) as i32);
     }
+
+    Ok(())
 }
@@ -0,0 +1,315 @@
+use base64::{prelude::BASE64_STANDARD, Engine};
+use fuels::prelude::Provider;
+use hyper::{
+    client::HttpConnector,
+    header::{HeaderName, HeaderValue, AUTHORIZATION, HOST},
+    service::{make_service_fn, service_fn},
+    Body, Client, Request, Response, Server, StatusCode, Uri,
+};
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use rand::{distributions::Alphanumeric, Rng};
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    net::SocketAddr,
+    str::FromStr,
+    sync::{Mutex, OnceLock},
+};
+
+/// Environment variable with a bearer token for the RPC, so secrets stay off the command line
+const RPC_TOKEN: &str = "RPC_TOKEN";
+
+/// User name of the proxy credentials, the password is the secret of the process
+const PROXY_USER: &str = "spark-cli";
+
+static RPC_HEADERS: OnceLock<Vec<RpcHeader>> = OnceLock::new();
+
+/// The proxy URL of each RPC, so a proxy is started once per process and RPC
+static PROXIES: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+
+pub(crate) type RpcHeader = (HeaderName, HeaderValue);
+
+/// Parses a `--rpc-header` value written as `Key: Value`
+pub(crate) fn parse_rpc_header(header: &str) -> anyhow::Result<RpcHeader> {
+    let (name, value) = header
+        .split_once(':')
+        .ok_or_else(|| anyhow::anyhow!("Expected \"Key: Value\", got \"{}\"", header))?;
+    Ok((
+        HeaderName::from_str(name.trim())?,
+        HeaderValue::from_str(value.trim())?,
+    ))
+}
+
+/// Headers from `--rpc-header` are sent with every RPC request, plus `RPC_TOKEN` as a bearer token
+pub(crate) fn init_rpc_headers(headers: Vec<RpcHeader>) -> anyhow::Result<()> {
+    let headers = with_token(headers, std::env::var(RPC_TOKEN).ok())?;
+    let _ = RPC_HEADERS.set(headers);
+    Ok(())
+}
+
+/// Adds `token` as a bearer token, unless it is missing or blank as in an `RPC_TOKEN=` line
+fn with_token(
+    mut headers: Vec<RpcHeader>,
+    token: Option<String>,
+) -> anyhow::Result<Vec<RpcHeader>> {
+    if let Some(token) = token.filter(|token| !token.trim().is_empty()) {
+        headers.push((
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", token.trim()))?,
+        ));
+    }
+    Ok(headers)
+}
+
+/// Connects to `rpc`, directly unless RPC headers are configured. The provider of fuels 0.66
+/// builds its own Fuel client from the URL and takes no headers or HTTP client, so only then
+/// are requests sent through a local proxy that adds them
+pub(crate) async fn connect(rpc: &str) -> anyhow::Result<Provider> {
+    match RPC_HEADERS.get() {
+        Some(headers) if !headers.is_empty() => {
+            Ok(Provider::connect(proxy_url(rpc, headers)?).await?)
+        }
+        _ => Ok(Provider::connect(rpc).await?),
+    }
+}
+
+/// The URL of the proxy to `rpc`, starting it on first use. The URL carries the credentials
+/// the proxy requires, which the Fuel client sends as basic auth with every request
+fn proxy_url(rpc: &str, headers: &[RpcHeader]) -> anyhow::Result<String> {
+    let mut proxies = PROXIES.get_or_init(Default::default).lock().unwrap();
+    if let Some(url) = proxies.get(rpc) {
+        return Ok(url.clone());
+    }
+
+    let secret: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect();
+    let addr = spawn_proxy(rpc, headers.to_vec(), &secret)?;
+    let url = format!("http://{PROXY_USER}:{secret}@{addr}");
+    proxies.insert(rpc.to_string(), url.clone());
+    Ok(url)
+}
+
+/// The Fuel client offers no way to set request headers, so requests are relayed through a
+/// proxy on localhost that adds them and forwards to `rpc`, streaming subscriptions included.
+/// Other local processes must not borrow the RPC credentials, so only requests authenticated
+/// with `secret` are relayed
+fn spawn_proxy(rpc: &str, headers: Vec<RpcHeader>, secret: &str) -> anyhow::Result<SocketAddr> {
+    // Same scheme default as the Fuel client
+    let upstream: Uri = match rpc.starts_with("http") {
+        true => rpc.parse()?,
+        false => format!("http://{rpc}").parse()?,
+    };
+    let client = Client::builder().build(
+        HttpsConnectorBuilder::new()
+            .with_webpki_roots()
+            .https_or_http()
+            .enable_http1()
+            .build(),
+    );
+
+    let credentials = HeaderValue::from_str(&format!(
+        "Basic {}",
+        BASE64_STANDARD.encode(format!("{PROXY_USER}:{secret}"))
+    ))?;
+
+    let make_service = make_service_fn(move |_| {
+        let (client, upstream, headers, credentials) = (
+            client.clone(),
+            upstream.clone(),
+            headers.clone(),
+            credentials.clone(),
+        );
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                forward(
+                    client.clone(),
+                    upstream.clone(),
+                    headers.clone(),
+                    credentials.clone(),
+                    request,
+                )
+            }))
+        }
+    });
+    let server = Server::try_bind(&SocketAddr::from(([127, 0, 0, 1], 0)))?.serve(make_service);
+    let addr = server.local_addr();
+    tokio::spawn(server);
+
+    Ok(addr)
+}
+
+async fn forward(
+    client: Client<HttpsConnector<HttpConnector>>,
+    upstream: Uri,
+    headers: Vec<RpcHeader>,
+    credentials: HeaderValue,
+    mut request: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+    if request.headers().get(AUTHORIZATION) != Some(&credentials) {
+        let mut response = Response::new(Body::empty());
+        *response.status_mut() = StatusCode::UNAUTHORIZED;
+        return Ok(response);
+    }
+    // The proxy credentials are not meant for the RPC
+    request.headers_mut().remove(AUTHORIZATION);
+
+    let mut parts = upstream.into_parts();
+    parts.path_and_query = request.uri().path_and_query().cloned();
+    let uri = match Uri::from_parts(parts) {
+        Ok(uri) => uri,
+        Err(error) => return Ok(bad_gateway(error)),
+    };
+
+    // The client sets the upstream host from the uri
+    *request.uri_mut() = uri;
+    request.headers_mut().remove(HOST);
+    for (name, value) in headers {
+        request.headers_mut().insert(name, value);
+    }
+
+    Ok(client.request(request).await.unwrap_or_else(bad_gateway))
+}
+
+fn bad_gateway(error: impl std::fmt::Display) -> Response<Body> {
+    let mut response = Response::new(Body::from(error.to_string()));
+    *response.status_mut() = StatusCode::BAD_GATEWAY;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuels::{
+        accounts::{Account, ViewOnlyAccount},
+        prelude::{AssetId, TxPolicies},
+        test_helpers::launch_provider_and_get_wallet,
+        types::bech32::Bech32Address,
+    };
+    use hyper::{body::to_bytes, HeaderMap};
+    use tokio::sync::mpsc;
+
+    /// A mock RPC that reports the uri and headers of every request it receives
+    fn spawn_mock_rpc() -> anyhow::Result<(SocketAddr, mpsc::UnboundedReceiver<(Uri, HeaderMap)>)> {
+        let (seen, received) = mpsc::unbounded_channel();
+        let make_service = make_service_fn(move |_| {
+            let seen = seen.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                    let _ = seen.send((request.uri().clone(), request.headers().clone()));
+                    async { Ok::<_, Infallible>(Response::new(Body::from("ok"))) }
+                }))
+            }
+        });
+        let mock = Server::try_bind(&SocketAddr::from(([127, 0, 0, 1], 0)))?.serve(make_service);
+        let mock_addr = mock.local_addr();
+        tokio::spawn(mock);
+        Ok((mock_addr, received))
+    }
+
+    #[tokio::test]
+    async fn adds_headers_to_forwarded_requests() -> anyhow::Result<()> {
+        let (mock_addr, mut received) = spawn_mock_rpc()?;
+
+        let headers = vec![
+            parse_rpc_header("Authorization: Bearer secret")?,
+            parse_rpc_header("x-api-key:  abc ")?,
+        ];
+        let proxy = spawn_proxy(&mock_addr.to_string(), headers, "password")?;
+
+        let response = Client::new()
+            .request(
+                Request::post(format!("http://{proxy}/v1/graphql?query=1"))
+                    .header(
+                        AUTHORIZATION,
+                        format!(
+                            "Basic {}",
+                            BASE64_STANDARD.encode(format!("{PROXY_USER}:password"))
+                        ),
+                    )
+                    .body(Body::from("{}"))?,
+            )
+            .await?;
+        assert_eq!(to_bytes(response.into_body()).await?, "ok");
+
+        let (uri, headers) = received.recv().await.unwrap();
+        assert_eq!(uri.path_and_query().unwrap(), "/v1/graphql?query=1");
+        assert_eq!(headers[AUTHORIZATION], "Bearer secret");
+        assert_eq!(headers["x-api-key"], "abc");
+        assert_eq!(headers[HOST], mock_addr.to_string());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_requests_without_the_secret() -> anyhow::Result<()> {
+        let (mock_addr, mut received) = spawn_mock_rpc()?;
+        let proxy = spawn_proxy(
+            &mock_addr.to_string(),
+            vec![parse_rpc_header("x-api-key: abc")?],
+            "password",
+        )?;
+
+        for authorization in [None, Some("Basic c3BhcmstY2xpOndyb25n")] {
+            let mut request = Request::post(format!("http://{proxy}/v1/graphql"));
+            if let Some(authorization) = authorization {
+                request = request.header(AUTHORIZATION, authorization);
+            }
+            let response = Client::new()
+                .request(request.body(Body::from("{}"))?)
+                .await?;
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+        assert!(received.try_recv().is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn transactions_commit_through_the_proxy() -> anyhow::Result<()> {
+        let mut wallet = launch_provider_and_get_wallet().await?;
+        let rpc = wallet.try_provider()?.url().to_string();
+
+        let headers = vec![parse_rpc_header("x-api-key: abc")?];
+        let proxy = proxy_url(&rpc, &headers)?;
+        // Every connection to the RPC shares the proxy
+        assert_eq!(proxy_url(&rpc, &headers)?, proxy);
+        wallet.set_provider(Provider::connect(&proxy).await?);
+
+        // Awaiting the commit goes through the streaming subscription endpoint
+        let recipient = Bech32Address::default();
+        wallet
+            .transfer(&recipient, 1, AssetId::default(), TxPolicies::default())
+            .await?;
+        assert_eq!(
+            wallet
+                .try_provider()?
+                .get_asset_balance(&recipient, AssetId::default())
+                .await?,
+            1
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn blank_token_adds_no_header() -> anyhow::Result<()> {
+        assert!(with_token(Vec::new(), None)?.is_empty());
+        assert!(with_token(Vec::new(), Some(" ".to_string()))?.is_empty());
+
+        let headers = with_token(Vec::new(), Some("secret".to_string()))?;
+        assert_eq!(
+            headers,
+            vec![(AUTHORIZATION, HeaderValue::from_static("Bearer secret"))]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_header_without_separator() {
+        assert!(parse_rpc_header("Authorization Bearer secret").is_err());
+    }
+}
@@ -1,9 +1,215 @@
-use clap::ValueEnum;
-use fuels::prelude::{ContractId, Provider, WalletUnlocked};
-use std::str::FromStr;
+use crate::rpc_headers;
+use anstyle::{AnsiColor, Style};
+use async_trait::async_trait;
+use base64::{prelude::BASE64_STANDARD, Engine};
+use clap::{Args, ValueEnum};
+use fuel_core_client::client::FuelClient;
+use fuels::{
+    accounts::{Account, ViewOnlyAccount},
+    core::traits::Signer,
+    prelude::{ContractId, Provider, WalletUnlocked},
+    types::{
+        bech32::Bech32Address, coin_type_id::CoinTypeId, input::Input,
+        transaction_builders::TransactionBuilder, Address, AssetId, Identity,
+    },
+};
+use spark_market_sdk::{
+    AssetType as ContractAssetType, FeeConfirmation, FeeTooHigh, MarketCall, MarketConfig,
+    OrderType as ContractOrderType, ZeroAmount,
+};
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+    fs::File,
+    future::Future,
+    io::{BufRead, BufWriter, IsTerminal, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
-pub(crate) async fn setup(rpc: &str) -> anyhow::Result<WalletUnlocked> {
-    let provider = Provider::connect(rpc).await?;
+const SUCCESS: Style = Style::new().fg_color(Some(anstyle::Color::Ansi(AnsiColor::Green)));
+const WARNING: Style = Style::new().fg_color(Some(anstyle::Color::Ansi(AnsiColor::Yellow)));
+const ERROR: Style = Style::new()
+    .fg_color(Some(anstyle::Color::Ansi(AnsiColor::Red)))
+    .bold();
+
+static COLOR: AtomicBool = AtomicBool::new(false);
+static QUIET: AtomicBool = AtomicBool::new(false);
+static CONFIRM_FEE: AtomicBool = AtomicBool::new(false);
+static JSON_PRETTY: AtomicBool = AtomicBool::new(false);
+
+/// Colors are only used when stdout is a terminal and neither `--no-color` nor `NO_COLOR` is set
+pub(crate) fn init_color(no_color: bool) {
+    let enabled =
+        !no_color && std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal();
+    COLOR.store(enabled, Ordering::Relaxed);
+}
+
+fn paint(style: Style, line: impl Display, color: bool) -> String {
+    match color {
+        true => format!("{style}{line}{style:#}"),
+        false => line.to_string(),
+    }
+}
+
+/// In quiet mode only the result of a command is printed so scripts can capture it
+pub(crate) fn init_verbosity(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// JSON output is compact for piping unless `--json-pretty` asks for indentation
+pub(crate) fn init_json_style(pretty: bool) {
+    JSON_PRETTY.store(pretty, Ordering::Relaxed);
+}
+
+/// Renders a JSON result in the style chosen with `--json-pretty`
+pub(crate) fn format_json(value: &serde_json::Value) -> String {
+    json_string(value, JSON_PRETTY.load(Ordering::Relaxed))
+}
+
+fn json_string(value: &serde_json::Value, pretty: bool) -> String {
+    match pretty {
+        true => format!("{:#}", value),
+        false => value.to_string(),
+    }
+}
+
+pub(crate) fn print_success(line: impl Display) {
+    if !QUIET.load(Ordering::Relaxed) {
+        println!("{}", paint(SUCCESS, line, COLOR.load(Ordering::Relaxed)));
+    }
+}
+
+/// Details of a call such as its cost, left out in quiet mode
+pub(crate) fn print_info(line: impl Display) {
+    if let Some(line) = info_line(line, QUIET.load(Ordering::Relaxed)) {
+        println!("{}", line);
+    }
+}
+
+/// The value a command is run for, printed as `line` or bare in quiet mode
+pub(crate) fn print_result(line: impl Display, value: impl Display) {
+    println!(
+        "{}",
+        result_line(line, value, QUIET.load(Ordering::Relaxed))
+    );
+}
+
+fn info_line(line: impl Display, quiet: bool) -> Option<String> {
+    (!quiet).then(|| line.to_string())
+}
+
+fn result_line(line: impl Display, value: impl Display, quiet: bool) -> String {
+    match quiet {
+        true => value.to_string(),
+        false => line.to_string(),
+    }
+}
+
+pub(crate) fn print_warning(line: impl Display) {
+    eprintln!("{}", paint(WARNING, line, COLOR.load(Ordering::Relaxed)));
+}
+
+pub(crate) fn print_error(line: impl Display) {
+    eprintln!("{}", paint(ERROR, line, COLOR.load(Ordering::Relaxed)));
+}
+
+/// With `confirm` core commands ask before sending each call, unless `yes` answers for the user
+pub(crate) fn init_fee_confirmation(confirm: bool, yes: bool) {
+    CONFIRM_FEE.store(confirm && !yes, Ordering::Relaxed);
+}
+
+/// Prompts on stderr and reads the answer from stdin, `None` when confirmation is off
+pub(crate) fn fee_confirmation() -> Option<FeeConfirmation> {
+    CONFIRM_FEE.load(Ordering::Relaxed).then(|| {
+        Arc::new(|estimated: u64| {
+            confirm_fee(
+                estimated,
+                &mut std::io::stdin().lock(),
+                &mut std::io::stderr(),
+            )
+        }) as FeeConfirmation
+    })
+}
+
+/// Asks whether to send a call with an `estimated` fee, anything but "y" or "yes" declines
+pub(crate) fn confirm_fee(
+    estimated: u64,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> bool {
+    let _ = write!(
+        output,
+        "Estimated fee: {}. Send the call? [y/N] ",
+        estimated
+    );
+    let _ = output.flush();
+
+    let mut answer = String::new();
+    if input.read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Process exit code of a failed command, by the kind of error it failed with so scripts can
+/// branch on it. Clap exits with 2 on invalid arguments
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ExitCode {
+    Failure = 1,
+    Unauthorized = 3,
+    NotFound = 4,
+    InsufficientBalance = 5,
+    FeeTooHigh = 6,
+    ZeroAmount = 7,
+}
+
+pub(crate) fn exit_code(error: &anyhow::Error) -> ExitCode {
+    if error.downcast_ref::<FeeTooHigh>().is_some() {
+        return ExitCode::FeeTooHigh;
+    }
+    if error.downcast_ref::<ZeroAmount>().is_some() {
+        return ExitCode::ZeroAmount;
+    }
+
+    // Contract reverts only reach the CLI as text, which names the logged error variant
+    let chain = format!("{:#}", error);
+    if chain.contains("Unauthorized") {
+        ExitCode::Unauthorized
+    } else if ["OrderNotFound", "NotDeployed"]
+        .iter()
+        .any(|reason| chain.contains(reason))
+    {
+        ExitCode::NotFound
+    } else if chain.contains("InsufficientBalance") {
+        ExitCode::InsufficientBalance
+    } else {
+        ExitCode::Failure
+    }
+}
+
+/// The outermost message of `error`, or with `verbose` its causes and a backtrace if captured
+pub(crate) fn error_message(error: &anyhow::Error, verbose: bool) -> String {
+    match verbose {
+        true => format!("Error: {:?}", error),
+        false => format!("Error: {}", error),
+    }
+}
+
+/// Connects to `rpc` with the default signer, a private key or mnemonic read from the environment
+pub(crate) async fn setup(rpc: &str) -> anyhow::Result<SignerWallet> {
+    let wallet = setup_wallet(rpc).await?;
+    let provider = wallet.try_provider()?.clone();
+    Ok(SignerWallet::new(wallet, provider))
+}
+
+/// Connects to `rpc` with the environment key as a plain wallet, for SDKs that require one
+pub(crate) async fn setup_wallet(rpc: &str) -> anyhow::Result<WalletUnlocked> {
+    let provider = rpc_headers::connect(rpc).await?;
 
     // First, try to get the private key from environment
     if let Ok(secret) = std::env::var("WALLET_SECRET") {
@@ -23,6 +229,60 @@ pub(crate) async fn setup(rpc: &str) -> anyhow::Result<WalletUnlocked> {
     ))
 }
 
+/// An account whose transactions are signed by `S`. Hardware wallets or remote key stores plug in
+/// by implementing `Signer`; the default is the environment key loaded by `setup`
+#[derive(Clone, Debug)]
+pub(crate) struct SignerWallet<S = WalletUnlocked> {
+    signer: S,
+    provider: Provider,
+}
+
+impl<S> SignerWallet<S> {
+    pub(crate) fn new(signer: S, provider: Provider) -> Self {
+        Self { signer, provider }
+    }
+
+    pub(crate) fn provider(&self) -> Option<&Provider> {
+        Some(&self.provider)
+    }
+}
+
+#[async_trait]
+impl<S: Signer + Clone + Debug + Send + Sync> ViewOnlyAccount for SignerWallet<S> {
+    fn address(&self) -> &Bech32Address {
+        self.signer.address()
+    }
+
+    fn try_provider(&self) -> fuels::types::errors::Result<&Provider> {
+        Ok(&self.provider)
+    }
+}
+
+#[async_trait]
+impl<S: Signer + Clone + Debug + Send + Sync> Account for SignerWallet<S> {
+    async fn get_asset_inputs_for_amount(
+        &self,
+        asset_id: AssetId,
+        amount: u64,
+        excluded_coins: Option<Vec<CoinTypeId>>,
+    ) -> fuels::types::errors::Result<Vec<Input>> {
+        Ok(self
+            .get_spendable_resources(asset_id, amount, excluded_coins)
+            .await?
+            .into_iter()
+            .map(Input::resource_signed)
+            .collect())
+    }
+
+    fn add_witnesses<Tb: TransactionBuilder>(
+        &self,
+        tb: &mut Tb,
+    ) -> fuels::types::errors::Result<()> {
+        tb.add_signer(self.signer.clone())?;
+        Ok(())
+    }
+}
+
 pub(crate) fn validate_contract_id(contract_id: &str) -> anyhow::Result<ContractId> {
     if contract_id.len() as u64 != 66 {
         anyhow::bail!("Invalid contract id length");
@@ -31,7 +291,359 @@ pub(crate) fn validate_contract_id(contract_id: &str) -> anyhow::Result<Contract
     Ok(ContractId::from_str(contract_id).expect("Invalid contract id"))
 }
 
-#[derive(Clone, ValueEnum)]
+/// Like `validate_contract_id`, and also checks that `provider` holds bytecode at the id so a
+/// mistyped market is reported as such rather than as a failure to decode the call
+pub(crate) async fn validate_deployed_contract_id(
+    contract_id: &str,
+    provider: &Provider,
+) -> anyhow::Result<ContractId> {
+    let id = validate_contract_id(contract_id)?;
+
+    // The provider has no contract lookup, so its node is queried directly
+    if FuelClient::new(provider.url())?
+        .contract(&id)
+        .await?
+        .is_none()
+    {
+        anyhow::bail!("NotDeployed: no contract is deployed at {}", contract_id);
+    }
+
+    Ok(id)
+}
+
+pub(crate) fn save_call(path: &Option<PathBuf>, call: &MarketCall) -> anyhow::Result<()> {
+    if let Some(path) = path {
+        std::fs::write(path, call.to_json())?;
+        print_info(format!("Call saved to: {}", path.display()));
+    }
+    Ok(())
+}
+
+/// Runs `work` until it finishes or `interrupt` resolves first, e.g. on Ctrl-C, in which case
+/// `work` is dropped at its current await point and `true` is returned
+pub(crate) async fn until_interrupted(
+    work: impl Future<Output = anyhow::Result<()>>,
+    interrupt: impl Future<Output = ()>,
+) -> anyhow::Result<bool> {
+    tokio::select! {
+        result = work => result.map(|_| false),
+        _ = interrupt => Ok(true),
+    }
+}
+
+/// Where a command writes its result: stdout, or the `--output` file when one is given
+pub(crate) fn output_writer(path: &Option<PathBuf>) -> anyhow::Result<Box<dyn Write>> {
+    match path {
+        Some(path) => {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            Ok(Box::new(BufWriter::new(File::create(path)?)))
+        }
+        None => Ok(Box::new(std::io::stdout())),
+    }
+}
+
+/// The cost of a market call, kept apart from any amount the call itself moves
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum CallCost {
+    /// Fee asset spent, from the balance difference around the call
+    Fee(u64),
+    /// Gas used by the call, reported when the call also moves the fee asset so a balance
+    /// difference would mix the transferred amount into the cost
+    Gas(u64),
+}
+
+impl CallCost {
+    pub(crate) fn new(
+        fee_asset: &AssetId,
+        moved_asset: &AssetId,
+        fee_balance_before: u64,
+        fee_balance_after: u64,
+        gas_used: u64,
+    ) -> Self {
+        if fee_asset == moved_asset {
+            Self::Gas(gas_used)
+        } else {
+            Self::Fee(fee_balance_before - fee_balance_after)
+        }
+    }
+}
+
+impl Display for CallCost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fee(fee) => write!(f, "Contract call cost: {}", fee),
+            Self::Gas(gas) => write!(f, "Contract call gas used: {}", gas),
+        }
+    }
+}
+
+/// Where the node the CLI talks to stands, to tell a node at the chain tip from a stale one
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct NetworkStatus {
+    pub(crate) block_height: u32,
+    pub(crate) chain_id: u64,
+    /// The latest block's timestamp as UTC text and unix seconds, if the node reports one
+    pub(crate) latest_block_time: Option<(String, i64)>,
+}
+
+impl NetworkStatus {
+    /// Seconds the latest block trails `now`, in unix seconds
+    pub(crate) fn lag(&self, now: i64) -> Option<i64> {
+        self.latest_block_time
+            .as_ref()
+            .map(|(_, timestamp)| now - timestamp)
+    }
+}
+
+pub(crate) async fn network_status(provider: &Provider) -> anyhow::Result<NetworkStatus> {
+    let chain_info = provider.chain_info().await?;
+    let header = chain_info.latest_block.header;
+    Ok(NetworkStatus {
+        block_height: header.height,
+        chain_id: u64::from(provider.chain_id()),
+        latest_block_time: header.time.map(|time| (time.to_string(), time.timestamp())),
+    })
+}
+
+/// The current wall-clock time in unix seconds
+pub(crate) fn unix_now() -> anyhow::Result<i64> {
+    Ok(std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs() as i64)
+}
+
+pub(crate) fn encode_b256(bytes: &[u8; 32], encoding: &Encoding) -> String {
+    match encoding {
+        Encoding::Hex => hex::encode(bytes),
+        Encoding::Base64 => BASE64_STANDARD.encode(bytes),
+    }
+}
+
+/// Assets the CLI names without a symbol file, on Fuel testnet
+const KNOWN_SYMBOLS: [(&str, &str); 3] = [
+    (
+        "0xf8f8b6283d7fa5b672b530cbb84fcccb4ff8dc40f8176ef4544ddb1f1952ad07",
+        "ETH",
+    ),
+    (
+        "0x38e4ca985b22625fff93205e997bfc5cc8453a953da638ad297ca60a9f2600bc",
+        "BTC",
+    ),
+    (
+        "0x336b7c06352a4b736ff6f688ba6885788b3df16e136e95310ade51aa32dc6f05",
+        "USDC",
+    ),
+];
+
+/// Human symbols printed in place of asset ids
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SymbolMap {
+    symbols: HashMap<AssetId, String>,
+}
+
+impl SymbolMap {
+    /// The known assets, extended or overridden by a `--symbols` JSON file of
+    /// `{"<asset id>": "<symbol>"}`
+    pub(crate) fn load(path: Option<&Path>) -> anyhow::Result<Self> {
+        let mut map = Self::default();
+        for (asset, symbol) in KNOWN_SYMBOLS {
+            map.symbols
+                .insert(AssetId::from_str(asset).unwrap(), symbol.to_string());
+        }
+        if let Some(path) = path {
+            map.extend_from_json(&std::fs::read_to_string(path)?)?;
+        }
+        Ok(map)
+    }
+
+    pub(crate) fn extend_from_json(&mut self, json: &str) -> anyhow::Result<()> {
+        let entries: HashMap<String, String> = serde_json::from_str(json)?;
+        for (asset, symbol) in entries {
+            let asset = AssetId::from_str(&asset)
+                .map_err(|_| anyhow::anyhow!("Invalid asset id in symbol map: {}", asset))?;
+            self.symbols.insert(asset, symbol);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn symbol(&self, asset: &AssetId) -> Option<&str> {
+        self.symbols.get(asset).map(String::as_str)
+    }
+
+    /// The symbol of `asset`, or its id shortened to `0x1234abcd..5678` when it has none
+    pub(crate) fn label(&self, asset: &AssetId) -> String {
+        match self.symbol(asset) {
+            Some(symbol) => symbol.to_string(),
+            None => {
+                let id = hex::encode(**asset);
+                format!("0x{}..{}", &id[..8], &id[id.len() - 4..])
+            }
+        }
+    }
+}
+
+pub(crate) fn parse_amount(value: &str, decimals: u32) -> anyhow::Result<u64> {
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+
+    if whole.is_empty() && fraction.is_empty() {
+        anyhow::bail!("Invalid amount: {}", value);
+    }
+    if fraction.len() as u32 > decimals {
+        anyhow::bail!("Amount {} has more than {} decimals", value, decimals);
+    }
+
+    let scale = 10_u64
+        .checked_pow(decimals)
+        .ok_or_else(|| anyhow::anyhow!("Unsupported decimals: {}", decimals))?;
+    let whole = match whole {
+        "" => 0,
+        whole => whole.parse::<u64>()?,
+    };
+    let fraction = match fraction {
+        "" => 0,
+        fraction => fraction.parse::<u64>()? * 10_u64.pow(decimals - fraction.len() as u32),
+    };
+
+    whole
+        .checked_mul(scale)
+        .and_then(|whole| whole.checked_add(fraction))
+        .ok_or_else(|| anyhow::anyhow!("Amount {} overflows", value))
+}
+
+/// Renders an amount of an asset with `decimals` in whole units, without trailing zeros
+pub(crate) fn format_amount(amount: u64, decimals: u32) -> String {
+    let scale = 10_u128.pow(decimals);
+    let (whole, fraction) = (amount as u128 / scale, amount as u128 % scale);
+    match fraction {
+        0 => whole.to_string(),
+        _ => {
+            let fraction = format!("{:0width$}", fraction, width = decimals as usize);
+            format!("{}.{}", whole, fraction.trim_end_matches('0'))
+        }
+    }
+}
+
+/// Where printed amounts take their base and quote decimals from
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum DecimalsSource {
+    /// The decimals the market was deployed with
+    #[default]
+    Chain,
+    /// --base-decimals and --quote-decimals, the market's for any not given
+    Override,
+}
+
+/// The decimals amounts are printed with, shared by every command that formats amounts
+#[derive(Args, Clone, Debug, Default)]
+pub(crate) struct DecimalsArgs {
+    /// Where printed amounts take their decimals from
+    #[clap(long, value_enum, default_value_t)]
+    pub(crate) decimals_source: DecimalsSource,
+
+    /// The base decimals printed amounts use with --decimals-source override
+    #[clap(long)]
+    pub(crate) base_decimals: Option<u32>,
+
+    /// The quote decimals printed amounts use with --decimals-source override
+    #[clap(long)]
+    pub(crate) quote_decimals: Option<u32>,
+}
+
+impl DecimalsArgs {
+    /// The base and quote decimals to format amounts of `config`'s market with. Amounts sent
+    /// to the market are always scaled by its own decimals
+    pub(crate) fn resolve(&self, config: &MarketConfig) -> (u32, u32) {
+        match self.decimals_source {
+            DecimalsSource::Chain => (config.base_decimals, config.quote_decimals),
+            DecimalsSource::Override => (
+                self.base_decimals.unwrap_or(config.base_decimals),
+                self.quote_decimals.unwrap_or(config.quote_decimals),
+            ),
+        }
+    }
+}
+
+/// Renders a price in market units with `precision` fractional digits, rounding half to even
+pub(crate) fn format_price(price: u64, price_decimals: u32, precision: u32) -> String {
+    let (mut units, decimals) = if precision >= price_decimals {
+        (
+            price as u128 * 10_u128.pow(precision - price_decimals),
+            precision,
+        )
+    } else {
+        let scale = 10_u128.pow(price_decimals - precision);
+        let (quotient, remainder) = (price as u128 / scale, price as u128 % scale);
+        let rounds_up = remainder * 2 > scale || (remainder * 2 == scale && quotient % 2 == 1);
+        (quotient + rounds_up as u128, precision)
+    };
+
+    if decimals == 0 {
+        return units.to_string();
+    }
+    let scale = 10_u128.pow(decimals);
+    let whole = units / scale;
+    units %= scale;
+    format!("{}.{:0width$}", whole, units, width = decimals as usize)
+}
+
+/// How the cells of a table column are justified, numbers are right-justified
+#[derive(Clone, Copy)]
+pub(crate) enum Align {
+    Left,
+    Right,
+}
+
+/// Renders rows under the (header, alignment) columns with every column as wide as its widest cell
+pub(crate) fn render_table(columns: &[(&str, Align)], rows: &[Vec<String>]) -> String {
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(column, (header, _))| {
+            rows.iter()
+                .map(|row| row[column].len())
+                .fold(header.len(), usize::max)
+        })
+        .collect();
+
+    let separator = widths
+        .iter()
+        .map(|width| "-".repeat(width + 2))
+        .collect::<Vec<_>>()
+        .join("+");
+    let separator = format!("+{}+", separator);
+    let line = |cells: Vec<&str>| {
+        let cells: Vec<String> = cells
+            .iter()
+            .enumerate()
+            .map(|(column, cell)| match columns[column].1 {
+                Align::Left => format!(" {:<width$} ", cell, width = widths[column]),
+                Align::Right => format!(" {:>width$} ", cell, width = widths[column]),
+            })
+            .collect();
+        format!("|{}|", cells.join("|"))
+    };
+
+    let headers = columns.iter().map(|(header, _)| *header).collect();
+    let mut table = vec![separator.clone(), line(headers), separator.clone()];
+    for row in rows {
+        table.push(line(row.iter().map(String::as_str).collect()));
+    }
+    table.push(separator);
+    table.join("\n")
+}
+
+#[derive(Clone, Default, ValueEnum)]
+pub(crate) enum OutputFormat {
+    /// The command's usual output
+    #[default]
+    Plain,
+    /// Aligned columns for reading in a terminal
+    Table,
+}
+
+#[derive(Clone, Debug, PartialEq, ValueEnum)]
 pub(crate) enum AccountType {
     /// Externally Owned Account
     Address,
@@ -39,7 +651,34 @@ pub(crate) enum AccountType {
     Contract,
 }
 
-#[derive(Clone, ValueEnum)]
+impl AccountType {
+    /// Parses `account_id` as this kind of account
+    pub(crate) fn identity(&self, account_id: &str) -> anyhow::Result<Identity> {
+        Ok(match self {
+            AccountType::Address => {
+                Identity::Address(Address::from_str(account_id).map_err(|error| {
+                    anyhow::anyhow!("Invalid address {}: {}", account_id, error)
+                })?)
+            }
+            AccountType::Contract => {
+                Identity::ContractId(ContractId::from_str(account_id).map_err(|error| {
+                    anyhow::anyhow!("Invalid contract id {}: {}", account_id, error)
+                })?)
+            }
+        })
+    }
+}
+
+impl From<&Identity> for AccountType {
+    fn from(identity: &Identity) -> Self {
+        match identity {
+            Identity::Address(_) => AccountType::Address,
+            Identity::ContractId(_) => AccountType::Contract,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, ValueEnum)]
 pub(crate) enum AssetType {
     /// Base asset
     Base,
@@ -47,6 +686,34 @@ pub(crate) enum AssetType {
     Quote,
 }
 
+impl From<AssetType> for ContractAssetType {
+    fn from(asset_type: AssetType) -> Self {
+        match asset_type {
+            AssetType::Base => ContractAssetType::Base,
+            AssetType::Quote => ContractAssetType::Quote,
+        }
+    }
+}
+
+impl From<ContractAssetType> for AssetType {
+    fn from(asset_type: ContractAssetType) -> Self {
+        match asset_type {
+            ContractAssetType::Base => AssetType::Base,
+            ContractAssetType::Quote => AssetType::Quote,
+        }
+    }
+}
+
+#[derive(Clone, Default, ValueEnum)]
+pub(crate) enum Encoding {
+    /// Hex string without the 0x prefix
+    #[default]
+    Hex,
+    /// Standard base64
+    Base64,
+}
+
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Clone, ValueEnum)]
 pub(crate) enum LimitType {
     /// Immediatelly or Cancel
@@ -55,10 +722,388 @@ pub(crate) enum LimitType {
     FOK,
 }
 
-#[derive(Clone, ValueEnum)]
+#[derive(Clone, Debug, PartialEq, ValueEnum)]
 pub(crate) enum OrderType {
     /// Buy order
     Buy,
     /// Sell order
     Sell,
 }
+
+impl From<OrderType> for ContractOrderType {
+    fn from(order_type: OrderType) -> Self {
+        match order_type {
+            OrderType::Buy => ContractOrderType::Buy,
+            OrderType::Sell => ContractOrderType::Sell,
+        }
+    }
+}
+
+impl From<ContractOrderType> for OrderType {
+    fn from(order_type: ContractOrderType) -> Self {
+        match order_type {
+            ContractOrderType::Buy => OrderType::Buy,
+            ContractOrderType::Sell => OrderType::Sell,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuels::{
+        crypto::{Message, SecretKey, Signature},
+        prelude::{
+            launch_custom_provider_and_get_wallets, setup_single_asset_coins, setup_test_provider,
+            WalletsConfig,
+        },
+        test_helpers::launch_provider_and_get_wallet,
+    };
+    use spark_market_sdk::{FeeDeclined, SparkMarketContract};
+    use std::sync::atomic::AtomicUsize;
+
+    // Signs with a local key and counts the signatures it was asked for
+    #[derive(Clone, Debug)]
+    struct MockSigner {
+        wallet: WalletUnlocked,
+        signatures: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Signer for MockSigner {
+        async fn sign(&self, message: Message) -> fuels::types::errors::Result<Signature> {
+            self.signatures.fetch_add(1, Ordering::Relaxed);
+            self.wallet.sign(message).await
+        }
+
+        fn address(&self) -> &Bech32Address {
+            self.wallet.address()
+        }
+    }
+
+    #[tokio::test]
+    async fn market_calls_are_signed_by_the_plugged_in_signer() -> anyhow::Result<()> {
+        let secret = "0x862512a2363db2b3a375c0d4bbbd27172180d89f23f2e259bac850ab02619301";
+        let mut wallet = WalletUnlocked::new_from_private_key(SecretKey::from_str(secret)?, None);
+        let coins =
+            setup_single_asset_coins(wallet.address(), AssetId::default(), 1, 1_000_000_000);
+        let provider = setup_test_provider(coins, vec![], None, None).await?;
+        wallet.set_provider(provider.clone());
+
+        let market = SparkMarketContract::deploy(
+            AssetId::from([1; 32]),
+            9,
+            AssetId::from([2; 32]),
+            6,
+            wallet.clone(),
+            9,
+            0x000400,
+        )
+        .await?;
+
+        let signatures = Arc::new(AtomicUsize::new(0));
+        let signer = MockSigner {
+            wallet,
+            signatures: signatures.clone(),
+        };
+        let contract = SparkMarketContract::new(
+            ContractId::from(*market.id()),
+            SignerWallet::new(signer, provider),
+        )
+        .await;
+        assert_eq!(signatures.load(Ordering::Relaxed), 0);
+
+        contract.set_matcher_fee(5).await?;
+
+        assert!(signatures.load(Ordering::Relaxed) > 0);
+        assert_eq!(contract.matcher_fee().await?.value, 5);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_undeployed_contract_id() -> anyhow::Result<()> {
+        let wallet = launch_provider_and_get_wallet().await?;
+        let provider = wallet.try_provider()?;
+
+        let market = SparkMarketContract::deploy(
+            AssetId::from([1; 32]),
+            9,
+            AssetId::from([2; 32]),
+            6,
+            wallet.clone(),
+            9,
+            0x000400,
+        )
+        .await?;
+        let deployed = format!("0x{}", hex::encode(*market.id()));
+        assert_eq!(
+            validate_deployed_contract_id(&deployed, provider).await?,
+            ContractId::from(*market.id())
+        );
+
+        // Well-formed, but nothing lives at this id
+        let undeployed = format!("0x{}", "ab".repeat(32));
+        let error = validate_deployed_contract_id(&undeployed, provider)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().starts_with("NotDeployed"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn non_owner_call_exits_unauthorized() -> anyhow::Result<()> {
+        let config = WalletsConfig::new(Some(2), Some(1), Some(1_000_000_000));
+        let mut wallets = launch_custom_provider_and_get_wallets(config, None, None).await?;
+        let owner = wallets.pop().unwrap();
+        let user = wallets.pop().unwrap();
+
+        let market = SparkMarketContract::deploy(
+            AssetId::from([1; 32]),
+            9,
+            AssetId::from([2; 32]),
+            6,
+            owner,
+            9,
+            0x000400,
+        )
+        .await?;
+
+        let error = market
+            .with_account(&user)
+            .await?
+            .set_matcher_fee(5)
+            .await
+            .unwrap_err();
+        assert_eq!(exit_code(&error), ExitCode::Unauthorized);
+        assert_eq!(exit_code(&error) as i32, 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn declined_fee_is_not_sent() -> anyhow::Result<()> {
+        let config = WalletsConfig::new(Some(1), Some(1), Some(1_000_000_000));
+        let owner = launch_custom_provider_and_get_wallets(config, None, None)
+            .await?
+            .pop()
+            .unwrap();
+        let market = SparkMarketContract::deploy(
+            AssetId::from([1; 32]),
+            9,
+            AssetId::from([2; 32]),
+            6,
+            owner,
+            9,
+            0x000400,
+        )
+        .await?;
+        let answering = |answer: &'static str| -> Option<FeeConfirmation> {
+            Some(Arc::new(move |estimated: u64| {
+                confirm_fee(estimated, &mut answer.as_bytes(), &mut std::io::sink())
+            }) as FeeConfirmation)
+        };
+
+        let market = market.with_fee_confirmation(answering("no\n"));
+        let error = market.set_matcher_fee(5).await.unwrap_err();
+        assert!(error.downcast_ref::<FeeDeclined>().is_some());
+        assert_eq!(market.matcher_fee().await?.value, 0);
+
+        let market = market.with_fee_confirmation(answering("yes\n"));
+        market.set_matcher_fee(5).await?;
+        assert_eq!(market.matcher_fee().await?.value, 5);
+
+        let mut prompt = Vec::new();
+        assert!(confirm_fee(42, &mut "Y\n".as_bytes(), &mut prompt));
+        assert!(!confirm_fee(42, &mut "".as_bytes(), &mut std::io::sink()));
+        assert_eq!(
+            String::from_utf8(prompt)?,
+            "Estimated fee: 42. Send the call? [y/N] "
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn exit_codes_follow_the_error() {
+        let not_found = anyhow::anyhow!("NotDeployed: no contract at 0xab");
+        assert_eq!(exit_code(&not_found), ExitCode::NotFound);
+
+        let fee = anyhow::Error::new(FeeTooHigh {
+            estimated: 10,
+            limit: 5,
+        });
+        assert_eq!(exit_code(&fee), ExitCode::FeeTooHigh);
+        // Context on top keeps the typed error reachable
+        assert_eq!(exit_code(&fee.context("open order")), ExitCode::FeeTooHigh);
+
+        let zero = anyhow::Error::new(ZeroAmount {
+            operation: "withdraw",
+        });
+        assert_eq!(exit_code(&zero), ExitCode::ZeroAmount);
+
+        let other = anyhow::anyhow!("connection refused").context("setup");
+        assert_eq!(exit_code(&other), ExitCode::Failure);
+        assert_eq!(error_message(&other, false), "Error: setup");
+        assert!(error_message(&other, true).contains("connection refused"));
+    }
+
+    #[test]
+    fn assets_print_as_symbols() -> anyhow::Result<()> {
+        let (base, unmapped) = (AssetId::from([1; 32]), AssetId::from([0xab; 32]));
+        let mut symbols = SymbolMap::load(None)?;
+        symbols.extend_from_json(&format!(r#"{{"0x{}": "BTC"}}"#, base))?;
+
+        assert_eq!(symbols.label(&base), "BTC");
+        assert_eq!(symbols.label(&unmapped), "0xabababab..abab");
+
+        // Known assets need no file
+        let usdc = AssetId::from_str(KNOWN_SYMBOLS[2].0).unwrap();
+        assert_eq!(symbols.label(&usdc), "USDC");
+
+        assert!(symbols.extend_from_json(r#"{"0x12": "BAD"}"#).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn b256_encodings_round_trip() {
+        let bytes: [u8; 32] = core::array::from_fn(|i| (i * 7) as u8);
+
+        let hex_encoded = encode_b256(&bytes, &Encoding::Hex);
+        let base64_encoded = encode_b256(&bytes, &Encoding::Base64);
+
+        assert_ne!(hex_encoded, base64_encoded);
+        assert_eq!(hex::decode(hex_encoded).unwrap(), bytes);
+        assert_eq!(BASE64_STANDARD.decode(base64_encoded).unwrap(), bytes);
+    }
+
+    #[test]
+    fn cli_types_round_trip_through_sdk_types() -> anyhow::Result<()> {
+        for order_type in OrderType::value_variants() {
+            let contract_order_type = ContractOrderType::from(order_type.clone());
+            assert_eq!(&OrderType::from(contract_order_type), order_type);
+        }
+        for order_type in [ContractOrderType::Buy, ContractOrderType::Sell] {
+            let cli_order_type = OrderType::from(order_type.clone());
+            assert_eq!(ContractOrderType::from(cli_order_type), order_type);
+        }
+
+        for asset_type in AssetType::value_variants() {
+            let contract_asset_type = ContractAssetType::from(asset_type.clone());
+            assert_eq!(&AssetType::from(contract_asset_type), asset_type);
+        }
+        for asset_type in [ContractAssetType::Base, ContractAssetType::Quote] {
+            let cli_asset_type = AssetType::from(asset_type.clone());
+            assert_eq!(ContractAssetType::from(cli_asset_type), asset_type);
+        }
+
+        let account_id = format!("0x{}", "ab".repeat(32));
+        for account_type in AccountType::value_variants() {
+            let identity = account_type.identity(&account_id)?;
+            assert_eq!(&AccountType::from(&identity), account_type);
+        }
+        assert!(AccountType::Address.identity("0x1234").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn json_is_compact_unless_pretty() {
+        let value = serde_json::json!([
+            {"price": 70000, "cumulative_bid": 5, "cumulative_ask": 0},
+            {"price": 71000, "cumulative_bid": 0, "cumulative_ask": 3},
+        ]);
+
+        let compact = json_string(&value, false);
+        assert!(!compact.contains('\n'));
+        assert!(!compact.contains(": "));
+
+        let pretty = json_string(&value, true);
+        assert!(pretty.starts_with("[\n  {\n    \""));
+        assert!(pretty.contains("\n    \"price\": 70000"));
+
+        let parse = |json: &str| serde_json::from_str::<serde_json::Value>(json).unwrap();
+        assert_eq!(parse(&compact), value);
+        assert_eq!(parse(&pretty), value);
+    }
+
+    #[test]
+    fn quiet_output_is_only_the_result() {
+        // The lines printed by `core open`
+        let output = |quiet| {
+            let mut lines = Vec::new();
+            lines.extend(info_line("\nContract call cost: 12", quiet));
+            lines.push(result_line("Order ID: 0a0b", "0a0b", quiet));
+            lines.extend(info_line("Deposit consumed: 5 base, 0 quote", quiet));
+            lines
+        };
+
+        assert_eq!(output(true), vec!["0a0b"]);
+        assert_eq!(
+            output(false),
+            vec![
+                "\nContract call cost: 12",
+                "Order ID: 0a0b",
+                "Deposit consumed: 5 base, 0 quote"
+            ]
+        );
+    }
+
+    #[test]
+    fn no_color_output_has_no_ansi_escapes() {
+        assert!(paint(SUCCESS, "done", true).contains('\x1b'));
+        assert!(!paint(SUCCESS, "done", false).contains('\x1b'));
+        assert!(!paint(WARNING, "careful", false).contains('\x1b'));
+        assert!(!paint(ERROR, "failed", false).contains('\x1b'));
+
+        // Test output is captured rather than written to a terminal, as when piped
+        init_color(false);
+        assert!(!COLOR.load(Ordering::Relaxed));
+        init_color(true);
+        assert!(!COLOR.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn format_price_rounds_to_precision() {
+        // 1234.565 at 3 price decimals
+        assert_eq!(format_price(1_234_565, 3, 3), "1234.565");
+        assert_eq!(format_price(1_234_565, 3, 5), "1234.56500");
+        assert_eq!(format_price(1_234_565, 3, 2), "1234.56");
+        assert_eq!(format_price(1_234_575, 3, 2), "1234.58");
+        assert_eq!(format_price(1_234_567, 3, 2), "1234.57");
+
+        // Integer display rounds half to even
+        assert_eq!(format_price(2_500, 3, 0), "2");
+        assert_eq!(format_price(3_500, 3, 0), "4");
+        assert_eq!(format_price(3_501, 3, 0), "4");
+        assert_eq!(format_price(70_000_000_000_000, 9, 0), "70000");
+        assert_eq!(format_price(5, 9, 1), "0.0");
+    }
+
+    #[test]
+    fn parse_amount_scales_by_decimals() {
+        assert_eq!(parse_amount("0.01", 6).unwrap(), 10_000);
+        assert_eq!(parse_amount("1", 6).unwrap(), 1_000_000);
+        assert_eq!(parse_amount("1.5", 9).unwrap(), 1_500_000_000);
+        assert_eq!(parse_amount(".25", 2).unwrap(), 25);
+
+        assert!(parse_amount("0.0000001", 6).is_err());
+        assert!(parse_amount("-1", 6).is_err());
+        assert!(parse_amount(".", 6).is_err());
+        assert!(parse_amount("18446744073709551616", 0).is_err());
+    }
+
+    #[test]
+    fn format_amount_reverses_parse_amount() {
+        assert_eq!(format_amount(10_000, 6), "0.01");
+        assert_eq!(format_amount(1_500_000_000, 9), "1.5");
+        assert_eq!(format_amount(250_000_000_000, 6), "250000");
+        assert_eq!(format_amount(7, 0), "7");
+
+        for value in ["0.01", "1", "1.5", "123.456789"] {
+            assert_eq!(format_amount(parse_amount(value, 6).unwrap(), 6), value);
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! Quotes a two-sided ladder around a mid-price and re-quotes it on an interval
+//!
+//! ```text
+//! WALLET_SECRET=0x... cargo run -p spark-market-sdk --example market_maker -- \
+//!     <contract id> <rpc> <mid price> [levels] [spacing] [size] [interval seconds]
+//! ```
+//!
+//! The mid-price follows the last trade once the market has traded. Each round cancels the
+//! maker's orders that are no longer on the ladder and opens the missing levels, funding them
+//! from the deposited balance when it is enough and otherwise by attaching the deposit.
+
+use fuels::{
+    accounts::{provider::Provider, wallet::WalletUnlocked, Account},
+    core::codec::DecoderConfig,
+    crypto::SecretKey,
+    types::{Bits256, ContractId, Identity},
+};
+use spark_market_sdk::{DepositSource, OrderType, SparkMarketContract};
+use std::{str::FromStr, time::Duration};
+
+/// Order ids fetched per call when the maker has too many to fetch at once
+const PAGE_SIZE: u64 = 100;
+
+struct Config {
+    /// Quoted around until the market has a last trade price
+    mid_price: u64,
+    /// Orders on each side of the mid-price
+    levels: u64,
+    /// Price distance between two levels, and between the mid-price and the first level
+    spacing: u64,
+    /// Base amount of each order
+    size: u64,
+    interval: Duration,
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.len() < 3 {
+        anyhow::bail!(
+            "Usage: market_maker <contract id> <rpc> <mid price> [levels] [spacing] [size] [interval seconds]"
+        );
+    }
+    let arg = |index: usize, default: u64| -> anyhow::Result<u64> {
+        Ok(match args.get(index) {
+            Some(value) => value.parse()?,
+            None => default,
+        })
+    };
+
+    let contract_id = ContractId::from_str(&args[0]).expect("Invalid contract id");
+    let provider = Provider::connect(&args[1]).await?;
+    let secret = std::env::var("WALLET_SECRET")?;
+    let wallet =
+        WalletUnlocked::new_from_private_key(SecretKey::from_str(&secret)?, Some(provider));
+    let market = SparkMarketContract::new(contract_id, wallet).await;
+
+    let config = Config {
+        mid_price: arg(2, 0)?,
+        levels: arg(3, 3)?,
+        spacing: arg(4, 1)?,
+        size: arg(5, 1)?,
+        interval: Duration::from_secs(arg(6, 10)?),
+    };
+
+    run(&market, &config, None).await
+}
+
+/// Re-quotes every `config.interval`, for `rounds` rounds or until the process is stopped
+async fn run(
+    market: &SparkMarketContract<impl Account>,
+    config: &Config,
+    rounds: Option<usize>,
+) -> anyhow::Result<()> {
+    let mut round = 0;
+    while rounds.map_or(true, |rounds| round < rounds) {
+        // Markets deployed before the last trade price was recorded cannot report it
+        let last_trade_price = market.last_trade_price().await.ok().map(|r| r.value);
+        let mid_price = match last_trade_price {
+            None | Some(0) => config.mid_price,
+            Some(price) => price,
+        };
+        let (cancelled, opened) = requote(market, &ladder(mid_price, config)?, config.size).await?;
+
+        let depth = market.book_depth(maker_orders(market).await?).await?;
+        println!(
+            "Mid {}: cancelled {}, opened {}, bids {:?}, asks {:?}",
+            mid_price, cancelled, opened, depth.bids, depth.asks
+        );
+
+        round += 1;
+        tokio::time::sleep(config.interval).await;
+    }
+    Ok(())
+}
+
+/// The (order type, price) of every level, closest to the mid-price first
+fn ladder(mid_price: u64, config: &Config) -> anyhow::Result<Vec<(OrderType, u64)>> {
+    let reach = config.levels * config.spacing;
+    if reach >= mid_price {
+        anyhow::bail!("The ladder reaches below a zero price");
+    }
+
+    Ok((1..=config.levels)
+        .flat_map(|level| {
+            let offset = level * config.spacing;
+            [
+                (OrderType::Buy, mid_price - offset),
+                (OrderType::Sell, mid_price + offset),
+            ]
+        })
+        .collect())
+}
+
+/// Cancels the maker's orders which are not on `ladder` and opens the missing levels of `size`,
+/// returning how many were cancelled and opened
+async fn requote(
+    market: &SparkMarketContract<impl Account>,
+    ladder: &[(OrderType, u64)],
+    size: u64,
+) -> anyhow::Result<(usize, usize)> {
+    let config = market.config().await?;
+
+    let mut quoted = Vec::new();
+    let mut cancelled = 0;
+    for order_id in maker_orders(market).await? {
+        let Some(order) = market.order(order_id).await?.value else {
+            continue;
+        };
+        let level = (order.order_type.clone(), order.price);
+        // Levels already quoted once are stale too, as are partially filled orders
+        if ladder.contains(&level) && !quoted.contains(&level) && order.amount == size {
+            quoted.push(level);
+        } else {
+            market.cancel_order(order_id).await?;
+            cancelled += 1;
+        }
+    }
+
+    let maker = Identity::Address(market.get_instance().account().address().into());
+    let mut opened = 0;
+    for (order_type, price) in ladder.iter().filter(|level| !quoted.contains(level)) {
+        let (asset, deposit) = market
+            .required_deposit(size, order_type.clone(), *price)
+            .await?;
+        let liquid = market.account(maker).await?.value.liquid;
+        let available = match asset == config.base_asset {
+            true => liquid.base,
+            false => liquid.quote,
+        };
+        let source = match available >= deposit {
+            true => DepositSource::Balance,
+            false => DepositSource::Attached,
+        };
+        market
+            .open_order_from(size, order_type.clone(), *price, source)
+            .await?;
+        opened += 1;
+    }
+
+    Ok((cancelled, opened))
+}
+
+async fn maker_orders(market: &SparkMarketContract<impl Account>) -> anyhow::Result<Vec<Bits256>> {
+    let maker = Identity::Address(market.get_instance().account().address().into());
+    Ok(market
+        .all_user_orders(maker, PAGE_SIZE, DecoderConfig::default())
+        .await?
+        .orders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuels::{
+        prelude::{launch_custom_provider_and_get_wallets, AssetConfig, WalletsConfig},
+        types::AssetId,
+    };
+
+    #[tokio::test]
+    async fn keeps_a_ladder_around_the_mid_price() -> anyhow::Result<()> {
+        let (base, quote) = (AssetId::from([1; 32]), AssetId::from([2; 32]));
+        let assets = [AssetId::default(), base, quote]
+            .into_iter()
+            .map(|id| AssetConfig {
+                id,
+                num_coins: 1,
+                coin_amount: 1_000_000_000_000,
+            })
+            .collect();
+        let mut wallets = launch_custom_provider_and_get_wallets(
+            WalletsConfig::new_multiple_assets(1, assets),
+            None,
+            None,
+        )
+        .await?;
+        let wallet = wallets.pop().unwrap();
+        let market = SparkMarketContract::deploy(base, 9, quote, 6, wallet, 9, 0x000400).await?;
+
+        // Levels 1 USDC apart around 50,000 USDC, 0.1 BTC each
+        let unit = 10_u64.pow(9);
+        let config = Config {
+            mid_price: 50_000 * unit,
+            levels: 2,
+            spacing: unit,
+            size: 100_000_000,
+            interval: Duration::ZERO,
+        };
+
+        // Later rounds find the ladder in place and leave it alone
+        run(&market, &config, Some(3)).await?;
+        let depth = market.book_depth(maker_orders(&market).await?).await?;
+        assert_eq!(
+            depth.bids,
+            vec![(49_999 * unit, 100_000_000), (49_998 * unit, 100_000_000)]
+        );
+        assert_eq!(
+            depth.asks,
+            vec![(50_001 * unit, 100_000_000), (50_002 * unit, 100_000_000)]
+        );
+
+        // A new mid-price cancels the levels that moved and reuses the freed deposits
+        let moved = Config {
+            mid_price: 50_001 * unit,
+            ..config
+        };
+        assert_eq!(
+            requote(&market, &ladder(moved.mid_price, &moved)?, moved.size).await?,
+            (2, 2)
+        );
+        let depth = market.book_depth(maker_orders(&market).await?).await?;
+        assert_eq!(
+            depth.bids,
+            vec![(50_000 * unit, 100_000_000), (49_999 * unit, 100_000_000)]
+        );
+        assert_eq!(
+            depth.asks,
+            vec![(50_002 * unit, 100_000_000), (50_003 * unit, 100_000_000)]
+        );
+
+        Ok(())
+    }
+}
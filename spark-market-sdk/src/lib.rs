@@ -1,14 +1,54 @@
 use fuels::{
+    accounts::provider::TransactionCost,
+    core::{
+        codec::DecoderConfig,
+        traits::{Parameterize, Tokenizable},
+    },
+    crypto::Hasher,
     prelude::{
         abigen, AssetId, CallParameters, Contract, ContractId, LoadConfiguration,
         StorageConfiguration, TxPolicies, VariableOutputPolicy, WalletUnlocked,
     },
-    programs::{calls::Execution, responses::CallResponse},
-    types::{bech32::Bech32ContractId, Bits256, Bytes32, Identity},
+    programs::{
+        calls::{CallHandler, ContractCall, ContractDependency, Execution},
+        responses::CallResponse,
+    },
+    tx::Output,
+    types::{
+        bech32::Bech32ContractId,
+        errors::{error, Error},
+        transaction::{Transaction, TransactionType},
+        tx_status::TxStatus,
+        Address, Bits256, Bytes32, Identity, Token,
+    },
 };
 
+use futures::{stream, Future, StreamExt};
 use rand::Rng;
-use std::path::PathBuf;
+use serde_json::{json, Value};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+use tokio::{
+    runtime::Handle,
+    sync::{mpsc, oneshot, Mutex as AsyncMutex},
+    task::JoinHandle,
+    time::{sleep_until, timeout_at, Instant},
+};
+
+mod math;
+
+pub use math::{
+    base_of_quote_amount, checked_sum, mul_div, quote_of_base_amount, quote_scale, scaled_price,
+    Price, RoundingMode,
+};
 
 abigen!(Contract(
     name = "SparkMarket",
@@ -18,272 +58,2381 @@ abigen!(Contract(
 const MARKET_CONTRACT_BINARY_PATH: &str = "spark-market/out/release/spark-market.bin";
 const MARKET_CONTRACT_STORAGE_PATH: &str =
     "spark-market/out/release/spark-market-storage_slots.json";
+const HUNDRED_PERCENT: u64 = 10_000;
+const MAX_BATCH_SIZE: usize = 32;
 
-pub struct SparkMarketContract {
-    instance: SparkMarket<WalletUnlocked>,
+/// What the market should hold locked for `orders`, plus `keeper_rewards` locked in quote by
+/// orders opened with a deadline, rounded as the market rounds each lock
+pub fn expected_locked(
+    orders: &[Order],
+    keeper_rewards: u64,
+    config: &MarketConfig,
+) -> anyhow::Result<Balance> {
+    let mut locked = Balance {
+        base: 0,
+        quote: keeper_rewards,
+    };
+    for order in orders {
+        match order.order_type {
+            OrderType::Sell => locked.base = checked_sum(&[locked.base, order.amount])?,
+            OrderType::Buy => {
+                let quote = quote_of_base_amount(
+                    order.amount,
+                    order.price,
+                    config.base_decimals,
+                    config.quote_decimals,
+                    config.price_decimals,
+                    RoundingMode::Floor,
+                )?;
+                let protocol_fee = mul_div(
+                    quote,
+                    order.protocol_maker_fee.max(order.protocol_taker_fee),
+                    HUNDRED_PERCENT,
+                    RoundingMode::Floor,
+                )?;
+                locked.quote =
+                    checked_sum(&[locked.quote, quote, protocol_fee, order.matcher_fee])?;
+            }
+        }
+    }
+    Ok(locked)
 }
 
-impl SparkMarketContract {
-    pub async fn deploy(
-        base_asset: AssetId,
-        base_decimals: u32,
-        quote_asset: AssetId,
-        quote_decimals: u32,
-        owner: WalletUnlocked,
-        price_decimals: u32,
-        version: u32,
-    ) -> anyhow::Result<Self> {
-        let mut rng = rand::thread_rng();
-        let salt = rng.gen::<[u8; 32]>();
+/// The matcher fees the market pays out when `order0` and `order1` trade, each order paying its
+/// matcher fee pro rata to the traded share of its amount. `None` when the orders do not cross
+pub fn expected_matcher_reward(order0: &Order, order1: &Order) -> Option<u64> {
+    let (sell, buy) = match (&order0.order_type, &order1.order_type) {
+        (OrderType::Sell, OrderType::Buy) => (order0, order1),
+        (OrderType::Buy, OrderType::Sell) => (order1, order0),
+        _ => return None,
+    };
+    if sell.price > buy.price {
+        return None;
+    }
 
-        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        let storage_configuration = StorageConfiguration::default()
-            .add_slot_overrides_from_file(root.join(MARKET_CONTRACT_STORAGE_PATH));
+    let trade_size = sell.amount.min(buy.amount);
+    let fee_of = |order: &Order| {
+        (order.matcher_fee as u128 * trade_size as u128 / order.amount as u128) as u64
+    };
+    Some(fee_of(sell) + fee_of(buy))
+}
 
-        let configurables = SparkMarketConfigurables::default()
-            .with_BASE_ASSET(base_asset)
-            .unwrap()
-            .with_BASE_ASSET_DECIMALS(base_decimals)
-            .unwrap()
-            .with_QUOTE_ASSET(quote_asset)
-            .unwrap()
-            .with_QUOTE_ASSET_DECIMALS(quote_decimals)
-            .unwrap()
-            .with_OWNER(owner.address().into())
-            .unwrap()
-            .with_PRICE_DECIMALS(price_decimals)
-            .unwrap()
-            .with_VERSION(version)
-            .unwrap();
+/// Flags the orders of `opened`, each with the block it was opened in, that `trades` filled
+/// within `max_blocks` of that block against an order opened in an earlier block or before
+/// `opened` starts
+pub fn flag_frontrun(
+    opened: &[(u32, OpenOrderEvent)],
+    trades: &[TradeOrderEvent],
+    max_blocks: u32,
+) -> Vec<FrontRunSuspect> {
+    let opened_at: HashMap<[u8; 32], (u32, &OpenOrderEvent)> = opened
+        .iter()
+        .map(|(block, event)| (event.order_id.0, (*block, event)))
+        .collect();
 
-        let contract_configuration = LoadConfiguration::default()
-            .with_storage_configuration(storage_configuration?)
-            .with_configurables(configurables);
+    let mut suspects = Vec::new();
+    for trade in trades {
+        let sides = [
+            (
+                trade.base_buy_order_id,
+                trade.base_sell_order_id,
+                &trade.order_seller,
+            ),
+            (
+                trade.base_sell_order_id,
+                trade.base_buy_order_id,
+                &trade.order_buyer,
+            ),
+        ];
+        for (order_id, counterparty_id, counterparty) in sides {
+            let Some((opened_block, event)) = opened_at.get(&order_id.0) else {
+                continue;
+            };
+            let resting_before = opened_at
+                .get(&counterparty_id.0)
+                .map_or(true, |(block, _)| block < opened_block);
+            if trade.block_height.saturating_sub(*opened_block) <= max_blocks && resting_before {
+                suspects.push(FrontRunSuspect {
+                    order_id,
+                    owner: event.user,
+                    opened_block: *opened_block,
+                    matched_block: trade.block_height,
+                    counterparty_id,
+                    counterparty: *counterparty,
+                    trade_price: trade.trade_price,
+                    trade_size: trade.trade_size,
+                });
+            }
+        }
+    }
+    suspects
+}
 
-        let contract_id = Contract::load_from(
-            root.join(MARKET_CONTRACT_BINARY_PATH),
-            contract_configuration,
-        )?
-        .with_salt(salt)
-        .deploy(&owner, TxPolicies::default())
-        .await?;
+/// Runs `f` over `items` with at most `max_in_flight` futures pending at once and returns the
+/// outputs in the order of `items`
+pub async fn run_bounded<T, F, Fut>(items: Vec<T>, max_in_flight: usize, f: F) -> Vec<Fut::Output>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future,
+{
+    stream::iter(items)
+        .map(f)
+        .buffered(max_in_flight.max(1))
+        .collect()
+        .await
+}
 
-        let market = SparkMarket::new(contract_id.clone(), owner.clone());
+async fn call_with_funds<A, T>(
+    call: CallHandler<A, ContractCall, T>,
+    asset_id: AssetId,
+    amount: u64,
+    var_outputs: usize,
+    fee_checks: &FeeChecks,
+) -> anyhow::Result<CallResponse<T>>
+where
+    A: fuels::accounts::Account,
+    T: Tokenizable + Parameterize + Debug,
+{
+    let call = if amount > 0 {
+        let call_params = CallParameters::default()
+            .with_asset_id(asset_id)
+            .with_amount(amount);
+        call.call_params(call_params)?
+    } else {
+        call
+    };
 
-        Ok(Self { instance: market })
-    }
+    let call = call.with_variable_output_policy(VariableOutputPolicy::Exactly(var_outputs));
+    check_fee(&call, fee_checks).await?;
 
-    pub async fn new(contract_id: ContractId, wallet: WalletUnlocked) -> Self {
-        let _self = Self {
-            instance: SparkMarket::new(contract_id, wallet),
-        };
-        assert!(
-            _self.contract_version().await.unwrap() & 0xFF0000 == Self::sdk_version() & 0xFF0000,
-            "Market contract version mismatch with SDK version"
-        );
-        _self
-    }
+    Ok(call.call().await?)
+}
 
-    pub fn get_instance(&self) -> &SparkMarket<WalletUnlocked> {
-        &self.instance
+/// Asked with the estimated fee of a call before it is sent, the call is only sent if it
+/// returns `true`
+pub type FeeConfirmation = Arc<dyn Fn(u64) -> bool + Send + Sync>;
+
+/// The checks a call's estimated fee must pass before the call is sent
+#[derive(Clone, Default)]
+struct FeeChecks {
+    /// Calls whose estimated fee exceeds it are not sent
+    max_fee: Option<u64>,
+    confirm: Option<FeeConfirmation>,
+}
+
+/// What an opened order took from the liquid balance, from the balance before the transaction,
+/// the base and quote attached to it and the liquid balance its `OpenOrderEvent` reports after
+/// the lock
+fn liquid_taken(
+    before: &Balance,
+    (base_attached, quote_attached): (u64, u64),
+    events: &[OpenOrderEvent],
+) -> anyhow::Result<(u64, u64)> {
+    let event = events
+        .last()
+        .ok_or_else(|| anyhow::anyhow!("Missing OpenOrderEvent"))?;
+    let taken = |before: u64, attached: u64, after: u64| {
+        before
+            .checked_add(attached)
+            .and_then(|available| available.checked_sub(after))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Liquid balance grew from {} to {} while opening the order",
+                    before,
+                    after
+                )
+            })
+    };
+    Ok((
+        taken(before.base, base_attached, event.liquid_base)?,
+        taken(before.quote, quote_attached, event.liquid_quote)?,
+    ))
+}
+
+/// Fails with `FeeTooHigh` when the estimated fee of `call` exceeds the cap, or with
+/// `FeeDeclined` when the confirmation declines it, before it is sent
+async fn check_fee<A, T>(
+    call: &CallHandler<A, ContractCall, T>,
+    fee_checks: &FeeChecks,
+) -> anyhow::Result<()>
+where
+    A: fuels::accounts::Account,
+    T: Tokenizable + Parameterize + Debug,
+{
+    check_estimated_fee(call.estimate_transaction_cost(None, None), fee_checks).await
+}
+
+/// Like `check_fee` for any transaction, multicalls and calls that are submitted without
+/// awaiting their result included. `estimate` is only awaited when a check is set
+async fn check_estimated_fee(
+    estimate: impl Future<Output = fuels::types::errors::Result<TransactionCost>>,
+    fee_checks: &FeeChecks,
+) -> anyhow::Result<()> {
+    if fee_checks.max_fee.is_none() && fee_checks.confirm.is_none() {
+        return Ok(());
     }
 
-    pub async fn with_account(&self, account: &WalletUnlocked) -> anyhow::Result<Self> {
-        Ok(Self {
-            instance: self.instance.clone().with_account(account.clone()),
-        })
+    // Estimated once and shared by both checks
+    let estimated = estimate.await?.total_fee;
+    if let Some(limit) = fee_checks.max_fee {
+        if estimated > limit {
+            return Err(FeeTooHigh { estimated, limit }.into());
+        }
     }
+    if let Some(confirm) = &fee_checks.confirm {
+        if !confirm(estimated) {
+            return Err(FeeDeclined { estimated }.into());
+        }
+    }
+    Ok(())
+}
 
-    pub fn id(&self) -> Bytes32 {
-        self.instance.contract_id().hash
+async fn with_gas_multiplier<A, T>(
+    call: CallHandler<A, ContractCall, T>,
+    gas_multiplier: f64,
+) -> anyhow::Result<CallHandler<A, ContractCall, T>>
+where
+    A: fuels::accounts::Account,
+    T: Tokenizable + Parameterize + Debug,
+{
+    if gas_multiplier < 1.0 {
+        anyhow::bail!("Gas multiplier must be at least 1");
     }
 
-    pub fn contract_id(&self) -> &Bech32ContractId {
-        self.instance.contract_id()
+    let gas_used = call.estimate_transaction_cost(None, None).await?.gas_used;
+    let script_gas_limit = (gas_used as f64 * gas_multiplier).ceil() as u64;
+
+    Ok(call.with_tx_policies(TxPolicies::default().with_script_gas_limit(script_gas_limit)))
+}
+
+fn map_price_error(error: anyhow::Error) -> anyhow::Error {
+    let message = error.to_string();
+    if message.contains("PriceOutOfBand") {
+        error.context("Order price is outside the allowed band around the last traded price")
+    } else if message.contains("InvalidTick") {
+        error.context("Order price is not a multiple of the market tick size")
+    } else {
+        error
     }
+}
 
-    pub async fn contract_version(&self) -> anyhow::Result<u32> {
-        let (_, _, _, _, _, _, version) = self.config().await?.value;
-        Ok(version)
+/// Whether `error` means the returned data was too large, the decoder's token limit or the VM
+/// running out of memory for it. Running out of gas is not one of these, smaller pages would not
+/// help
+fn is_return_size_error(error: &Error) -> bool {
+    let error = error.to_string();
+    ["token limit", "MemoryOverflow"]
+        .iter()
+        .any(|reason| error.contains(reason))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UserOrderIds {
+    pub orders: Vec<Bits256>,
+    /// Set when the orders had to be fetched page by page
+    pub warning: Option<String>,
+}
+
+impl Order {
+    pub fn builder() -> OrderBuilder {
+        OrderBuilder {
+            order: Order {
+                amount: 0,
+                asset_type: AssetType::Base,
+                order_type: OrderType::Sell,
+                owner: Identity::Address(Address::zeroed()),
+                price: 0,
+                block_height: 0,
+                order_height: 0,
+                matcher_fee: 0,
+                protocol_maker_fee: 0,
+                protocol_taker_fee: 0,
+            },
+        }
     }
+}
 
-    pub async fn contract_str_version(&self) -> anyhow::Result<String> {
-        let version = self.contract_version().await?;
-        Ok(format!(
-            "{}.{}.{}",
-            (version & 0xFF0000) >> 16,
-            (version & 0xFF00) >> 8,
-            version & 0xFF
-        ))
+/// Builds an expected `Order`, fields which are not set are zero, `Base` and `Sell`
+#[derive(Clone, Debug)]
+pub struct OrderBuilder {
+    order: Order,
+}
+
+impl OrderBuilder {
+    pub fn amount(mut self, amount: u64) -> Self {
+        self.order.amount = amount;
+        self
     }
 
-    pub fn sdk_version() -> u32 {
-        let s_version = Self::sdk_str_version();
-        // Converts "0.1.1" string version to 257u32 (0x000101)
-        let version = s_version.split('.').collect::<Vec<&str>>();
-        let len = version.len();
-        version
-            .iter()
-            .enumerate()
-            .map(|(i, &x)| x.parse::<u32>().unwrap() << (8 * (len - i - 1)))
-            .collect::<Vec<u32>>()
-            .iter()
-            .sum()
+    pub fn asset_type(mut self, asset_type: AssetType) -> Self {
+        self.order.asset_type = asset_type;
+        self
     }
 
-    pub fn sdk_str_version() -> String {
-        env!("CARGO_PKG_VERSION").into()
+    pub fn order_type(mut self, order_type: OrderType) -> Self {
+        self.order.order_type = order_type;
+        self
     }
 
-    pub async fn deposit(&self, amount: u64, asset: AssetId) -> anyhow::Result<CallResponse<()>> {
-        let call_params = CallParameters::new(amount, asset, 1_000_000);
+    pub fn owner(mut self, owner: Identity) -> Self {
+        self.order.owner = owner;
+        self
+    }
 
-        Ok(self
-            .instance
-            .methods()
-            .deposit()
-            .call_params(call_params)?
-            .call()
-            .await?)
+    pub fn price(mut self, price: u64) -> Self {
+        self.order.price = price;
+        self
     }
 
-    pub async fn withdraw(
-        &self,
-        amount: u64,
-        asset_type: AssetType,
-    ) -> anyhow::Result<CallResponse<()>> {
-        Ok(self
-            .instance
-            .methods()
-            .withdraw(amount, asset_type)
-            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
-            .call()
-            .await?)
+    pub fn block_height(mut self, block_height: u32) -> Self {
+        self.order.block_height = block_height;
+        self
     }
 
-    pub async fn open_order(
-        &self,
-        amount: u64,
-        order_type: OrderType,
-        price: u64,
-    ) -> anyhow::Result<CallResponse<Bits256>> {
-        Ok(self
-            .instance
-            .methods()
-            .open_order(amount, order_type, price)
-            .call()
-            .await?)
+    pub fn order_height(mut self, order_height: u64) -> Self {
+        self.order.order_height = order_height;
+        self
     }
 
-    pub async fn cancel_order(&self, order_id: Bits256) -> anyhow::Result<CallResponse<()>> {
-        Ok(self
-            .instance
-            .methods()
-            .cancel_order(order_id)
-            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
-            .call()
-            .await?)
+    pub fn matcher_fee(mut self, matcher_fee: u64) -> Self {
+        self.order.matcher_fee = matcher_fee;
+        self
     }
 
-    pub async fn match_order_pair(
-        &self,
-        order_id0: Bits256,
-        order_id1: Bits256,
-    ) -> anyhow::Result<CallResponse<()>> {
-        Ok(self
-            .instance
-            .methods()
-            .match_order_pair(order_id0, order_id1)
-            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
-            .call()
-            .await?)
+    pub fn protocol_fee(mut self, maker_fee: u64, taker_fee: u64) -> Self {
+        self.order.protocol_maker_fee = maker_fee;
+        self.order.protocol_taker_fee = taker_fee;
+        self
     }
 
-    pub async fn match_order_many(&self, orders: Vec<Bits256>) -> anyhow::Result<CallResponse<()>> {
-        Ok(self
-            .instance
-            .methods()
-            .match_order_many(orders)
-            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
-            .call()
-            .await?)
+    pub fn build(self) -> Order {
+        self.order
     }
+}
 
-    pub async fn fulfill_many(
-        &self,
+/// A market call in a form which can be saved and submitted again
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MarketCall {
+    OpenOrder {
+        amount: u64,
+        order_type: OrderType,
+        price: u64,
+    },
+    CancelOrder {
+        order_id: Bits256,
+    },
+    MatchOrderPair {
+        order_id0: Bits256,
+        order_id1: Bits256,
+    },
+    MatchOrderMany {
+        orders: Vec<Bits256>,
+    },
+    FulfillMany {
         amount: u64,
         order_type: OrderType,
         limit_type: LimitType,
         price: u64,
         slippage: u64,
         orders: Vec<Bits256>,
-    ) -> anyhow::Result<CallResponse<Bits256>> {
-        Ok(self
-            .instance
-            .methods()
-            .fulfill_order_many(amount, order_type, limit_type, price, slippage, orders)
-            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
-            .call()
-            .await?)
+    },
+}
+
+impl MarketCall {
+    pub fn to_json(&self) -> String {
+        let hex = |id: &Bits256| format!("0x{}", id.0.map(|b| format!("{:02x}", b)).concat());
+        let value = match self {
+            MarketCall::OpenOrder {
+                amount,
+                order_type,
+                price,
+            } => json!({
+                "method": "open_order",
+                "amount": amount,
+                "order_type": format!("{:?}", order_type),
+                "price": price,
+            }),
+            MarketCall::CancelOrder { order_id } => json!({
+                "method": "cancel_order",
+                "order_id": hex(order_id),
+            }),
+            MarketCall::MatchOrderPair {
+                order_id0,
+                order_id1,
+            } => json!({
+                "method": "match_order_pair",
+                "order_id0": hex(order_id0),
+                "order_id1": hex(order_id1),
+            }),
+            MarketCall::MatchOrderMany { orders } => json!({
+                "method": "match_order_many",
+                "orders": orders.iter().map(hex).collect::<Vec<_>>(),
+            }),
+            MarketCall::FulfillMany {
+                amount,
+                order_type,
+                limit_type,
+                price,
+                slippage,
+                orders,
+            } => json!({
+                "method": "fulfill_many",
+                "amount": amount,
+                "order_type": format!("{:?}", order_type),
+                "limit_type": format!("{:?}", limit_type),
+                "price": price,
+                "slippage": slippage,
+                "orders": orders.iter().map(hex).collect::<Vec<_>>(),
+            }),
+        };
+        value.to_string()
     }
 
-    pub async fn set_protocol_fee(
-        &self,
-        fee: Vec<ProtocolFee>,
-    ) -> anyhow::Result<CallResponse<()>> {
-        Ok(self.instance.methods().set_protocol_fee(fee).call().await?)
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        let value: Value = serde_json::from_str(json)?;
+        let field = |name: &str| {
+            value
+                .get(name)
+                .ok_or_else(|| anyhow::anyhow!("Missing field: {}", name))
+        };
+        let u64_field = |name: &str| {
+            field(name)?
+                .as_u64()
+                .ok_or_else(|| anyhow::anyhow!("Invalid field: {}", name))
+        };
+        let str_field = |name: &str| {
+            field(name)?
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("Invalid field: {}", name))
+        };
+        let id_field =
+            |name: &str| Ok::<_, anyhow::Error>(Bits256::from_hex_str(str_field(name)?)?);
+        let ids_field = |name: &str| {
+            field(name)?
+                .as_array()
+                .ok_or_else(|| anyhow::anyhow!("Invalid field: {}", name))?
+                .iter()
+                .map(|id| {
+                    let id = id
+                        .as_str()
+                        .ok_or_else(|| anyhow::anyhow!("Invalid field: {}", name))?;
+                    Ok(Bits256::from_hex_str(id)?)
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        };
+        let order_type_field = || match str_field("order_type")? {
+            "Buy" => Ok(OrderType::Buy),
+            "Sell" => Ok(OrderType::Sell),
+            other => anyhow::bail!("Invalid order type: {}", other),
+        };
+        let limit_type_field = || match str_field("limit_type")? {
+            "GTC" => Ok(LimitType::GTC),
+            "IOC" => Ok(LimitType::IOC),
+            "FOK" => Ok(LimitType::FOK),
+            other => anyhow::bail!("Invalid limit type: {}", other),
+        };
+
+        Ok(match str_field("method")? {
+            "open_order" => MarketCall::OpenOrder {
+                amount: u64_field("amount")?,
+                order_type: order_type_field()?,
+                price: u64_field("price")?,
+            },
+            "cancel_order" => MarketCall::CancelOrder {
+                order_id: id_field("order_id")?,
+            },
+            "match_order_pair" => MarketCall::MatchOrderPair {
+                order_id0: id_field("order_id0")?,
+                order_id1: id_field("order_id1")?,
+            },
+            "match_order_many" => MarketCall::MatchOrderMany {
+                orders: ids_field("orders")?,
+            },
+            "fulfill_many" => MarketCall::FulfillMany {
+                amount: u64_field("amount")?,
+                order_type: order_type_field()?,
+                limit_type: limit_type_field()?,
+                price: u64_field("price")?,
+                slippage: u64_field("slippage")?,
+                orders: ids_field("orders")?,
+            },
+            other => anyhow::bail!("Unknown method: {}", other),
+        })
     }
+}
 
-    pub async fn set_matcher_fee(&self, amount: u64) -> anyhow::Result<CallResponse<()>> {
-        Ok(self
-            .instance
-            .methods()
-            .set_matcher_fee(amount)
-            .call()
-            .await?)
+/// The market configuration with named fields in place of the contract's tuple
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MarketConfig {
+    pub base_asset: AssetId,
+    pub base_decimals: u32,
+    pub quote_asset: AssetId,
+    pub quote_decimals: u32,
+    pub owner: Identity,
+    pub price_decimals: u32,
+    pub version: u32,
+}
+
+impl MarketConfig {
+    /// Scales a whole-unit price by the market's price decimals
+    pub fn price(&self, value: u64) -> anyhow::Result<Price> {
+        Price::from_human(value, self.price_decimals)
     }
+}
 
-    pub async fn account(&self, user: Identity) -> anyhow::Result<CallResponse<Account>> {
-        Ok(self
-            .instance
-            .methods()
-            .account(user)
-            .simulate(Execution::StateReadOnly)
-            .await?)
+impl From<(AssetId, u32, AssetId, u32, Identity, u32, u32)> for MarketConfig {
+    fn from(config: (AssetId, u32, AssetId, u32, Identity, u32, u32)) -> Self {
+        let (
+            base_asset,
+            base_decimals,
+            quote_asset,
+            quote_decimals,
+            owner,
+            price_decimals,
+            version,
+        ) = config;
+        Self {
+            base_asset,
+            base_decimals,
+            quote_asset,
+            quote_decimals,
+            owner,
+            price_decimals,
+            version,
+        }
     }
+}
 
-    pub async fn protocol_fee(&self) -> anyhow::Result<CallResponse<Vec<ProtocolFee>>> {
-        Ok(self
-            .instance
-            .methods()
-            .protocol_fee()
-            .simulate(Execution::StateReadOnly)
-            .await?)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TradeQuote {
+    pub quote: u64,
+    pub maker_fee: u64,
+    pub taker_fee: u64,
+    pub matcher_fee: u64,
+}
+
+/// A newly opened order and the amounts its lock took from the owner's liquid balance
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpenedOrder {
+    pub order_id: Bits256,
+    pub base_deposited: u64,
+    pub quote_deposited: u64,
+}
+
+/// What an order was opened with, as logged by the session that opened it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OrderParams {
+    pub owner: Identity,
+    pub order_type: OrderType,
+    pub price: u64,
+    pub amount: u64,
+    pub deadline: Option<u32>,
+}
+
+/// How many orders the opened-order log of a session keeps unless set with
+/// `with_order_log_capacity`
+pub const ORDER_LOG_CAPACITY: usize = 10_000;
+
+/// The parameters of the orders opened by a session, the least recently used dropped once more
+/// than `capacity` are logged
+struct OrderLog {
+    capacity: usize,
+    /// Keyed by the id bytes, with the tick of their last use
+    entries: HashMap<[u8; 32], (u64, OrderParams)>,
+    /// The ids by the tick of their last use, least recent first
+    by_use: BTreeMap<u64, [u8; 32]>,
+    tick: u64,
+}
+
+impl Default for OrderLog {
+    fn default() -> Self {
+        Self {
+            capacity: ORDER_LOG_CAPACITY,
+            entries: HashMap::new(),
+            by_use: BTreeMap::new(),
+            tick: 0,
+        }
     }
+}
 
-    pub async fn protocol_fee_user(
-        &self,
-        user: Identity,
-    ) -> anyhow::Result<CallResponse<(u64, u64)>> {
-        Ok(self
-            .instance
-            .methods()
-            .protocol_fee_user(user)
-            .simulate(Execution::StateReadOnly)
-            .await?)
+impl OrderLog {
+    fn insert(&mut self, order_id: [u8; 32], params: OrderParams) {
+        self.tick += 1;
+        if let Some((used, _)) = self.entries.insert(order_id, (self.tick, params)) {
+            self.by_use.remove(&used);
+        }
+        self.by_use.insert(self.tick, order_id);
+        self.evict();
     }
 
-    pub async fn protocol_fee_user_amount(
-        &self,
-        amount: u64,
-        user: Identity,
-    ) -> anyhow::Result<CallResponse<(u64, u64)>> {
-        Ok(self
-            .instance
-            .methods()
-            .protocol_fee_user_amount(amount, user)
-            .simulate(Execution::StateReadOnly)
-            .await?)
+    fn get(&mut self, order_id: &[u8; 32]) -> Option<OrderParams> {
+        self.tick += 1;
+        let (used, params) = self.entries.get_mut(order_id)?;
+        self.by_use.remove(used);
+        *used = self.tick;
+        self.by_use.insert(self.tick, *order_id);
+        Some(params.clone())
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            let Some((_, order_id)) = self.by_use.pop_first() else {
+                break;
+            };
+            self.entries.remove(&order_id);
+        }
+    }
+}
+
+/// An open order keyed by its id, independent of the contract's `Order` layout
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OrderDetails {
+    pub id: Bits256,
+    pub owner: Identity,
+    pub order_type: OrderType,
+    pub price: u64,
+    pub amount: u64,
+    pub block_height: u32,
+    pub order_height: u64,
+}
+
+impl OrderDetails {
+    fn new(id: Bits256, order: Order) -> Self {
+        Self {
+            id,
+            owner: order.owner,
+            order_type: order.order_type,
+            price: order.price,
+            amount: order.amount,
+            block_height: order.block_height,
+            order_height: order.order_height,
+        }
+    }
+}
+
+/// The base amount of an order when it was opened and what is left of it after partial fills
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OrderSize {
+    pub original: u64,
+    pub remaining: u64,
+}
+
+/// A trader's locked balances as reported by the market and as summed from their open orders
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LockedReconciliation {
+    pub reported: Balance,
+    pub expected: Balance,
+}
+
+impl LockedReconciliation {
+    /// Reported minus expected locked base
+    pub fn base_delta(&self) -> i128 {
+        self.reported.base as i128 - self.expected.base as i128
+    }
+
+    /// Reported minus expected locked quote
+    pub fn quote_delta(&self) -> i128 {
+        self.reported.quote as i128 - self.expected.quote as i128
+    }
+
+    pub fn is_balanced(&self) -> bool {
+        self.reported == self.expected
+    }
+}
+
+impl std::fmt::Display for LockedReconciliation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "locked base {} (expected {}, delta {}), locked quote {} (expected {}, delta {})",
+            self.reported.base,
+            self.expected.base,
+            self.base_delta(),
+            self.reported.quote,
+            self.expected.quote,
+            self.quote_delta()
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OrderAging {
+    pub tip: u32,
+    pub oldest: u32,
+    pub median: u32,
+    /// (lowest age in the bucket, number of orders)
+    pub buckets: Vec<(u32, u64)>,
+}
+
+#[derive(Debug)]
+pub enum MatchOutcome {
+    Submitted(CallResponse<()>),
+    /// The simulated match found nothing left to match, so no transaction was sent
+    NoLongerCrossing,
+}
+
+/// Where the funds locked by a new order come from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DepositSource {
+    /// The liquid balance already deposited in the market, nothing is transferred
+    Balance,
+    /// Exactly what `required_deposit` asks for, attached to the call from the wallet
+    Attached,
+}
+
+/// Bounds of a `fulfill_many_with_limits` sweep besides its price limit, `None` leaves one open
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FulfillLimits {
+    /// Quote a buy may pay at the trade prices of its fills
+    pub max_quote_spend: Option<u64>,
+    /// Base a buy must receive, the sweep is not sent when the other bounds leave less
+    pub min_base_received: Option<u64>,
+    /// Resting orders the sweep may fill, partially or fully
+    pub max_orders_to_consume: Option<usize>,
+}
+
+/// What ended a `fulfill_many_with_limits` sweep
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FulfillStop {
+    /// The whole amount was filled
+    Filled,
+    /// No more of the given orders cross within the price and slippage
+    PriceLimit,
+    MaxQuoteSpend,
+    MaxOrdersToConsume,
+}
+
+/// The trade logged by a pair match
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MatchExecution {
+    pub sell_order_id: Bits256,
+    pub buy_order_id: Bits256,
+    pub trade_size: u64,
+    /// The sell price, or the resting buy price under maker-price execution
+    pub execution_price: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoundedFill {
+    pub order_id: Bits256,
+    pub stop: FulfillStop,
+    pub base_filled: u64,
+    /// Quote of the fills at their trade prices, paid by a buy or received by a sell
+    pub quote: u64,
+}
+
+/// Price levels of a book as (price, base amount), each side ordered from the best price outward
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BookDepth {
+    pub bids: Vec<(u64, u64)>,
+    pub asks: Vec<(u64, u64)>,
+}
+
+/// The market event that changed a trader's liquid balances
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BalanceChangeKind {
+    Deposit,
+    Withdraw,
+    OpenOrder,
+    CancelOrder,
+    Trade,
+}
+
+/// A trader's liquid balances right after an event that changed them
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BalanceChange {
+    pub block_height: u32,
+    pub tx_id: Bytes32,
+    pub kind: BalanceChangeKind,
+    pub liquid_base: u64,
+    pub liquid_quote: u64,
+}
+
+/// The fee a `FeeChange` replaced and the fee it set
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FeeChangeKind {
+    Matcher {
+        old_fee: u64,
+        new_fee: u64,
+    },
+    Protocol {
+        old_fee: Vec<ProtocolFee>,
+        new_fee: Vec<ProtocolFee>,
+    },
+}
+
+/// A change of the matcher or protocol fee and who made it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeeChange {
+    pub block_height: u32,
+    pub tx_id: Bytes32,
+    pub changed_by: Identity,
+    pub kind: FeeChangeKind,
+}
+
+/// A fill of a taker order against one resting maker order
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditedFill {
+    pub maker_id: Bits256,
+    /// The execution price the trade settled at, the resting buy's price under maker-price
+    /// execution and the sell price otherwise
+    pub price: u64,
+    pub amount: u64,
+}
+
+/// A pair of orders whose matcher reward outweighs the fee of matching them
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProfitableMatch {
+    pub order_ids: (Bits256, Bits256),
+    /// The matcher fees the pair pays out, in quote
+    pub reward: u64,
+    /// The estimated fee of the match, converted to quote
+    pub cost: u64,
+    pub profit: u64,
+}
+
+/// An order that traded within a few blocks of being opened against an order that was
+/// already resting, the pattern of an order placed ahead of a pending match
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrontRunSuspect {
+    pub order_id: Bits256,
+    pub owner: Identity,
+    pub opened_block: u32,
+    pub matched_block: u32,
+    /// The resting order it traded against
+    pub counterparty_id: Bits256,
+    pub counterparty: Identity,
+    pub trade_price: u64,
+    pub trade_size: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BookMicrostructure {
+    pub best_bid: Option<u64>,
+    pub best_ask: Option<u64>,
+    /// (best_bid + best_ask) / 2, rounded down
+    pub mid_price: Option<u64>,
+    /// Base amount resting on the top levels of each side
+    pub bid_depth: u64,
+    pub ask_depth: u64,
+    /// bid_depth / (bid_depth + ask_depth) in basis points
+    pub imbalance_bps: Option<u64>,
+}
+
+/// A freshly deployed market with the transaction that created it
+pub struct Deployment<A: fuels::accounts::Account = WalletUnlocked> {
+    pub market: SparkMarketContract<A>,
+    pub contract_id: Bech32ContractId,
+    pub tx_id: Bytes32,
+    /// Fee asset spent by the owner on the deployment
+    pub fee: u64,
+}
+
+/// The trades of the market over a window of the latest blocks
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TradeThroughput {
+    pub trades: usize,
+    /// Blocks in the window, including the tip
+    pub blocks: u32,
+}
+
+impl TradeThroughput {
+    /// trades / blocks, 0 for a window without trades
+    pub fn per_block(&self) -> f64 {
+        if self.trades == 0 || self.blocks == 0 {
+            return 0.0;
+        }
+        self.trades as f64 / self.blocks as f64
+    }
+}
+
+/// The resting orders of a book at one point in time, in the order they were requested
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BookSnapshot {
+    pub orders: Vec<(Bits256, Order)>,
+    /// The market's dust threshold when the snapshot was taken
+    pub dust_threshold: u64,
+}
+
+impl BookSnapshot {
+    pub fn order(&self, order_id: Bits256) -> Option<&Order> {
+        self.orders
+            .iter()
+            .find(|(id, _)| *id == order_id)
+            .map(|(_, order)| order)
+    }
+
+    /// The orders aggregated into price levels
+    pub fn depth(&self) -> BookDepth {
+        // Base amount resting at each price level
+        let mut bids = BTreeMap::new();
+        let mut asks = BTreeMap::new();
+        for (_, order) in &self.orders {
+            let side = match order.order_type {
+                OrderType::Buy => &mut bids,
+                OrderType::Sell => &mut asks,
+            };
+            *side.entry(order.price).or_insert(0_u64) += order.amount;
+        }
+
+        BookDepth {
+            bids: bids.into_iter().rev().collect(),
+            asks: asks.into_iter().collect(),
+        }
+    }
+
+    /// The book after `trades`, replayed in order the way the market fills orders: both sides
+    /// of a trade shrink by its size and pay out their share of the matcher fee, and an order
+    /// filled completely or left below the dust threshold leaves the book. Trades against
+    /// orders the snapshot does not hold are skipped
+    pub fn apply_trades(&self, trades: &[TradeOrderEvent]) -> BookSnapshot {
+        let mut orders = self.orders.clone();
+        for trade in trades {
+            for order_id in [trade.base_sell_order_id, trade.base_buy_order_id] {
+                let Some(index) = orders.iter().position(|(id, _)| *id == order_id) else {
+                    continue;
+                };
+                let order = &mut orders[index].1;
+                if trade.trade_size >= order.amount {
+                    orders.remove(index);
+                    continue;
+                }
+                order.matcher_fee -= (order.matcher_fee as u128 * trade.trade_size as u128
+                    / order.amount as u128) as u64;
+                order.amount -= trade.trade_size;
+                // The market cancels and refunds residuals too small to match
+                if order.amount < self.dust_threshold {
+                    orders.remove(index);
+                }
+            }
+        }
+        BookSnapshot {
+            orders,
+            dust_threshold: self.dust_threshold,
+        }
+    }
+}
+
+/// The price a trigger order is evaluated against
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReferencePrice {
+    /// The price of the market's most recent trade
+    #[default]
+    LastTrade,
+    /// The highest resting buy price among the book orders
+    BestBid,
+    /// The lowest resting sell price among the book orders
+    BestAsk,
+    /// (best_bid + best_ask) / 2, rounded down
+    Mid,
+}
+
+/// Which side of the trigger price the reference price must reach
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriggerCondition {
+    AtOrAbove,
+    AtOrBelow,
+}
+
+/// An order that is only opened once the reference price reaches `trigger_price`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TriggerOrder {
+    pub amount: u64,
+    pub order_type: OrderType,
+    pub price: u64,
+    pub trigger_price: u64,
+    pub condition: TriggerCondition,
+    /// Overrides the market's reference price set with `with_reference_price`
+    pub reference: Option<ReferencePrice>,
+}
+
+/// A call was not sent because its estimated fee exceeds the cap set with `with_max_fee`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeTooHigh {
+    pub estimated: u64,
+    pub limit: u64,
+}
+
+impl std::fmt::Display for FeeTooHigh {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "FeeTooHigh: the estimated fee {} exceeds the limit of {}",
+            self.estimated, self.limit
+        )
+    }
+}
+
+impl std::error::Error for FeeTooHigh {}
+
+/// A call was not sent because the confirmation set with `with_fee_confirmation` declined its
+/// estimated fee
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeDeclined {
+    pub estimated: u64,
+}
+
+impl std::fmt::Display for FeeDeclined {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "FeeDeclined: the call with an estimated fee of {} was not confirmed",
+            self.estimated
+        )
+    }
+}
+
+impl std::error::Error for FeeDeclined {}
+
+/// A zero amount where the market requires a positive one, caught before the call is sent
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ZeroAmount {
+    /// The call the amount was passed to, e.g. `withdraw`
+    pub operation: &'static str,
+}
+
+impl std::fmt::Display for ZeroAmount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ZeroAmount: {} needs an amount greater than zero",
+            self.operation
+        )
+    }
+}
+
+impl std::error::Error for ZeroAmount {}
+
+/// The market reverted a call because the caller does not own the order it acts on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Unauthorized {
+    pub order_id: Bits256,
+    pub caller: Identity,
+}
+
+impl std::fmt::Display for Unauthorized {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Unauthorized: order 0x{} is not owned by the caller {:?}",
+            self.order_id.0.map(|b| format!("{:02x}", b)).concat(),
+            self.caller
+        )
+    }
+}
+
+impl std::error::Error for Unauthorized {}
+
+/// Fails with `ZeroAmount` for deposits, withdrawals and order sizes of zero. Fees are not
+/// checked, a zero fee is a valid setting
+pub fn ensure_nonzero(amount: u64, operation: &'static str) -> Result<(), ZeroAmount> {
+    match amount {
+        0 => Err(ZeroAmount { operation }),
+        _ => Ok(()),
+    }
+}
+
+/// Computes the id `market` gives an order without calling it. The market id is hashed in first
+/// as a domain separator, so equal orders on different markets never share an id
+pub fn market_order_id(
+    market: ContractId,
+    order_type: OrderType,
+    owner: Identity,
+    price: u64,
+    order_height: u64,
+) -> Bits256 {
+    let owner_hash = match owner {
+        Identity::Address(address) => Hasher::default().chain(*market).chain([0]).chain(*address),
+        Identity::ContractId(contract_id) => Hasher::default()
+            .chain(*market)
+            .chain([1])
+            .chain(*contract_id),
+    };
+    let order_type = match order_type {
+        OrderType::Buy => 0,
+        OrderType::Sell => 1,
+    };
+
+    // Orders are always keyed as base asset orders
+    let id = Hasher::default()
+        .chain(owner_hash.finalize())
+        .chain([1, order_type])
+        .chain(price.to_be_bytes())
+        .chain(order_height.to_be_bytes())
+        .finalize();
+    Bits256(*id)
+}
+
+pub struct SparkMarketContract<A: fuels::accounts::Account = WalletUnlocked> {
+    instance: SparkMarket<A>,
+    /// The parameters of the orders opened by this session
+    opened_orders: Arc<Mutex<OrderLog>>,
+    /// Checks the estimated fee of a call before it is sent
+    fee_checks: FeeChecks,
+    /// What trigger orders without their own reference price are evaluated against
+    reference_price: ReferencePrice,
+}
+
+impl SparkMarketContract {
+    pub fn sdk_version() -> u32 {
+        let s_version = Self::sdk_str_version();
+        // Converts "0.1.1" string version to 257u32 (0x000101)
+        let version = s_version.split('.').collect::<Vec<&str>>();
+        let len = version.len();
+        version
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| x.parse::<u32>().unwrap() << (8 * (len - i - 1)))
+            .collect::<Vec<u32>>()
+            .iter()
+            .sum()
+    }
+
+    pub fn sdk_str_version() -> String {
+        env!("CARGO_PKG_VERSION").into()
+    }
+}
+
+impl<A: fuels::accounts::Account> SparkMarketContract<A> {
+    pub async fn deploy(
+        base_asset: AssetId,
+        base_decimals: u32,
+        quote_asset: AssetId,
+        quote_decimals: u32,
+        owner: A,
+        price_decimals: u32,
+        version: u32,
+    ) -> anyhow::Result<Self> {
+        let mut rng = rand::thread_rng();
+        let salt = rng.gen::<[u8; 32]>();
+
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let storage_configuration = StorageConfiguration::default()
+            .add_slot_overrides_from_file(root.join(MARKET_CONTRACT_STORAGE_PATH));
+
+        let configurables = SparkMarketConfigurables::default()
+            .with_BASE_ASSET(base_asset)
+            .unwrap()
+            .with_BASE_ASSET_DECIMALS(base_decimals)
+            .unwrap()
+            .with_QUOTE_ASSET(quote_asset)
+            .unwrap()
+            .with_QUOTE_ASSET_DECIMALS(quote_decimals)
+            .unwrap()
+            .with_OWNER(owner.address().into())
+            .unwrap()
+            .with_PRICE_DECIMALS(price_decimals)
+            .unwrap()
+            .with_VERSION(version)
+            .unwrap();
+
+        let contract_configuration = LoadConfiguration::default()
+            .with_storage_configuration(storage_configuration?)
+            .with_configurables(configurables);
+
+        let contract_id = Contract::load_from(
+            root.join(MARKET_CONTRACT_BINARY_PATH),
+            contract_configuration,
+        )?
+        .with_salt(salt)
+        .deploy(&owner, TxPolicies::default())
+        .await?;
+
+        let market = SparkMarket::new(contract_id.clone(), owner.clone());
+
+        Ok(Self {
+            instance: market,
+            opened_orders: Default::default(),
+            fee_checks: FeeChecks::default(),
+            reference_price: ReferencePrice::default(),
+        })
+    }
+
+    /// Deploys like `deploy` and also reports the deployment transaction and its fee
+    pub async fn deploy_with_receipt(
+        base_asset: AssetId,
+        base_decimals: u32,
+        quote_asset: AssetId,
+        quote_decimals: u32,
+        owner: A,
+        price_decimals: u32,
+        version: u32,
+    ) -> anyhow::Result<Deployment<A>> {
+        let provider = owner.try_provider()?.clone();
+        let fee_asset = *provider.base_asset_id();
+        let from_block = provider.latest_block_height().await? + 1;
+        let balance = owner.get_asset_balance(&fee_asset).await?;
+
+        let market = Self::deploy(
+            base_asset,
+            base_decimals,
+            quote_asset,
+            quote_decimals,
+            owner.clone(),
+            price_decimals,
+            version,
+        )
+        .await?;
+
+        let fee = balance - owner.get_asset_balance(&fee_asset).await?;
+        let contract_id = market.contract_id().clone();
+        let target = ContractId::from(&contract_id);
+
+        // The deployment is the create transaction committed since `from_block` whose output is
+        // the new contract
+        let to_block = provider.latest_block_height().await?;
+        for height in from_block..=to_block {
+            let Some(block) = provider.block_by_height(height.into()).await? else {
+                break;
+            };
+            for tx_id in block.transactions {
+                let Some(response) = provider.get_transaction_by_id(&tx_id).await? else {
+                    continue;
+                };
+                let TransactionType::Create(create) = response.transaction else {
+                    continue;
+                };
+                let creates_market = create.outputs().iter().any(|output| {
+                    matches!(output, Output::ContractCreated { contract_id, .. } if *contract_id == target)
+                });
+                if creates_market {
+                    return Ok(Deployment {
+                        market,
+                        contract_id,
+                        tx_id,
+                        fee,
+                    });
+                }
+            }
+        }
+
+        anyhow::bail!(
+            "Deployment transaction of contract {} not found",
+            contract_id
+        )
+    }
+
+    pub async fn new(contract_id: ContractId, wallet: A) -> Self {
+        let _self = Self {
+            instance: SparkMarket::new(contract_id, wallet),
+            opened_orders: Default::default(),
+            fee_checks: FeeChecks::default(),
+            reference_price: ReferencePrice::default(),
+        };
+        assert!(
+            _self.contract_version().await.unwrap() & 0xFF0000
+                == SparkMarketContract::sdk_version() & 0xFF0000,
+            "Market contract version mismatch with SDK version"
+        );
+        _self
+    }
+
+    pub fn get_instance(&self) -> &SparkMarket<A> {
+        &self.instance
+    }
+
+    pub async fn with_account(&self, account: &A) -> anyhow::Result<Self> {
+        Ok(Self {
+            instance: self.instance.clone().with_account(account.clone()),
+            opened_orders: self.opened_orders.clone(),
+            fee_checks: self.fee_checks.clone(),
+            reference_price: self.reference_price,
+        })
+    }
+
+    /// Caps the fee of the calls sent through this instance, `None` removes the cap
+    pub fn with_max_fee(mut self, max_fee: Option<u64>) -> Self {
+        self.fee_checks.max_fee = max_fee;
+        self
+    }
+
+    /// Asks `confirm` with the estimated fee of each call before it is sent, a call it declines
+    /// fails with `FeeDeclined`. `None` sends calls without asking
+    pub fn with_fee_confirmation(mut self, confirm: Option<FeeConfirmation>) -> Self {
+        self.fee_checks.confirm = confirm;
+        self
+    }
+
+    /// Bounds the opened-order log shared with the handles made through `with_account` to
+    /// `capacity` orders, dropping the least recently opened or recalled beyond it
+    pub fn with_order_log_capacity(self, capacity: usize) -> Self {
+        self.opened_orders.lock().unwrap().set_capacity(capacity);
+        self
+    }
+
+    /// Sets the reference price of trigger orders which do not choose their own
+    pub fn with_reference_price(mut self, reference_price: ReferencePrice) -> Self {
+        self.reference_price = reference_price;
+        self
+    }
+
+    pub fn id(&self) -> Bytes32 {
+        self.instance.contract_id().hash
+    }
+
+    pub fn contract_id(&self) -> &Bech32ContractId {
+        self.instance.contract_id()
+    }
+
+    pub async fn contract_version(&self) -> anyhow::Result<u32> {
+        Ok(self.config().await?.version)
+    }
+
+    pub async fn contract_str_version(&self) -> anyhow::Result<String> {
+        let version = self.contract_version().await?;
+        Ok(format!(
+            "{}.{}.{}",
+            (version & 0xFF0000) >> 16,
+            (version & 0xFF00) >> 8,
+            version & 0xFF
+        ))
+    }
+
+    pub async fn deposit(&self, amount: u64, asset: AssetId) -> anyhow::Result<CallResponse<()>> {
+        ensure_nonzero(amount, "deposit")?;
+        call_with_funds(
+            self.instance.methods().deposit(),
+            asset,
+            amount,
+            0,
+            &self.fee_checks,
+        )
+        .await
+    }
+
+    pub async fn withdraw(
+        &self,
+        amount: u64,
+        asset_type: AssetType,
+    ) -> anyhow::Result<CallResponse<()>> {
+        ensure_nonzero(amount, "withdraw")?;
+        call_with_funds(
+            self.instance.methods().withdraw(amount, asset_type),
+            AssetId::default(),
+            0,
+            1,
+            &self.fee_checks,
+        )
+        .await
+    }
+
+    pub async fn open_order(
+        &self,
+        amount: u64,
+        order_type: OrderType,
+        price: u64,
+    ) -> anyhow::Result<CallResponse<OpenedOrder>> {
+        ensure_nonzero(amount, "open_order")?;
+        let user = Identity::Address(self.instance.account().address().into());
+
+        // The account is read in the same transaction, so nothing lands between it and the lock
+        let multi_call = CallHandler::new_multi_call(self.instance.account())
+            .add_call(self.instance.methods().account(user))
+            .add_call(
+                self.instance
+                    .methods()
+                    .open_order(amount, order_type.clone(), price),
+            );
+        check_estimated_fee(
+            multi_call.estimate_transaction_cost(None, None),
+            &self.fee_checks,
+        )
+        .await?;
+        let response = multi_call
+            .call::<(Account, Bits256)>()
+            .await
+            .map_err(|error| map_price_error(error.into()))?;
+        let (before, order_id) = response.value.clone();
+
+        self.log_opened_order(order_id, order_type, price, amount, None);
+        let (base_deposited, quote_deposited) = liquid_taken(
+            &before.liquid,
+            (0, 0),
+            &response.decode_logs_with_type::<OpenOrderEvent>()?,
+        )?;
+
+        Ok(CallResponse {
+            value: OpenedOrder {
+                order_id,
+                base_deposited,
+                quote_deposited,
+            },
+            receipts: response.receipts,
+            gas_used: response.gas_used,
+            log_decoder: response.log_decoder,
+            tx_id: response.tx_id,
+        })
+    }
+
+    /// Deposits exactly what `required_deposit` asks for and opens the order in the same transaction
+    pub async fn deposit_and_open(
+        &self,
+        order_type: OrderType,
+        price: u64,
+        amount: u64,
+    ) -> anyhow::Result<CallResponse<OpenedOrder>> {
+        ensure_nonzero(amount, "deposit_and_open")?;
+        let (asset, deposit) = self
+            .required_deposit(amount, order_type.clone(), price)
+            .await?;
+        let (base_attached, quote_attached) = match order_type {
+            OrderType::Sell => (deposit, 0),
+            OrderType::Buy => (0, deposit),
+        };
+        let user = Identity::Address(self.instance.account().address().into());
+
+        let call_params = CallParameters::default()
+            .with_asset_id(asset)
+            .with_amount(deposit);
+        // The account is read in the same transaction, so nothing lands between it and the lock
+        let multi_call = CallHandler::new_multi_call(self.instance.account())
+            .add_call(self.instance.methods().account(user))
+            .add_call(self.instance.methods().deposit().call_params(call_params)?)
+            .add_call(
+                self.instance
+                    .methods()
+                    .open_order(amount, order_type.clone(), price),
+            );
+        check_estimated_fee(
+            multi_call.estimate_transaction_cost(None, None),
+            &self.fee_checks,
+        )
+        .await?;
+        let response = multi_call
+            .call::<(Account, (), Bits256)>()
+            .await
+            .map_err(|error| map_price_error(error.into()))?;
+        let (before, _, order_id) = response.value.clone();
+
+        self.log_opened_order(order_id, order_type, price, amount, None);
+
+        // The deposit lands in the liquid balance before the lock takes from it
+        let (base_deposited, quote_deposited) = liquid_taken(
+            &before.liquid,
+            (base_attached, quote_attached),
+            &response.decode_logs_with_type::<OpenOrderEvent>()?,
+        )?;
+
+        Ok(CallResponse {
+            value: OpenedOrder {
+                order_id,
+                base_deposited,
+                quote_deposited,
+            },
+            receipts: response.receipts,
+            gas_used: response.gas_used,
+            log_decoder: response.log_decoder,
+            tx_id: response.tx_id,
+        })
+    }
+
+    /// Opens every `(amount, order_type, price)` from the deposited balance, as many per
+    /// transaction as a multicall allows, and returns their ids in the order given
+    pub async fn open_orders(
+        &self,
+        orders: Vec<(u64, OrderType, u64)>,
+    ) -> anyhow::Result<Vec<Bits256>> {
+        for (amount, _, _) in &orders {
+            ensure_nonzero(*amount, "open_orders")?;
+        }
+        let mut order_ids = Vec::with_capacity(orders.len());
+        for chunk in orders.chunks(MAX_BATCH_SIZE) {
+            let mut multi_call = CallHandler::new_multi_call(self.instance.account());
+            for (amount, order_type, price) in chunk {
+                multi_call = multi_call.add_call(self.instance.methods().open_order(
+                    *amount,
+                    order_type.clone(),
+                    *price,
+                ));
+            }
+            check_estimated_fee(
+                multi_call.estimate_transaction_cost(None, None),
+                &self.fee_checks,
+            )
+            .await?;
+            let outputs = multi_call
+                .call::<CallOutputs>()
+                .await
+                .map_err(|error| map_price_error(error.into()))?
+                .value;
+
+            for ((amount, order_type, price), token) in chunk.iter().zip(outputs.0) {
+                let order_id = Bits256::from_token(token)?;
+                self.log_opened_order(order_id, order_type.clone(), *price, *amount, None);
+                order_ids.push(order_id);
+            }
+        }
+        Ok(order_ids)
+    }
+
+    /// Opens an order funded from `source`, like `open_order` for the deposited balance and
+    /// `deposit_and_open` for attached funds
+    pub async fn open_order_from(
+        &self,
+        amount: u64,
+        order_type: OrderType,
+        price: u64,
+        source: DepositSource,
+    ) -> anyhow::Result<CallResponse<OpenedOrder>> {
+        match source {
+            DepositSource::Balance => self.open_order(amount, order_type, price).await,
+            DepositSource::Attached => self.deposit_and_open(order_type, price, amount).await,
+        }
+    }
+
+    pub async fn open_order_post_only(
+        &self,
+        amount: u64,
+        order_type: OrderType,
+        price: u64,
+        orders: Vec<Bits256>,
+    ) -> anyhow::Result<CallResponse<Bits256>> {
+        ensure_nonzero(amount, "open_order_post_only")?;
+        let response = call_with_funds(
+            self.instance
+                .methods()
+                .open_order_post_only(amount, order_type.clone(), price, orders),
+            AssetId::default(),
+            0,
+            0,
+            &self.fee_checks,
+        )
+        .await
+        .map_err(map_price_error)?;
+
+        self.log_opened_order(response.value, order_type, price, amount, None);
+        Ok(response)
+    }
+
+    pub async fn open_order_with_deadline(
+        &self,
+        amount: u64,
+        order_type: OrderType,
+        price: u64,
+        deadline: u32,
+        keeper_reward: u64,
+    ) -> anyhow::Result<CallResponse<Bits256>> {
+        ensure_nonzero(amount, "open_order_with_deadline")?;
+        let response = call_with_funds(
+            self.instance.methods().open_order_with_deadline(
+                amount,
+                order_type.clone(),
+                price,
+                deadline,
+                keeper_reward,
+            ),
+            AssetId::default(),
+            0,
+            0,
+            &self.fee_checks,
+        )
+        .await
+        .map_err(map_price_error)?;
+
+        self.log_opened_order(response.value, order_type, price, amount, Some(deadline));
+        Ok(response)
+    }
+
+    pub async fn cancel_order(&self, order_id: Bits256) -> anyhow::Result<CallResponse<()>> {
+        call_with_funds(
+            self.instance.methods().cancel_order(order_id),
+            AssetId::default(),
+            0,
+            1,
+            &self.fee_checks,
+        )
+        .await
+        .map_err(|error| {
+            // Only the owner may cancel, name the order and the caller the revert is about
+            if error.to_string().contains("Unauthorized") {
+                let caller = Identity::Address(self.instance.account().address().into());
+                error.context(Unauthorized { order_id, caller })
+            } else {
+                error
+            }
+        })
+    }
+
+    /// Cancels `order_id` and opens an order of the same type for `new_size` at `new_price` in
+    /// one transaction. The refund lands in the liquid balance the new lock takes from, so only
+    /// the part of the new lock that the liquid balance and refund do not cover is attached
+    pub async fn cancel_and_reopen(
+        &self,
+        order_id: Bits256,
+        new_price: u64,
+        new_size: u64,
+    ) -> anyhow::Result<CallResponse<Bits256>> {
+        ensure_nonzero(new_size, "cancel_and_reopen")?;
+        let order = self.order(order_id).await?.value.ok_or_else(|| {
+            let id = order_id.0.map(|b| format!("{:02x}", b)).concat();
+            anyhow::anyhow!("Order 0x{} not found", id)
+        })?;
+        let order_type = order.order_type.clone();
+        let (asset, required) = self
+            .required_deposit(new_size, order_type.clone(), new_price)
+            .await?;
+
+        // What the cancel unlocks, computed as the market locks it
+        let unlocked = expected_locked(&[order], 0, &self.config().await?)?;
+        let refund = match order_type {
+            OrderType::Sell => unlocked.base,
+            OrderType::Buy => unlocked.quote,
+        };
+        let user = Identity::Address(self.instance.account().address().into());
+        let liquid = self.account(user).await?.value.liquid;
+        let liquid = match order_type {
+            OrderType::Sell => liquid.base,
+            OrderType::Buy => liquid.quote,
+        };
+        let shortfall = required.saturating_sub(liquid.saturating_add(refund));
+
+        let mut multi_call = CallHandler::new_multi_call(self.instance.account())
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+            .add_call(self.instance.methods().cancel_order(order_id));
+        if shortfall > 0 {
+            let call_params = CallParameters::default()
+                .with_asset_id(asset)
+                .with_amount(shortfall);
+            multi_call =
+                multi_call.add_call(self.instance.methods().deposit().call_params(call_params)?);
+        }
+        let multi_call = multi_call.add_call(self.instance.methods().open_order(
+            new_size,
+            order_type.clone(),
+            new_price,
+        ));
+        check_estimated_fee(
+            multi_call.estimate_transaction_cost(None, None),
+            &self.fee_checks,
+        )
+        .await?;
+        let response = multi_call
+            .call::<CallOutputs>()
+            .await
+            .map_err(|error| map_price_error(error.into()))?;
+        let token = response
+            .value
+            .0
+            .last()
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Missing the reopened order id"))?;
+        let new_order_id = Bits256::from_token(token)?;
+
+        self.log_opened_order(new_order_id, order_type, new_price, new_size, None);
+
+        Ok(CallResponse {
+            value: new_order_id,
+            receipts: response.receipts,
+            gas_used: response.gas_used,
+            log_decoder: response.log_decoder,
+            tx_id: response.tx_id,
+        })
+    }
+
+    pub async fn cancel_by_params(
+        &self,
+        order_type: OrderType,
+        price: u64,
+        block_height: u32,
+        order_height: u64,
+    ) -> anyhow::Result<CallResponse<()>> {
+        let owner = Identity::Address(self.instance.account().address().into());
+        let order_id = self.order_id_offline(order_type.clone(), owner, price, order_height);
+
+        // The id does not cover the block height, so check it against the stored order
+        match self.order(order_id).await?.value {
+            Some(order) if order.order_type == order_type && order.block_height == block_height => {
+                self.cancel_order(order_id).await
+            }
+            _ => anyhow::bail!("No open order of the caller matches the given parameters"),
+        }
+    }
+
+    /// Cancels the `count` open orders of the caller with the lowest order height, as many per
+    /// transaction as a multicall allows, and returns their ids oldest first
+    pub async fn cancel_oldest(&self, count: usize) -> anyhow::Result<Vec<Bits256>> {
+        // Only the owner may cancel an order, so only the caller's orders can be cancelled
+        let user = Identity::Address(self.instance.account().address().into());
+        let mut orders = Vec::new();
+        for order_id in self.user_orders(user).await?.value {
+            if let Some(order) = self.order(order_id).await?.value {
+                orders.push((order.order_height, order_id));
+            }
+        }
+        orders.sort_by_key(|order| order.0);
+        let oldest: Vec<Bits256> = orders
+            .into_iter()
+            .take(count)
+            .map(|order| order.1)
+            .collect();
+
+        for chunk in oldest.chunks(MAX_BATCH_SIZE) {
+            let mut multi_call = CallHandler::new_multi_call(self.instance.account())
+                .with_variable_output_policy(VariableOutputPolicy::Exactly(chunk.len()));
+            for order_id in chunk {
+                multi_call = multi_call.add_call(self.instance.methods().cancel_order(*order_id));
+            }
+            check_estimated_fee(
+                multi_call.estimate_transaction_cost(None, None),
+                &self.fee_checks,
+            )
+            .await?;
+            multi_call.call::<CallOutputs>().await?;
+        }
+        Ok(oldest)
+    }
+
+    pub async fn cancel_expired(&self, order_id: Bits256) -> anyhow::Result<CallResponse<()>> {
+        call_with_funds(
+            self.instance.methods().cancel_expired(order_id),
+            AssetId::default(),
+            0,
+            1,
+            &self.fee_checks,
+        )
+        .await
+    }
+
+    pub async fn claim_keeper_rewards(&self) -> anyhow::Result<CallResponse<()>> {
+        call_with_funds(
+            self.instance.methods().claim_keeper_rewards(),
+            AssetId::default(),
+            0,
+            1,
+            &self.fee_checks,
+        )
+        .await
+    }
+
+    /// The `pairs` whose expected matcher reward exceeds the estimated fee of matching them,
+    /// most profitable first. `fee_to_quote` converts a fee in the fee asset into quote units;
+    /// pairs that do not cross or whose orders are gone are left out
+    pub async fn profitable_matches(
+        &self,
+        pairs: Vec<(Bits256, Bits256)>,
+        fee_to_quote: impl Fn(u64) -> u64,
+    ) -> anyhow::Result<Vec<ProfitableMatch>> {
+        let mut profitable = Vec::new();
+        for (order_id0, order_id1) in pairs {
+            let (Some(order0), Some(order1)) = (
+                self.order(order_id0).await?.value,
+                self.order(order_id1).await?.value,
+            ) else {
+                continue;
+            };
+            let Some(reward) = expected_matcher_reward(&order0, &order1) else {
+                continue;
+            };
+
+            let fee = self
+                .instance
+                .methods()
+                .match_order_pair(order_id0, order_id1)
+                .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+                .estimate_transaction_cost(None, None)
+                .await?
+                .total_fee;
+            let cost = fee_to_quote(fee);
+            if reward > cost {
+                profitable.push(ProfitableMatch {
+                    order_ids: (order_id0, order_id1),
+                    reward,
+                    cost,
+                    profit: reward - cost,
+                });
+            }
+        }
+
+        // Ties keep the order they were given in
+        profitable.sort_by(|a, b| b.profit.cmp(&a.profit));
+        Ok(profitable)
+    }
+
+    pub async fn match_order_pair(
+        &self,
+        order_id0: Bits256,
+        order_id1: Bits256,
+    ) -> anyhow::Result<CallResponse<()>> {
+        call_with_funds(
+            self.instance
+                .methods()
+                .match_order_pair(order_id0, order_id1),
+            AssetId::default(),
+            0,
+            1,
+            &self.fee_checks,
+        )
+        .await
+    }
+
+    /// Matches the pair like `match_order_pair` and returns the trade it executed
+    pub async fn match_order_pair_execution(
+        &self,
+        order_id0: Bits256,
+        order_id1: Bits256,
+    ) -> anyhow::Result<MatchExecution> {
+        let response = self.match_order_pair(order_id0, order_id1).await?;
+        let trade = response
+            .decode_logs_with_type::<TradeOrderEvent>()?
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("No trade logged by the match"))?;
+        Ok(MatchExecution {
+            sell_order_id: trade.base_sell_order_id,
+            buy_order_id: trade.base_buy_order_id,
+            trade_size: trade.trade_size,
+            execution_price: trade.trade_price,
+        })
+    }
+
+    pub async fn match_order_many(&self, orders: Vec<Bits256>) -> anyhow::Result<CallResponse<()>> {
+        call_with_funds(
+            self.instance.methods().match_order_many(orders),
+            AssetId::default(),
+            0,
+            1,
+            &self.fee_checks,
+        )
+        .await
+    }
+
+    pub async fn match_orders(
+        &self,
+        orders: Vec<Bits256>,
+        check_first: bool,
+    ) -> anyhow::Result<MatchOutcome> {
+        // An order can not match itself, and the market would only reject it with a bare CantMatch
+        if let Some(id) = orders
+            .iter()
+            .enumerate()
+            .find_map(|(index, id)| orders[..index].contains(id).then_some(id))
+        {
+            anyhow::bail!("SameOrder: {:?} is given more than once", id);
+        }
+
+        if check_first {
+            // Orders filled or cancelled since discovery are skipped by the market, which
+            // reverts with CantMatchMany once no crossing pair remains
+            let simulation = self
+                .instance
+                .methods()
+                .match_order_many(orders.clone())
+                .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+                .simulate(Execution::StateReadOnly)
+                .await;
+            if let Err(error) = simulation {
+                if error.to_string().contains("CantMatchMany") {
+                    return Ok(MatchOutcome::NoLongerCrossing);
+                }
+                return Err(error.into());
+            }
+        }
+
+        Ok(MatchOutcome::Submitted(
+            self.match_order_many(orders).await?,
+        ))
+    }
+
+    pub async fn match_order_pairs_concurrent(
+        &self,
+        pairs: Vec<(Bits256, Bits256)>,
+        max_in_flight: usize,
+    ) -> anyhow::Result<Vec<anyhow::Result<CallResponse<()>>>> {
+        if max_in_flight == 0 {
+            anyhow::bail!("Max in flight must be greater than zero");
+        }
+
+        // Coins are only marked as spent once a transaction is submitted, so building and
+        // submitting is serialized while the commits are awaited concurrently
+        let submitting = AsyncMutex::new(());
+        Ok(run_bounded(pairs, max_in_flight, |(order_id0, order_id1)| {
+            let submitting = &submitting;
+            async move {
+                let submitted = {
+                    let _guard = submitting.lock().await;
+                    let call = self
+                        .instance
+                        .methods()
+                        .match_order_pair(order_id0, order_id1)
+                        .with_variable_output_policy(VariableOutputPolicy::Exactly(1));
+                    // Checked under the lock, so confirmations are asked one at a time
+                    check_fee(&call, &self.fee_checks).await?;
+                    call.submit().await?
+                };
+                Ok(submitted.response().await?)
+            }
+        })
+        .await)
+    }
+
+    pub async fn fulfill_many(
+        &self,
+        amount: u64,
+        order_type: OrderType,
+        limit_type: LimitType,
+        price: u64,
+        slippage: u64,
+        orders: Vec<Bits256>,
+    ) -> anyhow::Result<CallResponse<Bits256>> {
+        call_with_funds(
+            self.instance
+                .methods()
+                .fulfill_order_many(amount, order_type, limit_type, price, slippage, orders),
+            AssetId::default(),
+            0,
+            1,
+            &self.fee_checks,
+        )
+        .await
+        .map_err(map_price_error)
+    }
+
+    /// Like `fulfill_many`, but the sweep also stops at whichever of `limits` is reached first.
+    /// The fills are planned from the current state of `orders` the way the market walks them,
+    /// and only the planned amount and the orders it consumes are sent
+    #[allow(clippy::too_many_arguments)]
+    pub async fn fulfill_many_with_limits(
+        &self,
+        amount: u64,
+        order_type: OrderType,
+        limit_type: LimitType,
+        price: u64,
+        slippage: u64,
+        orders: Vec<Bits256>,
+        limits: FulfillLimits,
+    ) -> anyhow::Result<CallResponse<BoundedFill>> {
+        if order_type == OrderType::Sell
+            && (limits.max_quote_spend.is_some() || limits.min_base_received.is_some())
+        {
+            anyhow::bail!("max_quote_spend and min_base_received only bound buy orders");
+        }
+
+        let config = self.config().await?;
+        let maker_price_execution = self.maker_price_execution().await?.value;
+        let max_distance = mul_div(price, slippage, HUNDRED_PERCENT, RoundingMode::Floor)?;
+
+        let mut remaining = amount;
+        let mut quote = 0;
+        let mut consumed = Vec::new();
+        let mut stop = FulfillStop::PriceLimit;
+        for order_id in orders {
+            let Some(order) = self.order(order_id).await?.value else {
+                continue;
+            };
+            // The market skips orders on the same side, outside the slippage or not crossing. A
+            // resting buy is older than the taker, so it sets the price under maker-price execution
+            let (crosses, trade_price) = match order_type {
+                OrderType::Buy => (order.price <= price, order.price),
+                OrderType::Sell if maker_price_execution => (order.price >= price, order.price),
+                OrderType::Sell => (order.price >= price, price),
+            };
+            if order.order_type == order_type
+                || !crosses
+                || price.abs_diff(order.price) > max_distance
+            {
+                continue;
+            }
+            if limits.max_orders_to_consume == Some(consumed.len()) {
+                stop = FulfillStop::MaxOrdersToConsume;
+                break;
+            }
+
+            let mut fill = remaining.min(order.amount);
+            let mut trade_quote = quote_of_base_amount(
+                fill,
+                trade_price,
+                config.base_decimals,
+                config.quote_decimals,
+                config.price_decimals,
+                RoundingMode::Floor,
+            )?;
+            if let Some(max_quote_spend) = limits.max_quote_spend {
+                if quote + trade_quote > max_quote_spend {
+                    // The most base the rest of the budget buys at this order's price
+                    fill = base_of_quote_amount(
+                        max_quote_spend - quote,
+                        trade_price,
+                        config.base_decimals,
+                        config.quote_decimals,
+                        config.price_decimals,
+                        RoundingMode::Floor,
+                    )?;
+                    trade_quote = quote_of_base_amount(
+                        fill,
+                        trade_price,
+                        config.base_decimals,
+                        config.quote_decimals,
+                        config.price_decimals,
+                        RoundingMode::Floor,
+                    )?;
+                    stop = FulfillStop::MaxQuoteSpend;
+                }
+            }
+            if fill > 0 {
+                consumed.push(order_id);
+                remaining -= fill;
+                quote += trade_quote;
+            }
+            if stop == FulfillStop::MaxQuoteSpend {
+                break;
+            }
+            if remaining == 0 {
+                stop = FulfillStop::Filled;
+                break;
+            }
+        }
+
+        let base_filled = amount - remaining;
+        if let Some(min_base_received) = limits.min_base_received {
+            if base_filled < min_base_received {
+                anyhow::bail!(
+                    "MinBaseReceived: the sweep stops at {:?} with {} base, less than {}",
+                    stop,
+                    base_filled,
+                    min_base_received
+                );
+            }
+        }
+        if base_filled == 0 {
+            anyhow::bail!("Nothing to fulfill, the sweep stops at {:?}", stop);
+        }
+        if stop != FulfillStop::Filled && limit_type == LimitType::FOK {
+            anyhow::bail!("CantFulfillFOK: the sweep stops at {:?}", stop);
+        }
+
+        let response = self
+            .fulfill_many(
+                base_filled,
+                order_type,
+                limit_type,
+                price,
+                slippage,
+                consumed,
+            )
+            .await?;
+
+        Ok(CallResponse {
+            value: BoundedFill {
+                order_id: response.value,
+                stop,
+                base_filled,
+                quote,
+            },
+            receipts: response.receipts,
+            gas_used: response.gas_used,
+            log_decoder: response.log_decoder,
+            tx_id: response.tx_id,
+        })
+    }
+
+    pub async fn replay(
+        &self,
+        call: &MarketCall,
+        gas_multiplier: f64,
+    ) -> anyhow::Result<Option<Bits256>> {
+        let methods = self.instance.methods();
+        let variable_outputs = VariableOutputPolicy::Exactly(1);
+
+        Ok(match call.clone() {
+            MarketCall::OpenOrder {
+                amount,
+                order_type,
+                price,
+            } => {
+                let call = methods.open_order(amount, order_type, price);
+                Some(
+                    call_with_funds(
+                        with_gas_multiplier(call, gas_multiplier).await?,
+                        AssetId::default(),
+                        0,
+                        0,
+                        &self.fee_checks,
+                    )
+                    .await?
+                    .value,
+                )
+            }
+            MarketCall::CancelOrder { order_id } => {
+                let call = methods
+                    .cancel_order(order_id)
+                    .with_variable_output_policy(variable_outputs);
+                call_with_funds(
+                    with_gas_multiplier(call, gas_multiplier).await?,
+                    AssetId::default(),
+                    0,
+                    1,
+                    &self.fee_checks,
+                )
+                .await?;
+                None
+            }
+            MarketCall::MatchOrderPair {
+                order_id0,
+                order_id1,
+            } => {
+                let call = methods
+                    .match_order_pair(order_id0, order_id1)
+                    .with_variable_output_policy(variable_outputs);
+                call_with_funds(
+                    with_gas_multiplier(call, gas_multiplier).await?,
+                    AssetId::default(),
+                    0,
+                    1,
+                    &self.fee_checks,
+                )
+                .await?;
+                None
+            }
+            MarketCall::MatchOrderMany { orders } => {
+                let call = methods
+                    .match_order_many(orders)
+                    .with_variable_output_policy(variable_outputs);
+                call_with_funds(
+                    with_gas_multiplier(call, gas_multiplier).await?,
+                    AssetId::default(),
+                    0,
+                    1,
+                    &self.fee_checks,
+                )
+                .await?;
+                None
+            }
+            MarketCall::FulfillMany {
+                amount,
+                order_type,
+                limit_type,
+                price,
+                slippage,
+                orders,
+            } => {
+                let call = methods
+                    .fulfill_order_many(amount, order_type, limit_type, price, slippage, orders)
+                    .with_variable_output_policy(variable_outputs);
+                Some(
+                    call_with_funds(
+                        with_gas_multiplier(call, gas_multiplier).await?,
+                        AssetId::default(),
+                        0,
+                        1,
+                        &self.fee_checks,
+                    )
+                    .await?
+                    .value,
+                )
+            }
+        })
+    }
+
+    pub async fn set_protocol_fee(
+        &self,
+        fee: Vec<ProtocolFee>,
+    ) -> anyhow::Result<CallResponse<()>> {
+        call_with_funds(
+            self.instance.methods().set_protocol_fee(fee),
+            AssetId::default(),
+            0,
+            0,
+            &self.fee_checks,
+        )
+        .await
+    }
+
+    pub async fn set_matcher_fee(&self, amount: u64) -> anyhow::Result<CallResponse<()>> {
+        call_with_funds(
+            self.instance.methods().set_matcher_fee(amount),
+            AssetId::default(),
+            0,
+            0,
+            &self.fee_checks,
+        )
+        .await
+    }
+
+    /// Sets the taker fee of the protocol fee tier starting at `volume_threshold`, keeping the
+    /// maker fees and every other tier
+    pub async fn set_taker_fee(
+        &self,
+        taker_fee: u64,
+        volume_threshold: u64,
+    ) -> anyhow::Result<CallResponse<()>> {
+        let mut protocol_fee = self.protocol_fee().await?.value;
+
+        if protocol_fee.is_empty() && volume_threshold == 0 {
+            protocol_fee.push(ProtocolFee {
+                maker_fee: 0,
+                taker_fee,
+                volume_threshold: 0,
+            });
+        } else {
+            let tier = protocol_fee
+                .iter_mut()
+                .find(|fee| fee.volume_threshold == volume_threshold)
+                .ok_or_else(|| {
+                    anyhow::anyhow!("No protocol fee tier starts at volume {}", volume_threshold)
+                })?;
+            tier.taker_fee = taker_fee;
+        }
+
+        self.set_protocol_fee(protocol_fee).await
+    }
+
+    pub async fn set_dust_threshold(&self, amount: u64) -> anyhow::Result<CallResponse<()>> {
+        call_with_funds(
+            self.instance.methods().set_dust_threshold(amount),
+            AssetId::default(),
+            0,
+            0,
+            &self.fee_checks,
+        )
+        .await
+    }
+
+    pub async fn set_tick_size(&self, tick: u64) -> anyhow::Result<CallResponse<()>> {
+        call_with_funds(
+            self.instance.methods().set_tick_size(tick),
+            AssetId::default(),
+            0,
+            0,
+            &self.fee_checks,
+        )
+        .await
+    }
+
+    pub async fn set_maker_price_execution(
+        &self,
+        enabled: bool,
+    ) -> anyhow::Result<CallResponse<()>> {
+        call_with_funds(
+            self.instance.methods().set_maker_price_execution(enabled),
+            AssetId::default(),
+            0,
+            0,
+            &self.fee_checks,
+        )
+        .await
+    }
+
+    pub async fn set_max_price_deviation_bps(&self, bps: u64) -> anyhow::Result<CallResponse<()>> {
+        call_with_funds(
+            self.instance.methods().set_max_price_deviation_bps(bps),
+            AssetId::default(),
+            0,
+            0,
+            &self.fee_checks,
+        )
+        .await
+    }
+
+    pub async fn add_fee_exempt(&self, user: Identity) -> anyhow::Result<CallResponse<()>> {
+        call_with_funds(
+            self.instance.methods().add_fee_exempt(user),
+            AssetId::default(),
+            0,
+            0,
+            &self.fee_checks,
+        )
+        .await
+    }
+
+    pub async fn remove_fee_exempt(&self, user: Identity) -> anyhow::Result<CallResponse<()>> {
+        call_with_funds(
+            self.instance.methods().remove_fee_exempt(user),
+            AssetId::default(),
+            0,
+            0,
+            &self.fee_checks,
+        )
+        .await
+    }
+
+    pub async fn account(&self, user: Identity) -> anyhow::Result<CallResponse<Account>> {
+        Ok(self
+            .instance
+            .methods()
+            .account(user)
+            .simulate(Execution::StateReadOnly)
+            .await?)
+    }
+
+    /// Base and quote the contract holds on-chain, read from the node rather than the accounts
+    /// the market keeps, so the two can be compared in a solvency audit
+    pub async fn contract_balances(&self) -> anyhow::Result<Balance> {
+        let config = self.config().await?;
+        let provider = self.instance.account().try_provider()?.clone();
+        let contract_id = self.contract_id();
+        Ok(Balance {
+            base: provider
+                .get_contract_asset_balance(contract_id, config.base_asset)
+                .await?,
+            quote: provider
+                .get_contract_asset_balance(contract_id, config.quote_asset)
+                .await?,
+        })
+    }
+
+    pub async fn accounts(&self, users: Vec<Identity>) -> anyhow::Result<Vec<Account>> {
+        let mut accounts = Vec::with_capacity(users.len());
+        for chunk in users.chunks(MAX_BATCH_SIZE) {
+            let mut multi_call = CallHandler::new_multi_call(self.instance.account());
+            for user in chunk {
+                multi_call = multi_call.add_call(self.instance.methods().account(*user));
+            }
+            let outputs = multi_call
+                .simulate::<CallOutputs>(Execution::StateReadOnly)
+                .await?
+                .value;
+            for token in outputs.0 {
+                accounts.push(Account::from_token(token)?);
+            }
+        }
+        Ok(accounts)
+    }
+
+    pub async fn protocol_fee(&self) -> anyhow::Result<CallResponse<Vec<ProtocolFee>>> {
+        Ok(self
+            .instance
+            .methods()
+            .protocol_fee()
+            .simulate(Execution::StateReadOnly)
+            .await?)
+    }
+
+    pub async fn taker_fee(&self) -> anyhow::Result<u64> {
+        Ok(self
+            .protocol_fee()
+            .await?
+            .value
+            .first()
+            .map_or(0, |fee| fee.taker_fee))
+    }
+
+    pub async fn protocol_fee_user(
+        &self,
+        user: Identity,
+    ) -> anyhow::Result<CallResponse<(u64, u64)>> {
+        Ok(self
+            .instance
+            .methods()
+            .protocol_fee_user(user)
+            .simulate(Execution::StateReadOnly)
+            .await?)
+    }
+
+    pub async fn protocol_fee_user_amount(
+        &self,
+        amount: u64,
+        user: Identity,
+    ) -> anyhow::Result<CallResponse<(u64, u64)>> {
+        Ok(self
+            .instance
+            .methods()
+            .protocol_fee_user_amount(amount, user)
+            .simulate(Execution::StateReadOnly)
+            .await?)
     }
 
     pub async fn matcher_fee(&self) -> anyhow::Result<CallResponse<u64>> {
@@ -295,82 +2444,1350 @@ impl SparkMarketContract {
             .await?)
     }
 
-    pub async fn get_epoch(&self) -> anyhow::Result<CallResponse<(u64, u64)>> {
+    pub async fn dust_threshold(&self) -> anyhow::Result<CallResponse<u64>> {
+        Ok(self
+            .instance
+            .methods()
+            .dust_threshold()
+            .simulate(Execution::StateReadOnly)
+            .await?)
+    }
+
+    pub async fn tick_size(&self) -> anyhow::Result<CallResponse<u64>> {
+        Ok(self
+            .instance
+            .methods()
+            .tick_size()
+            .simulate(Execution::StateReadOnly)
+            .await?)
+    }
+
+    pub async fn maker_price_execution(&self) -> anyhow::Result<CallResponse<bool>> {
+        Ok(self
+            .instance
+            .methods()
+            .maker_price_execution()
+            .simulate(Execution::StateReadOnly)
+            .await?)
+    }
+
+    pub async fn max_price_deviation_bps(&self) -> anyhow::Result<CallResponse<u64>> {
+        Ok(self
+            .instance
+            .methods()
+            .max_price_deviation_bps()
+            .simulate(Execution::StateReadOnly)
+            .await?)
+    }
+
+    pub async fn is_fee_exempt(&self, user: Identity) -> anyhow::Result<CallResponse<bool>> {
+        Ok(self
+            .instance
+            .methods()
+            .is_fee_exempt(user)
+            .simulate(Execution::StateReadOnly)
+            .await?)
+    }
+
+    pub async fn keeper_rewards(&self, keeper: Identity) -> anyhow::Result<CallResponse<u64>> {
+        Ok(self
+            .instance
+            .methods()
+            .keeper_rewards(keeper)
+            .simulate(Execution::StateReadOnly)
+            .await?)
+    }
+
+    pub async fn last_trade_price(&self) -> anyhow::Result<CallResponse<u64>> {
+        Ok(self
+            .instance
+            .methods()
+            .last_trade_price()
+            .simulate(Execution::StateReadOnly)
+            .await?)
+    }
+
+    pub async fn get_epoch(&self) -> anyhow::Result<CallResponse<(u64, u64)>> {
+        Ok(self
+            .instance
+            .methods()
+            .get_epoch()
+            .simulate(Execution::StateReadOnly)
+            .await?)
+    }
+
+    pub async fn set_epoch(
+        &self,
+        epoch: u64,
+        epoch_duration: u64,
+    ) -> anyhow::Result<CallResponse<()>> {
+        call_with_funds(
+            self.instance.methods().set_epoch(epoch, epoch_duration),
+            AssetId::default(),
+            0,
+            0,
+            &self.fee_checks,
+        )
+        .await
+    }
+
+    pub async fn order(&self, order: Bits256) -> anyhow::Result<CallResponse<Option<Order>>> {
+        Ok(self
+            .instance
+            .methods()
+            .order(order)
+            .simulate(Execution::StateReadOnly)
+            .await?)
+    }
+
+    pub async fn order_deadline(
+        &self,
+        order: Bits256,
+    ) -> anyhow::Result<CallResponse<Option<(u32, u64)>>> {
+        Ok(self
+            .instance
+            .methods()
+            .order_deadline(order)
+            .simulate(Execution::StateReadOnly)
+            .await?)
+    }
+
+    pub async fn order_by_id(&self, id: Bits256) -> anyhow::Result<Option<OrderDetails>> {
+        Ok(self
+            .order(id)
+            .await?
+            .value
+            .map(|order| OrderDetails::new(id, order)))
+    }
+
+    pub async fn user_orders(&self, user: Identity) -> anyhow::Result<CallResponse<Vec<Bits256>>> {
+        Ok(self
+            .instance
+            .methods()
+            .user_orders(user)
+            .simulate(Execution::StateReadOnly)
+            .await?)
+    }
+
+    pub async fn user_orders_paginated(
+        &self,
+        user: Identity,
+        offset: u64,
+        limit: u64,
+    ) -> anyhow::Result<CallResponse<Vec<Bits256>>> {
+        Ok(self
+            .instance
+            .methods()
+            .user_orders_paginated(user, offset, limit)
+            .simulate(Execution::StateReadOnly)
+            .await?)
+    }
+
+    pub async fn all_user_orders(
+        &self,
+        user: Identity,
+        page_size: u64,
+        decoder_config: DecoderConfig,
+    ) -> anyhow::Result<UserOrderIds> {
+        if page_size == 0 {
+            anyhow::bail!("Page size must be greater than zero");
+        }
+
+        let error = match self
+            .instance
+            .methods()
+            .user_orders(user)
+            .with_decoder_config(decoder_config)
+            .simulate(Execution::StateReadOnly)
+            .await
+        {
+            Ok(response) => {
+                return Ok(UserOrderIds {
+                    orders: response.value,
+                    warning: None,
+                })
+            }
+            Err(error) if is_return_size_error(&error) => error,
+            Err(error) => return Err(error.into()),
+        };
+
+        let mut orders: Vec<Bits256> = Vec::new();
+        loop {
+            let page = self
+                .instance
+                .methods()
+                .user_orders_paginated(user, orders.len() as u64, page_size)
+                .with_decoder_config(decoder_config)
+                .simulate(Execution::StateReadOnly)
+                .await?
+                .value;
+            let last_page = (page.len() as u64) < page_size;
+            orders.extend(page);
+            if last_page {
+                break;
+            }
+        }
+
+        let warning = format!(
+            "user_orders exceeded the return size limit ({}), fetched {} orders in pages of {}",
+            error,
+            orders.len(),
+            page_size
+        );
+
+        Ok(UserOrderIds {
+            orders,
+            warning: Some(warning),
+        })
+    }
+
+    pub async fn order_change_info(
+        &self,
+        order_id: Bits256,
+    ) -> anyhow::Result<CallResponse<Vec<OrderChangeInfo>>> {
+        Ok(self
+            .instance
+            .methods()
+            .order_change_info(order_id)
+            .simulate(Execution::StateReadOnly)
+            .await?)
+    }
+
+    /// Decodes the trades of this market from every successful transaction in the inclusive block range
+    pub async fn trade_events(
+        &self,
+        from_block: u32,
+        to_block: u32,
+    ) -> anyhow::Result<Vec<TradeOrderEvent>> {
+        self.events_in_blocks(from_block, to_block).await
+    }
+
+    /// Orders opened in the block range that traded within `max_blocks` of being opened
+    /// against an already resting order, see `flag_frontrun`
+    pub async fn frontrun_suspects(
+        &self,
+        from_block: u32,
+        to_block: u32,
+        max_blocks: u32,
+    ) -> anyhow::Result<Vec<FrontRunSuspect>> {
+        let opened = self
+            .events_by_block::<OpenOrderEvent>(from_block, to_block)
+            .await?;
+        // Orders opened near the end of the range may be matched after it
+        let trades = self
+            .trade_events(from_block, to_block.saturating_add(max_blocks))
+            .await?;
+        Ok(flag_frontrun(&opened, &trades, max_blocks))
+    }
+
+    /// Replays the trader's events in the block range, in the order they were logged
+    pub async fn balance_history(
+        &self,
+        trader: Identity,
+        from_block: u32,
+        to_block: u32,
+    ) -> anyhow::Result<Vec<BalanceChange>> {
+        let provider = self.instance.account().try_provider()?.clone();
+        let log_decoder = self.instance.log_decoder();
+
+        let mut history = Vec::new();
+        for height in from_block..=to_block {
+            let Some(block) = provider.block_by_height(height.into()).await? else {
+                break;
+            };
+            for tx_id in block.transactions {
+                let TxStatus::Success { receipts } = provider.tx_status(&tx_id).await? else {
+                    continue;
+                };
+                // Each receipt is decoded on its own so events of different types keep their order
+                for receipt in receipts.iter() {
+                    let receipt = std::slice::from_ref(receipt);
+                    let mut record = |kind, liquid_base, liquid_quote| {
+                        history.push(BalanceChange {
+                            block_height: height,
+                            tx_id,
+                            kind,
+                            liquid_base,
+                            liquid_quote,
+                        })
+                    };
+                    for event in log_decoder.decode_logs_with_type::<DepositEvent>(receipt)? {
+                        if event.user == trader {
+                            record(
+                                BalanceChangeKind::Deposit,
+                                event.liquid_base,
+                                event.liquid_quote,
+                            );
+                        }
+                    }
+                    for event in log_decoder.decode_logs_with_type::<WithdrawEvent>(receipt)? {
+                        if event.user == trader {
+                            record(
+                                BalanceChangeKind::Withdraw,
+                                event.liquid_base,
+                                event.liquid_quote,
+                            );
+                        }
+                    }
+                    for event in log_decoder.decode_logs_with_type::<OpenOrderEvent>(receipt)? {
+                        if event.user == trader {
+                            record(
+                                BalanceChangeKind::OpenOrder,
+                                event.liquid_base,
+                                event.liquid_quote,
+                            );
+                        }
+                    }
+                    for event in log_decoder.decode_logs_with_type::<CancelOrderEvent>(receipt)? {
+                        if event.user == trader {
+                            record(
+                                BalanceChangeKind::CancelOrder,
+                                event.liquid_base,
+                                event.liquid_quote,
+                            );
+                        }
+                    }
+                    // A trade moves both assets of both sides, the trader's side is taken
+                    for event in log_decoder.decode_logs_with_type::<TradeOrderEvent>(receipt)? {
+                        if event.order_buyer == trader {
+                            record(
+                                BalanceChangeKind::Trade,
+                                event.b_account_liquid_base,
+                                event.b_account_liquid_quote,
+                            );
+                        } else if event.order_seller == trader {
+                            record(
+                                BalanceChangeKind::Trade,
+                                event.s_account_liquid_base,
+                                event.s_account_liquid_quote,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        Ok(history)
+    }
+
+    /// The matcher and protocol fee changes in the block range, in the order they were logged
+    pub async fn fee_change_history(
+        &self,
+        from_block: u32,
+        to_block: u32,
+    ) -> anyhow::Result<Vec<FeeChange>> {
+        let provider = self.instance.account().try_provider()?.clone();
+        let log_decoder = self.instance.log_decoder();
+
+        let mut history = Vec::new();
+        for height in from_block..=to_block {
+            let Some(block) = provider.block_by_height(height.into()).await? else {
+                break;
+            };
+            for tx_id in block.transactions {
+                let TxStatus::Success { receipts } = provider.tx_status(&tx_id).await? else {
+                    continue;
+                };
+                // Each receipt is decoded on its own so both kinds of change keep their order
+                for receipt in receipts.iter() {
+                    let receipt = std::slice::from_ref(receipt);
+                    for event in
+                        log_decoder.decode_logs_with_type::<MatcherFeeChangeEvent>(receipt)?
+                    {
+                        history.push(FeeChange {
+                            block_height: height,
+                            tx_id,
+                            changed_by: event.changed_by,
+                            kind: FeeChangeKind::Matcher {
+                                old_fee: event.old_fee,
+                                new_fee: event.new_fee,
+                            },
+                        });
+                    }
+                    for event in
+                        log_decoder.decode_logs_with_type::<ProtocolFeeChangeEvent>(receipt)?
+                    {
+                        history.push(FeeChange {
+                            block_height: height,
+                            tx_id,
+                            changed_by: event.changed_by,
+                            kind: FeeChangeKind::Protocol {
+                                old_fee: event.old_fee,
+                                new_fee: event.new_fee,
+                            },
+                        });
+                    }
+                }
+            }
+        }
+        Ok(history)
+    }
+
+    /// The block of the most recent trade at or after `from_block`. Blocks are scanned back from
+    /// the chain tip one by one and never below `from_block`, so a market which stopped trading
+    /// long ago costs one request per block in between
+    pub async fn last_trade_block(&self, from_block: u32) -> anyhow::Result<Option<u32>> {
+        let tip = self
+            .instance
+            .account()
+            .try_provider()?
+            .latest_block_height()
+            .await?;
+
+        for height in (from_block..=tip).rev() {
+            if !self
+                .events_in_blocks::<TradeOrderEvent>(height, height)
+                .await?
+                .is_empty()
+            {
+                return Ok(Some(height));
+            }
+        }
+        Ok(None)
+    }
+
+    pub async fn is_active_within(&self, blocks: u32) -> anyhow::Result<bool> {
+        if blocks == 0 {
+            anyhow::bail!("Blocks must be greater than zero");
+        }
+
+        let tip = self
+            .instance
+            .account()
+            .try_provider()?
+            .latest_block_height()
+            .await?;
+        Ok(self
+            .last_trade_block(tip.saturating_sub(blocks - 1))
+            .await?
+            .is_some())
+    }
+
+    pub async fn trades_per_block(&self, blocks: u32) -> anyhow::Result<TradeThroughput> {
+        if blocks == 0 {
+            anyhow::bail!("Blocks must be greater than zero");
+        }
+
+        let tip = self
+            .instance
+            .account()
+            .try_provider()?
+            .latest_block_height()
+            .await?;
+        let from_block = tip.saturating_sub(blocks - 1);
+        let trades = self.trade_events(from_block, tip).await?.len();
+        Ok(TradeThroughput {
+            trades,
+            blocks: tip - from_block + 1,
+        })
+    }
+
+    pub async fn order_size(&self, id: Bits256) -> anyhow::Result<Option<OrderSize>> {
+        let Some(order) = self.order(id).await?.value else {
+            return Ok(None);
+        };
+
+        // The market only keeps the remaining amount, the opening amount is in the event
+        // logged in the block the order was opened in
+        let original = self
+            .events_in_blocks::<OpenOrderEvent>(order.block_height, order.block_height)
+            .await?
+            .into_iter()
+            .find(|event| event.order_id == id)
+            .ok_or_else(|| anyhow::anyhow!("Missing OpenOrderEvent for order {:?}", id))?
+            .amount;
+
+        Ok(Some(OrderSize {
+            original,
+            remaining: order.amount,
+        }))
+    }
+
+    async fn events_in_blocks<T>(&self, from_block: u32, to_block: u32) -> anyhow::Result<Vec<T>>
+    where
+        T: Tokenizable + Parameterize + 'static,
+    {
         Ok(self
-            .instance
-            .methods()
-            .get_epoch()
-            .simulate(Execution::StateReadOnly)
-            .await?)
+            .events_by_block(from_block, to_block)
+            .await?
+            .into_iter()
+            .map(|(_, event)| event)
+            .collect())
     }
 
-    pub async fn set_epoch(
+    // Like `events_in_blocks`, with the height of the block each event was logged in
+    async fn events_by_block<T>(
         &self,
-        epoch: u64,
-        epoch_duration: u64,
-    ) -> anyhow::Result<CallResponse<()>> {
+        from_block: u32,
+        to_block: u32,
+    ) -> anyhow::Result<Vec<(u32, T)>>
+    where
+        T: Tokenizable + Parameterize + 'static,
+    {
+        let provider = self.instance.account().try_provider()?.clone();
+        let log_decoder = self.instance.log_decoder();
+
+        let mut events = Vec::new();
+        for height in from_block..=to_block {
+            let Some(block) = provider.block_by_height(height.into()).await? else {
+                break;
+            };
+            for tx_id in block.transactions {
+                // Log ids are scoped to this contract so other contracts' logs are skipped
+                if let TxStatus::Success { receipts } = provider.tx_status(&tx_id).await? {
+                    events.extend(
+                        log_decoder
+                            .decode_logs_with_type::<T>(&receipts)?
+                            .into_iter()
+                            .map(|event| (height, event)),
+                    );
+                }
+            }
+        }
+        Ok(events)
+    }
+
+    pub async fn config(&self) -> anyhow::Result<MarketConfig> {
+        Ok(self.config_raw().await?.value.into())
+    }
+
+    pub async fn config_raw(
+        &self,
+    ) -> anyhow::Result<CallResponse<(AssetId, u32, AssetId, u32, Identity, u32, u32)>> {
         Ok(self
             .instance
             .methods()
-            .set_epoch(epoch, epoch_duration)
-            .call()
+            .config()
+            .simulate(Execution::StateReadOnly)
             .await?)
     }
 
-    pub async fn order(&self, order: Bits256) -> anyhow::Result<CallResponse<Option<Order>>> {
+    pub async fn order_id(
+        &self,
+        order_type: OrderType,
+        owner: Identity,
+        price: u64,
+        block_height: u32,
+        order_height: u64,
+    ) -> anyhow::Result<CallResponse<Bits256>> {
         Ok(self
             .instance
             .methods()
-            .order(order)
+            .order_id(order_type, owner, price, block_height, order_height)
             .simulate(Execution::StateReadOnly)
             .await?)
     }
 
-    pub async fn user_orders(&self, user: Identity) -> anyhow::Result<CallResponse<Vec<Bits256>>> {
+    pub async fn order_height(&self) -> anyhow::Result<CallResponse<u64>> {
         Ok(self
             .instance
             .methods()
-            .user_orders(user)
+            .order_height()
             .simulate(Execution::StateReadOnly)
             .await?)
     }
 
-    pub async fn order_change_info(
+    /// The order height the market will assign to the next order. Read from the market on every
+    /// call, since orders opened by other accounts advance it too
+    pub async fn next_order_height(&self) -> anyhow::Result<u64> {
+        Ok(self.order_height().await?.value)
+    }
+
+    fn log_opened_order(
         &self,
         order_id: Bits256,
-    ) -> anyhow::Result<CallResponse<Vec<OrderChangeInfo>>> {
-        Ok(self
+        order_type: OrderType,
+        price: u64,
+        amount: u64,
+        deadline: Option<u32>,
+    ) {
+        let params = OrderParams {
+            owner: Identity::Address(self.instance.account().address().into()),
+            order_type,
+            price,
+            amount,
+            deadline,
+        };
+        self.opened_orders
+            .lock()
+            .unwrap()
+            .insert(order_id.0, params);
+    }
+
+    /// The parameters an order was opened with by this session or a handle sharing it through
+    /// `with_account`, available after the order is filled or cancelled until the log drops it,
+    /// see `with_order_log_capacity`
+    pub fn recall_order_params(&self, order_id: Bits256) -> Option<OrderParams> {
+        self.opened_orders.lock().unwrap().get(&order_id.0)
+    }
+
+    /// Computes the id `order_id` returns without calling the market
+    pub fn order_id_offline(
+        &self,
+        order_type: OrderType,
+        owner: Identity,
+        price: u64,
+        order_height: u64,
+    ) -> Bits256 {
+        market_order_id(
+            ContractId::from(*self.id()),
+            order_type,
+            owner,
+            price,
+            order_height,
+        )
+    }
+
+    pub async fn required_deposit(
+        &self,
+        amount: u64,
+        order_type: OrderType,
+        price: u64,
+    ) -> anyhow::Result<(AssetId, u64)> {
+        let config = self.config().await?;
+
+        match order_type {
+            OrderType::Sell => Ok((config.base_asset, amount)),
+            OrderType::Buy => {
+                // Round the quote value and its fees up so the deposit never falls short of the lock
+                let quote = quote_of_base_amount(
+                    amount,
+                    price,
+                    config.base_decimals,
+                    config.quote_decimals,
+                    config.price_decimals,
+                    RoundingMode::Ceil,
+                )?;
+                let user = Identity::Address(self.instance.account().address().into());
+                let (maker_fee, taker_fee) = self.protocol_fee_user(user).await?.value;
+                let protocol_fee = mul_div(
+                    quote,
+                    maker_fee.max(taker_fee),
+                    HUNDRED_PERCENT,
+                    RoundingMode::Ceil,
+                )?;
+                let matcher_fee = self.matcher_fee().await?.value;
+
+                Ok((
+                    config.quote_asset,
+                    checked_sum(&[quote, protocol_fee, matcher_fee])?,
+                ))
+            }
+        }
+    }
+
+    pub async fn quote_trade(&self, amount: u64, price: u64) -> anyhow::Result<u64> {
+        let config = self.config().await?;
+
+        // Round the payout down so the market never pays out more than it holds
+        quote_of_base_amount(
+            amount,
+            price,
+            config.base_decimals,
+            config.quote_decimals,
+            config.price_decimals,
+            RoundingMode::Floor,
+        )
+    }
+
+    /// The orders among `orders` which still rest on the book
+    pub async fn book_snapshot(&self, orders: Vec<Bits256>) -> anyhow::Result<BookSnapshot> {
+        let mut snapshot = BookSnapshot {
+            orders: Vec::new(),
+            dust_threshold: self.dust_threshold().await?.value,
+        };
+        for order_id in orders {
+            // Orders which have been filled or cancelled no longer rest on the book
+            if let Some(order) = self.order(order_id).await?.value {
+                snapshot.orders.push((order_id, order));
+            }
+        }
+        Ok(snapshot)
+    }
+
+    pub async fn book_depth(&self, orders: Vec<Bits256>) -> anyhow::Result<BookDepth> {
+        Ok(self.book_snapshot(orders).await?.depth())
+    }
+
+    /// Pairs of resting (buy, sell) orders among `orders` where the bid is at or above the ask,
+    /// i.e. that `match_order_pair` would fill, best bid first and then best ask
+    pub async fn find_crossed_orders(
+        &self,
+        orders: Vec<Bits256>,
+    ) -> anyhow::Result<Vec<(Bits256, Bits256)>> {
+        let mut bids = Vec::new();
+        let mut asks = Vec::new();
+        for order_id in orders {
+            // Orders which have been filled or cancelled no longer rest on the book
+            if let Some(order) = self.order(order_id).await?.value {
+                match order.order_type {
+                    OrderType::Buy => bids.push((order.price, order_id)),
+                    OrderType::Sell => asks.push((order.price, order_id)),
+                }
+            }
+        }
+        bids.sort_by_key(|bid| std::cmp::Reverse(bid.0));
+        asks.sort_by_key(|ask| ask.0);
+
+        Ok(bids
+            .iter()
+            .flat_map(|bid| {
+                asks.iter()
+                    .take_while(move |ask| ask.0 <= bid.0)
+                    .map(move |ask| (bid.1, ask.1))
+            })
+            .collect())
+    }
+
+    /// Dry-runs matching `taker_id` against `makers` in price-time priority, best price first
+    /// and then the lowest order height, and returns the fills the market would make in order
+    pub async fn audit_match(
+        &self,
+        taker_id: Bits256,
+        makers: Vec<Bits256>,
+    ) -> anyhow::Result<Vec<AuditedFill>> {
+        let taker = self
+            .order(taker_id)
+            .await?
+            .value
+            .ok_or_else(|| anyhow::anyhow!("OrderNotFound: {:?}", taker_id))?;
+
+        let mut queue = Vec::new();
+        for maker_id in makers {
+            // Orders which have been filled or cancelled no longer rest on the book
+            if let Some(maker) = self.order(maker_id).await?.value {
+                if maker.order_type != taker.order_type && maker_id != taker_id {
+                    queue.push((maker.price, maker.order_height, maker_id));
+                }
+            }
+        }
+        match taker.order_type {
+            OrderType::Buy => queue.sort_by_key(|maker| (maker.0, maker.1)),
+            OrderType::Sell => queue.sort_by_key(|maker| (std::cmp::Reverse(maker.0), maker.1)),
+        }
+        if queue.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // The market matches the first order against the rest in the given order, skipping
+        // makers which do not cross, so the trades it logs are the fills in priority order
+        let orders = std::iter::once(taker_id)
+            .chain(queue.into_iter().map(|maker| maker.2))
+            .collect();
+        let response = match self
             .instance
             .methods()
-            .order_change_info(order_id)
+            .match_order_many(orders)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
             .simulate(Execution::StateReadOnly)
-            .await?)
+            .await
+        {
+            Ok(response) => response,
+            Err(error) if error.to_string().contains("CantMatchMany") => return Ok(Vec::new()),
+            Err(error) => return Err(error.into()),
+        };
+
+        Ok(response
+            .decode_logs_with_type::<TradeOrderEvent>()?
+            .into_iter()
+            .map(|trade| AuditedFill {
+                maker_id: match trade.base_sell_order_id == taker_id {
+                    true => trade.base_buy_order_id,
+                    false => trade.base_sell_order_id,
+                },
+                // The price the market settled the trade at, not either order's limit
+                price: trade.trade_price,
+                amount: trade.trade_size,
+            })
+            .collect())
     }
 
-    pub async fn config(
+    pub async fn book_microstructure(
         &self,
-    ) -> anyhow::Result<CallResponse<(AssetId, u32, AssetId, u32, Identity, u32, u32)>> {
-        Ok(self
+        orders: Vec<Bits256>,
+        levels: usize,
+    ) -> anyhow::Result<BookMicrostructure> {
+        if levels == 0 {
+            anyhow::bail!("Levels must be greater than zero");
+        }
+
+        let depth = self.book_depth(orders).await?;
+
+        let best_bid = depth.bids.first().map(|level| level.0);
+        let best_ask = depth.asks.first().map(|level| level.0);
+        let bid_depth: u64 = depth.bids.iter().take(levels).map(|level| level.1).sum();
+        let ask_depth: u64 = depth.asks.iter().take(levels).map(|level| level.1).sum();
+
+        let mid_price = match (best_bid, best_ask) {
+            (Some(bid), Some(ask)) => Some(((bid as u128 + ask as u128) / 2) as u64),
+            _ => None,
+        };
+        let imbalance_bps = match bid_depth as u128 + ask_depth as u128 {
+            0 => None,
+            total => Some((bid_depth as u128 * HUNDRED_PERCENT as u128 / total) as u64),
+        };
+
+        Ok(BookMicrostructure {
+            best_bid,
+            best_ask,
+            mid_price,
+            bid_depth,
+            ask_depth,
+            imbalance_bps,
+        })
+    }
+
+    /// The current `source` price, with the book formed by `orders` for the sources other than
+    /// `LastTrade`. `None` when the market has not traded or a side of the book is empty
+    pub async fn reference_price(
+        &self,
+        source: ReferencePrice,
+        orders: Vec<Bits256>,
+    ) -> anyhow::Result<Option<u64>> {
+        Ok(match source {
+            ReferencePrice::LastTrade => {
+                Some(self.last_trade_price().await?.value).filter(|price| *price != 0)
+            }
+            ReferencePrice::BestBid => self.book_microstructure(orders, 1).await?.best_bid,
+            ReferencePrice::BestAsk => self.book_microstructure(orders, 1).await?.best_ask,
+            ReferencePrice::Mid => self.book_microstructure(orders, 1).await?.mid_price,
+        })
+    }
+
+    /// Whether the reference price of `trigger` has reached its trigger price
+    pub async fn is_triggered(
+        &self,
+        trigger: &TriggerOrder,
+        orders: Vec<Bits256>,
+    ) -> anyhow::Result<bool> {
+        let source = trigger.reference.unwrap_or(self.reference_price);
+        let Some(price) = self.reference_price(source, orders).await? else {
+            return Ok(false);
+        };
+
+        Ok(match trigger.condition {
+            TriggerCondition::AtOrAbove => price >= trigger.trigger_price,
+            TriggerCondition::AtOrBelow => price <= trigger.trigger_price,
+        })
+    }
+
+    /// Opens `trigger` from the deposited balance once it is triggered, `None` until then
+    pub async fn open_if_triggered(
+        &self,
+        trigger: &TriggerOrder,
+        orders: Vec<Bits256>,
+    ) -> anyhow::Result<Option<CallResponse<OpenedOrder>>> {
+        if !self.is_triggered(trigger, orders).await? {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            self.open_order(trigger.amount, trigger.order_type.clone(), trigger.price)
+                .await?,
+        ))
+    }
+
+    pub async fn order_aging(
+        &self,
+        orders: Vec<Bits256>,
+        bucket_size: u32,
+    ) -> anyhow::Result<OrderAging> {
+        if bucket_size == 0 {
+            anyhow::bail!("Bucket size must be greater than zero");
+        }
+
+        let tip = self
             .instance
-            .methods()
-            .config()
-            .simulate(Execution::StateReadOnly)
-            .await?)
+            .account()
+            .try_provider()?
+            .latest_block_height()
+            .await?;
+
+        let mut ages = Vec::with_capacity(orders.len());
+        for order_id in orders {
+            // Orders which have been filled or cancelled no longer rest on the book
+            if let Some(order) = self.order(order_id).await?.value {
+                ages.push(tip.saturating_sub(order.block_height));
+            }
+        }
+
+        if ages.is_empty() {
+            anyhow::bail!("No open orders to report on");
+        }
+
+        ages.sort_unstable();
+
+        let mut buckets = BTreeMap::new();
+        for age in &ages {
+            *buckets.entry(age / bucket_size * bucket_size).or_insert(0) += 1;
+        }
+
+        Ok(OrderAging {
+            tip,
+            oldest: ages[ages.len() - 1],
+            median: ages[(ages.len() - 1) / 2],
+            buckets: buckets.into_iter().collect(),
+        })
     }
 
-    pub async fn order_id(
+    pub async fn quote_trade_breakdown(
+        &self,
+        amount: u64,
+        price: u64,
+    ) -> anyhow::Result<TradeQuote> {
+        let quote = self.quote_trade(amount, price).await?;
+        let user = Identity::Address(self.instance.account().address().into());
+        let (maker_fee, taker_fee) = self.protocol_fee_user(user).await?.value;
+
+        // The market rounds protocol fees down
+        Ok(TradeQuote {
+            quote,
+            maker_fee: mul_div(quote, maker_fee, HUNDRED_PERCENT, RoundingMode::Floor)?,
+            taker_fee: mul_div(quote, taker_fee, HUNDRED_PERCENT, RoundingMode::Floor)?,
+            matcher_fee: self.matcher_fee().await?.value,
+        })
+    }
+
+    pub async fn verify_order_id(
         &self,
         order_type: OrderType,
         owner: Identity,
         price: u64,
         block_height: u32,
         order_height: u64,
+    ) -> anyhow::Result<bool> {
+        let order_id = self
+            .order_id(order_type.clone(), owner, price, block_height, order_height)
+            .await?
+            .value;
+
+        Ok(match self.order(order_id).await?.value {
+            Some(order) => {
+                order.order_type == order_type
+                    && order.owner == owner
+                    && order.price == price
+                    && order.block_height == block_height
+                    && order.order_height == order_height
+            }
+            None => false,
+        })
+    }
+
+    /// Sums the locks of the open orders of `user` and compares them with the locked balance
+    /// the market reports for its account
+    pub async fn reconcile_locked(&self, user: Identity) -> anyhow::Result<LockedReconciliation> {
+        let config = self.config().await?;
+        let reported = self.account(user).await?.value.locked;
+
+        let mut orders = Vec::new();
+        let mut keeper_rewards = 0;
+        for order_id in self.user_orders(user).await?.value {
+            let order = self
+                .order(order_id)
+                .await?
+                .value
+                .ok_or_else(|| anyhow::anyhow!("Missing order {:?} of {:?}", order_id, user))?;
+            if let Some((_, keeper_reward)) = self.order_deadline(order_id).await?.value {
+                keeper_rewards += keeper_reward;
+            }
+            orders.push(order);
+        }
+
+        Ok(LockedReconciliation {
+            reported,
+            expected: expected_locked(&orders, keeper_rewards, &config)?,
+        })
+    }
+}
+
+/// Outputs of a multicall, one token per call
+#[derive(Debug)]
+struct CallOutputs(Vec<Token>);
+
+impl Tokenizable for CallOutputs {
+    fn from_token(token: Token) -> fuels::types::errors::Result<Self> {
+        match token {
+            Token::Tuple(tokens) => Ok(Self(tokens)),
+            other => Err(error!(
+                Codec,
+                "expected a tuple of call outputs, got {:?}", other
+            )),
+        }
+    }
+
+    fn into_token(self) -> Token {
+        Token::Tuple(self.0)
+    }
+}
+
+type OrderRequest = (
+    Bits256,
+    oneshot::Sender<Result<Option<OrderDetails>, String>>,
+);
+
+/// Coalesces concurrent order reads issued within a short window into a single multicall
+#[derive(Clone)]
+pub struct BatchingClient {
+    requests: mpsc::UnboundedSender<OrderRequest>,
+    round_trips: Arc<AtomicUsize>,
+}
+
+impl BatchingClient {
+    /// Starts the task that sends the batches on `runtime`
+    pub fn new<A: fuels::accounts::Account + 'static>(
+        contract: &SparkMarketContract<A>,
+        window: Duration,
+        runtime: &Handle,
+    ) -> Self {
+        let (requests, mut receiver) = mpsc::unbounded_channel::<OrderRequest>();
+        let round_trips = Arc::new(AtomicUsize::new(0));
+
+        let instance = contract.instance.clone();
+        let counter = round_trips.clone();
+        runtime.spawn(async move {
+            while let Some(first) = receiver.recv().await {
+                let mut batch = vec![first];
+                let deadline = Instant::now() + window;
+                while batch.len() < MAX_BATCH_SIZE {
+                    match timeout_at(deadline, receiver.recv()).await {
+                        Ok(Some(request)) => batch.push(request),
+                        _ => break,
+                    }
+                }
+
+                counter.fetch_add(1, Ordering::Relaxed);
+                let ids: Vec<Bits256> = batch.iter().map(|(id, _)| *id).collect();
+                match fetch_orders(&instance, &ids).await {
+                    Ok(orders) => {
+                        for ((_, reply), order) in batch.into_iter().zip(orders) {
+                            let _ = reply.send(Ok(order));
+                        }
+                    }
+                    Err(error) => {
+                        let error = error.to_string();
+                        for (_, reply) in batch {
+                            let _ = reply.send(Err(error.clone()));
+                        }
+                    }
+                }
+            }
+        });
+
+        Self {
+            requests,
+            round_trips,
+        }
+    }
+
+    pub async fn order_by_id(&self, id: Bits256) -> anyhow::Result<Option<OrderDetails>> {
+        let (reply, response) = oneshot::channel();
+        self.requests
+            .send((id, reply))
+            .map_err(|_| anyhow::anyhow!("Batching client has stopped"))?;
+        response.await?.map_err(|error| anyhow::anyhow!(error))
+    }
+
+    /// Number of multicalls sent to the node so far
+    pub fn round_trips(&self) -> usize {
+        self.round_trips.load(Ordering::Relaxed)
+    }
+}
+
+/// Runs a shutdown callback once when no successful action has been recorded for `interval`,
+/// e.g. to cancel a matcher's orders after it lost connectivity
+pub struct DeadMansSwitch {
+    last_action: Arc<Mutex<Instant>>,
+    fired: Arc<AtomicBool>,
+    watchdog: JoinHandle<()>,
+}
+
+impl DeadMansSwitch {
+    /// Arms the switch, counting the interval from now
+    pub fn arm<F, Fut>(interval: Duration, on_stale: F) -> Self
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let last_action = Arc::new(Mutex::new(Instant::now()));
+        let fired = Arc::new(AtomicBool::new(false));
+
+        let last = last_action.clone();
+        let flag = fired.clone();
+        let watchdog = tokio::spawn(async move {
+            loop {
+                // Actions recorded while asleep push the deadline back
+                let deadline = *last.lock().unwrap() + interval;
+                sleep_until(deadline).await;
+                if Instant::now() >= *last.lock().unwrap() + interval {
+                    break;
+                }
+            }
+            flag.store(true, Ordering::Relaxed);
+            on_stale().await;
+        });
+
+        Self {
+            last_action,
+            fired,
+            watchdog,
+        }
+    }
+
+    /// Restarts the interval, call it after each successful on-chain action
+    pub fn record_action(&self) {
+        *self.last_action.lock().unwrap() = Instant::now();
+    }
+
+    /// Awaits `action` and records it when it succeeds
+    pub async fn watch<T>(
+        &self,
+        action: impl Future<Output = anyhow::Result<T>>,
+    ) -> anyhow::Result<T> {
+        let result = action.await;
+        if result.is_ok() {
+            self.record_action();
+        }
+        result
+    }
+
+    /// Whether the interval has passed without an action and the callback was started
+    pub fn has_fired(&self) -> bool {
+        self.fired.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for DeadMansSwitch {
+    fn drop(&mut self) {
+        self.watchdog.abort();
+    }
+}
+
+async fn fetch_orders<A: fuels::accounts::Account>(
+    instance: &SparkMarket<A>,
+    ids: &[Bits256],
+) -> anyhow::Result<Vec<Option<OrderDetails>>> {
+    let mut multi_call = CallHandler::new_multi_call(instance.account());
+    for id in ids {
+        multi_call = multi_call.add_call(instance.methods().order(*id));
+    }
+    let outputs = multi_call
+        .simulate::<CallOutputs>(Execution::StateReadOnly)
+        .await?
+        .value;
+
+    outputs
+        .0
+        .into_iter()
+        .zip(ids)
+        .map(|(token, id)| {
+            Ok(Option::<Order>::from_token(token)?.map(|order| OrderDetails::new(*id, order)))
+        })
+        .collect()
+}
+
+/// Reads a cache served, `misses` counts the reads sent to the node
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+type CachedOrder = (Instant, Option<OrderDetails>);
+
+/// Memoizes order and config reads for `ttl`, dropping the orders a mutating call touches
+pub struct CachedSparkMarket<A: fuels::accounts::Account = WalletUnlocked> {
+    contract: SparkMarketContract<A>,
+    ttl: Duration,
+    /// Keyed by the id bytes, `Bits256` is not `Hash`
+    orders: Mutex<HashMap<[u8; 32], CachedOrder>>,
+    config: Mutex<Option<(Instant, MarketConfig)>>,
+    /// The order ids making up the book and their last snapshot, set by `new_with_warmup`
+    book: Mutex<Option<(Vec<Bits256>, Option<(Instant, BookSnapshot)>)>>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl<A: fuels::accounts::Account> CachedSparkMarket<A> {
+    pub fn new(contract: SparkMarketContract<A>, ttl: Duration) -> Self {
+        Self {
+            contract,
+            ttl,
+            orders: Mutex::new(HashMap::new()),
+            config: Mutex::new(None),
+            book: Mutex::new(None),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    /// Loads the config and a snapshot of the book made of `orders` before returning, so the
+    /// first reads of a matcher do not wait on the node. Neither load counts as a miss
+    pub async fn new_with_warmup(
+        contract: SparkMarketContract<A>,
+        ttl: Duration,
+        orders: Vec<Bits256>,
+    ) -> anyhow::Result<Self> {
+        let cached = Self::new(contract, ttl);
+        *cached.book.lock().unwrap() = Some((orders, None));
+        cached.refresh().await?;
+        Ok(cached)
+    }
+
+    /// Reloads the config and, after `new_with_warmup`, the book, whether or not they expired
+    pub async fn refresh(&self) -> anyhow::Result<()> {
+        let config = self.contract.config().await?;
+        *self.config.lock().unwrap() = Some((Instant::now(), config));
+
+        let orders = self
+            .book
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|book| book.0.clone());
+        if let Some(orders) = orders {
+            let snapshot = self.contract.book_snapshot(orders.clone()).await?;
+            *self.book.lock().unwrap() = Some((orders, Some((Instant::now(), snapshot))));
+        }
+        Ok(())
+    }
+
+    /// The book of the orders given to `new_with_warmup`, filled and cancelled orders left out
+    pub async fn book(&self) -> anyhow::Result<BookSnapshot> {
+        let cached = self.book.lock().unwrap().clone();
+        let Some((orders, snapshot)) = cached else {
+            anyhow::bail!("No book to read, the cache was not made with new_with_warmup");
+        };
+        if let Some((fetched_at, snapshot)) = snapshot {
+            if fetched_at.elapsed() < self.ttl {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(snapshot);
+            }
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let snapshot = self.contract.book_snapshot(orders.clone()).await?;
+        *self.book.lock().unwrap() = Some((orders, Some((Instant::now(), snapshot.clone()))));
+        Ok(snapshot)
+    }
+
+    pub async fn book_depth(&self) -> anyhow::Result<BookDepth> {
+        Ok(self.book().await?.depth())
+    }
+
+    /// The wrapped market, calls made through it bypass the cache
+    pub fn contract(&self) -> &SparkMarketContract<A> {
+        &self.contract
+    }
+
+    pub async fn order_by_id(&self, id: Bits256) -> anyhow::Result<Option<OrderDetails>> {
+        let cached = self.orders.lock().unwrap().get(&id.0).cloned();
+        if let Some((fetched_at, order)) = cached {
+            if fetched_at.elapsed() < self.ttl {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(order);
+            }
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let order = self.contract.order_by_id(id).await?;
+        self.orders
+            .lock()
+            .unwrap()
+            .insert(id.0, (Instant::now(), order.clone()));
+        Ok(order)
+    }
+
+    pub async fn config(&self) -> anyhow::Result<MarketConfig> {
+        let cached = self.config.lock().unwrap().clone();
+        if let Some((fetched_at, config)) = cached {
+            if fetched_at.elapsed() < self.ttl {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(config);
+            }
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let config = self.contract.config().await?;
+        *self.config.lock().unwrap() = Some((Instant::now(), config.clone()));
+        Ok(config)
+    }
+
+    pub async fn cancel_order(&self, order_id: Bits256) -> anyhow::Result<CallResponse<()>> {
+        self.invalidating(&[order_id], self.contract.cancel_order(order_id))
+            .await
+    }
+
+    pub async fn match_order_pair(
+        &self,
+        order_id0: Bits256,
+        order_id1: Bits256,
+    ) -> anyhow::Result<CallResponse<()>> {
+        self.invalidating(
+            &[order_id0, order_id1],
+            self.contract.match_order_pair(order_id0, order_id1),
+        )
+        .await
+    }
+
+    pub async fn match_order_many(&self, orders: Vec<Bits256>) -> anyhow::Result<CallResponse<()>> {
+        self.invalidating(&orders, self.contract.match_order_many(orders.clone()))
+            .await
+    }
+
+    pub async fn fulfill_many(
+        &self,
+        amount: u64,
+        order_type: OrderType,
+        limit_type: LimitType,
+        price: u64,
+        slippage: u64,
+        orders: Vec<Bits256>,
     ) -> anyhow::Result<CallResponse<Bits256>> {
-        Ok(self
-            .instance
-            .methods()
-            .order_id(order_type, owner, price, block_height, order_height)
-            .simulate(Execution::StateReadOnly)
-            .await?)
+        self.invalidating(
+            &orders,
+            self.contract.fulfill_many(
+                amount,
+                order_type,
+                limit_type,
+                price,
+                slippage,
+                orders.clone(),
+            ),
+        )
+        .await
+    }
+
+    /// Runs `call` with `orders` dropped before it and again once it returns, since a read made
+    /// while it was in flight may have cached them as they were before it
+    async fn invalidating<T>(
+        &self,
+        orders: &[Bits256],
+        call: impl Future<Output = anyhow::Result<T>>,
+    ) -> anyhow::Result<T> {
+        self.invalidate(orders);
+        let response = call.await;
+        self.invalidate(orders);
+        response
+    }
+
+    /// Drops the cached `orders` and the book snapshot if it holds any of them, for changes
+    /// made through `contract()` or by other accounts
+    pub fn invalidate(&self, orders: &[Bits256]) {
+        let mut cached = self.orders.lock().unwrap();
+        for id in orders {
+            cached.remove(&id.0);
+        }
+
+        if let Some((book_orders, snapshot)) = self.book.lock().unwrap().as_mut() {
+            if orders.iter().any(|id| book_orders.contains(id)) {
+                *snapshot = None;
+            }
+        }
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
     }
 }
@@ -0,0 +1,165 @@
+//! Amount, price and decimal arithmetic shared by the SDK helpers
+//!
+//! Products are taken in `u128` and only narrowed to `u64` once the result is known, so a
+//! value which overflows a `u64` along the way but fits at the end is still computed exactly,
+//! and one which does not fit is reported instead of wrapping.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundingMode {
+    Floor,
+    Ceil,
+}
+
+/// `value * mul_to / div_to`, rounded as `rounding` asks
+pub fn mul_div(
+    value: u64,
+    mul_to: u64,
+    div_to: u64,
+    rounding: RoundingMode,
+) -> anyhow::Result<u64> {
+    let result = div(value as u128 * mul_to as u128, div_to as u128, rounding)?;
+    narrow(result, || format!("{} * {} / {}", value, mul_to, div_to))
+}
+
+/// The divisor turning `amount * price` into quote units,
+/// `10.pow(base_decimals + price_decimals - quote_decimals)`
+pub fn quote_scale(
+    base_decimals: u32,
+    quote_decimals: u32,
+    price_decimals: u32,
+) -> anyhow::Result<u128> {
+    let exponent = (base_decimals + price_decimals)
+        .checked_sub(quote_decimals)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "UnsupportedDecimals: quote decimals {} exceed base decimals {} plus price decimals {}",
+                quote_decimals,
+                base_decimals,
+                price_decimals
+            )
+        })?;
+    10_u128.checked_pow(exponent).ok_or_else(|| {
+        anyhow::anyhow!(
+            "UnsupportedDecimals: 10^{} does not fit in a u128",
+            exponent
+        )
+    })
+}
+
+/// The quote `amount` of base is worth at `price`
+pub fn quote_of_base_amount(
+    amount: u64,
+    price: u64,
+    base_decimals: u32,
+    quote_decimals: u32,
+    price_decimals: u32,
+    rounding: RoundingMode,
+) -> anyhow::Result<u64> {
+    let scale = quote_scale(base_decimals, quote_decimals, price_decimals)?;
+    let result = div(amount as u128 * price as u128, scale, rounding)?;
+    narrow(result, || {
+        format!("quote of {} base at price {}", amount, price)
+    })
+}
+
+/// The base `quote` buys at `price`, the inverse of [`quote_of_base_amount`]
+pub fn base_of_quote_amount(
+    quote: u64,
+    price: u64,
+    base_decimals: u32,
+    quote_decimals: u32,
+    price_decimals: u32,
+    rounding: RoundingMode,
+) -> anyhow::Result<u64> {
+    let scale = quote_scale(base_decimals, quote_decimals, price_decimals)?;
+    let product = (quote as u128).checked_mul(scale).ok_or_else(|| {
+        anyhow::anyhow!(
+            "AmountOverflow: {} quote scaled by {} does not fit in a u128",
+            quote,
+            scale
+        )
+    })?;
+    let result = div(product, price as u128, rounding)?;
+    narrow(result, || {
+        format!("base of {} quote at price {}", quote, price)
+    })
+}
+
+/// Adds `amounts`, failing rather than wrapping once the total leaves the `u64` range
+pub fn checked_sum(amounts: &[u64]) -> anyhow::Result<u64> {
+    let total = amounts.iter().map(|amount| *amount as u128).sum::<u128>();
+    narrow(total, || {
+        let amounts: Vec<String> = amounts.iter().map(u64::to_string).collect();
+        amounts.join(" + ")
+    })
+}
+
+pub fn scaled_price(price: u64, price_decimals: u32) -> anyhow::Result<u64> {
+    Ok(Price::from_human(price, price_decimals)?.scaled())
+}
+
+fn div(product: u128, div_to: u128, rounding: RoundingMode) -> anyhow::Result<u128> {
+    if div_to == 0 {
+        anyhow::bail!("DivisionByZero: cannot divide {} by zero", product);
+    }
+    let mut result = product / div_to;
+    if rounding == RoundingMode::Ceil && result * div_to < product {
+        result += 1;
+    }
+    Ok(result)
+}
+
+fn narrow(value: u128, describe: impl FnOnce() -> String) -> anyhow::Result<u64> {
+    u64::try_from(value).map_err(|_| {
+        anyhow::anyhow!(
+            "AmountOverflow: {} = {} does not fit in a u64",
+            describe(),
+            value
+        )
+    })
+}
+
+/// A price in quote units per whole base unit, scaled by `10.pow(decimals)` as the market expects
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Price {
+    scaled: u64,
+    decimals: u32,
+}
+
+impl Price {
+    /// Wraps a price which is already scaled by `decimals`
+    pub fn new(scaled: u64, decimals: u32) -> anyhow::Result<Self> {
+        if 10_u64.checked_pow(decimals).is_none() {
+            anyhow::bail!("PriceDecimalsTooLarge: {}", decimals);
+        }
+        Ok(Self { scaled, decimals })
+    }
+
+    /// Scales a whole-unit price, e.g. `70_000` quote per base, by `decimals`
+    pub fn from_human(value: u64, decimals: u32) -> anyhow::Result<Self> {
+        let scaled = 10_u64
+            .checked_pow(decimals)
+            .and_then(|scale| value.checked_mul(scale))
+            .ok_or_else(|| anyhow::anyhow!("PriceOverflow: {} at {} decimals", value, decimals))?;
+        Ok(Self { scaled, decimals })
+    }
+
+    pub fn scaled(&self) -> u64 {
+        self.scaled
+    }
+
+    pub fn decimals(&self) -> u32 {
+        self.decimals
+    }
+
+    /// The price in whole quote units per base unit
+    pub fn to_human(&self) -> f64 {
+        self.scaled as f64 / 10_u64.pow(self.decimals) as f64
+    }
+}
+
+impl From<Price> for u64 {
+    fn from(price: Price) -> Self {
+        price.scaled
+    }
+}
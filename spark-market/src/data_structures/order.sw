@@ -56,6 +56,8 @@ impl Order {
         }
     }
 
+    /// The order's key in storage. The market's contract id is hashed in with the owner as a
+    /// domain separator, so equal orders on different markets get different ids.
     pub fn id(self) -> b256 {
         sha256((
             sha256((ContractId::this(), self.owner)),
@@ -25,6 +25,10 @@ pub enum OrderError {
     ZeroUnlockAmount: (),
     ZeroTransferAmount: (),
     FailedToRemove: b256,
+    PriceOutOfBand: (u64, u64),
+    InvalidTick: (u64, u64),
+    InvalidDeadline: u32,
+    OrderNotExpired: b256,
 }
 
 pub enum MatchError {
@@ -32,6 +36,7 @@ pub enum MatchError {
     CantMatchMany: (),
     CantFulfillMany: (),
     CantFulfillFOK: (),
+    WouldCross: b256,
 }
 
 pub enum AuthError {
@@ -26,6 +26,18 @@ pub struct OpenOrderEvent {
     pub liquid_quote: u64,
 }
 
+pub struct CancelExpiredOrderEvent {
+    pub order_id: b256,
+    pub keeper: Identity,
+    pub keeper_reward: u64,
+}
+
+pub struct ClaimKeeperRewardsEvent {
+    pub keeper: Identity,
+    pub asset: AssetId,
+    pub amount: u64,
+}
+
 pub struct CancelOrderEvent {
     pub order_id: b256,
     pub user: Identity,
@@ -71,3 +83,42 @@ pub struct SetProtocolFeeEvent {
 pub struct SetMatcherRewardEvent {
     pub amount: u64,
 }
+
+pub struct ProtocolFeeChangeEvent {
+    pub old_fee: Vec<ProtocolFee>,
+    pub new_fee: Vec<ProtocolFee>,
+    pub changed_by: Identity,
+}
+
+pub struct MatcherFeeChangeEvent {
+    pub old_fee: u64,
+    pub new_fee: u64,
+    pub changed_by: Identity,
+}
+
+pub struct SetDustThresholdEvent {
+    pub amount: u64,
+}
+
+pub struct SetMaxPriceDeviationEvent {
+    pub bps: u64,
+}
+
+pub struct SetTickSizeEvent {
+    pub tick: u64,
+}
+
+pub struct SetMakerPriceExecutionEvent {
+    pub enabled: bool,
+}
+
+pub struct SetFeeExemptEvent {
+    pub user: Identity,
+    pub exempt: bool,
+}
+
+pub struct OrderDeadlineEvent {
+    pub order_id: b256,
+    pub deadline: u32,
+    pub keeper_reward: u64,
+}
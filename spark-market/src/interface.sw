@@ -21,9 +21,34 @@ abi SparkMarket {
     #[storage(read, write)]
     fn open_order(amount: u64, order_type: OrderType, price: u64) -> b256;
 
+    /// Only checks the new order against the given `orders`, the market keeps no best prices, so a
+    /// crossing order left out of `orders` is not detected and the new order may still cross.
+    #[storage(read, write)]
+    fn open_order_post_only(
+        amount: u64,
+        order_type: OrderType,
+        price: u64,
+        orders: Vec<b256>,
+    ) -> b256;
+
+    #[storage(read, write)]
+    fn open_order_with_deadline(
+        amount: u64,
+        order_type: OrderType,
+        price: u64,
+        deadline: u32,
+        keeper_reward: u64,
+    ) -> b256;
+
     #[storage(read, write)]
     fn cancel_order(order_id: b256);
 
+    #[storage(read, write)]
+    fn cancel_expired(order_id: b256);
+
+    #[storage(read, write)]
+    fn claim_keeper_rewards();
+
     #[storage(read, write)]
     fn match_order_pair(order0_id: b256, order1_id: b256);
 
@@ -44,23 +69,65 @@ abi SparkMarket {
     #[storage(write)]
     fn set_epoch(epoch: u64, epoch_duration: u64);
 
-    #[storage(write)]
+    #[storage(read, write)]
     fn set_protocol_fee(protocol_fee: Vec<ProtocolFee>);
 
     #[storage(read, write)]
     fn set_matcher_fee(amount: u64);
+
+    #[storage(read, write)]
+    fn set_dust_threshold(amount: u64);
+
+    #[storage(read, write)]
+    fn set_max_price_deviation_bps(bps: u64);
+
+    #[storage(read, write)]
+    fn set_tick_size(tick: u64);
+
+    #[storage(read, write)]
+    fn add_fee_exempt(user: Identity);
+
+    #[storage(read, write)]
+    fn remove_fee_exempt(user: Identity);
+
+    #[storage(read, write)]
+    fn set_maker_price_execution(enabled: bool);
 }
 
 abi SparkMarketInfo {
     #[storage(read)]
     fn account(user: Identity) -> Account;
 
+    #[storage(read)]
+    fn dust_threshold() -> u64;
+
     #[storage(read)]
     fn get_epoch() -> (u64, u64);
 
+    #[storage(read)]
+    fn is_fee_exempt(user: Identity) -> bool;
+
+    #[storage(read)]
+    fn keeper_rewards(keeper: Identity) -> u64;
+
+    #[storage(read)]
+    fn last_trade_price() -> u64;
+
     #[storage(read)]
     fn matcher_fee() -> u64;
 
+    #[storage(read)]
+    fn max_price_deviation_bps() -> u64;
+
+    #[storage(read)]
+    fn tick_size() -> u64;
+
+    #[storage(read)]
+    fn maker_price_execution() -> bool;
+
+    #[storage(read)]
+    fn order_height() -> u64;
+
     #[storage(read)]
     fn protocol_fee() -> Vec<ProtocolFee>;
 
@@ -73,9 +140,15 @@ abi SparkMarketInfo {
     #[storage(read)]
     fn order(order: b256) -> Option<Order>;
 
+    #[storage(read)]
+    fn order_deadline(order: b256) -> Option<(u32, u64)>;
+
     #[storage(read)]
     fn user_orders(user: Identity) -> Vec<b256>;
 
+    #[storage(read)]
+    fn user_orders_paginated(user: Identity, offset: u64, limit: u64) -> Vec<b256>;
+
     #[storage(read)]
     fn order_change_info(order_id: b256) -> Vec<OrderChangeInfo>;
 
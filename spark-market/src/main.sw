@@ -21,12 +21,22 @@ use ::data_structures::{
 };
 use ::errors::{AccountError, AssetError, AuthError, MatchError, OrderError, ValueError};
 use ::events::{
+    CancelExpiredOrderEvent,
     CancelOrderEvent,
+    ClaimKeeperRewardsEvent,
     DepositEvent,
+    MatcherFeeChangeEvent,
     OpenOrderEvent,
+    OrderDeadlineEvent,
+    ProtocolFeeChangeEvent,
+    SetDustThresholdEvent,
     SetEpochEvent,
+    SetFeeExemptEvent,
+    SetMakerPriceExecutionEvent,
     SetMatcherRewardEvent,
+    SetMaxPriceDeviationEvent,
     SetProtocolFeeEvent,
+    SetTickSizeEvent,
     TradeOrderEvent,
     WithdrawEvent,
 };
@@ -41,6 +51,7 @@ use std::{
     hash::Hash,
     storage::storage_vec::*,
     tx::tx_id,
+    u128::U128,
 };
 
 use sway_libs::reentrancy::*;
@@ -78,6 +89,22 @@ storage {
     epoch_duration: u64 = 2629800,
     // Order height
     order_height: u64 = 0,
+    // Partially filled orders left with less than this amount are cancelled and refunded
+    dust_threshold: u64 = 0,
+    // Maximum deviation of a new order price from the last traded price in basis points, 0 disables the band
+    max_price_deviation_bps: u64 = 0,
+    // Price of the most recent trade
+    last_trade_price: u64 = 0,
+    // New order prices must be a multiple of this value, 0 disables the grid
+    tick_size: u64 = 0,
+    // Crossed orders trade at the price of the older (resting) order instead of the sell price
+    maker_price_execution: bool = false,
+    // Deadline block and keeper reward of orders a keeper may cancel once expired
+    order_deadlines: StorageMap<b256, (u32, u64)> = StorageMap {},
+    // Quote rewards accrued by keepers and not yet claimed
+    keeper_rewards: StorageMap<Identity, u64> = StorageMap {},
+    // Accounts whose orders pay no matcher or protocol fees
+    fee_exempt: StorageMap<Identity, bool> = StorageMap {},
 }
 
 impl SparkMarket for Contract {
@@ -151,6 +178,81 @@ impl SparkMarket for Contract {
         open_order_internal(amount, order_type, price, storage.matcher_fee.read())
     }
 
+    /// @notice Opens a new order that is only allowed to rest on the book as a maker.
+    /// @dev Reverts with `WouldCross` if any of the given resting orders on the opposite side would match the new order.
+    ///      Orders that no longer exist or sit on the same side are skipped.
+    ///      Only the given orders are checked, a crossing order the caller leaves out goes undetected and the new order may cross it.
+    /// @param amount The amount of the asset to be used in the order.
+    /// @param order_type The type of the order being created (e.g., buy or sell).
+    /// @param price The price at which the order should be placed.
+    /// @param orders The resting orders to check the new order against, usually the best levels of the opposite side.
+    /// @return b256 The unique identifier of the newly opened order.
+    #[storage(read, write)]
+    fn open_order_post_only(
+        amount: u64,
+        order_type: OrderType,
+        price: u64,
+        orders: Vec<b256>,
+    ) -> b256 {
+        reentrancy_guard();
+
+        let mut i = 0;
+        while i < orders.len() {
+            let id = orders.get(i).unwrap();
+            let order = storage.orders.get(id).try_read();
+            if order.is_some() {
+                let order = order.unwrap();
+                let crosses = match order_type {
+                    OrderType::Buy => order.order_type == OrderType::Sell && order.price <= price,
+                    OrderType::Sell => order.order_type == OrderType::Buy && order.price >= price,
+                };
+                require(!crosses, MatchError::WouldCross(id));
+            }
+            i += 1;
+        }
+
+        open_order_internal(amount, order_type, price, storage.matcher_fee.read())
+    }
+
+    /// @notice Opens a new order that any keeper may cancel once the deadline block has passed.
+    /// @dev The keeper reward is locked from the caller's liquid quote balance and paid to the keeper that cancels the order.
+    ///      It is returned to the owner if the order is filled or cancelled by the owner before then.
+    /// @param amount The amount of the asset to be used in the order.
+    /// @param order_type The type of the order being created (e.g., buy or sell).
+    /// @param price The price at which the order should be placed.
+    /// @param deadline The last block at which the order is still valid. It must be in the future.
+    /// @param keeper_reward The quote amount paid to the keeper that cancels the expired order.
+    /// @return b256 The unique identifier of the newly opened order.
+    #[storage(read, write)]
+    fn open_order_with_deadline(
+        amount: u64,
+        order_type: OrderType,
+        price: u64,
+        deadline: u32,
+        keeper_reward: u64,
+    ) -> b256 {
+        reentrancy_guard();
+
+        require(deadline > block_height(), OrderError::InvalidDeadline(deadline));
+
+        let order_id = open_order_internal(amount, order_type, price, storage.matcher_fee.read());
+
+        if keeper_reward > 0 {
+            let user = msg_sender().unwrap();
+            let mut account = storage.account.get(user).read();
+            account.lock_amount(keeper_reward, AssetType::Quote);
+            storage.account.insert(user, account);
+        }
+        storage.order_deadlines.insert(order_id, (deadline, keeper_reward));
+
+        log(OrderDeadlineEvent {
+            order_id,
+            deadline,
+            keeper_reward,
+        });
+        order_id
+    }
+
     /// @notice Cancels an existing order with the specified order ID.
     /// @param order_id The unique identifier of the order to be canceled.
     /// @return None - The function does not return a value.
@@ -161,6 +263,67 @@ impl SparkMarket for Contract {
         cancel_order_internal(order_id);
     }
 
+    /// @notice Cancels an order opened with a deadline once the deadline block has passed.
+    /// @dev Callable by anyone. The order is refunded to its owner and the keeper reward accrues to the caller until claimed.
+    /// @param order_id The unique identifier of the expired order.
+    /// @return None - The function does not return a value.
+    #[storage(read, write)]
+    fn cancel_expired(order_id: b256) {
+        reentrancy_guard();
+
+        let order = storage.orders.get(order_id).try_read();
+        require(order.is_some(), OrderError::OrderNotFound(order_id));
+        let order = order.unwrap();
+
+        let deadline = storage.order_deadlines.get(order_id).try_read();
+        require(
+            deadline.is_some() && block_height() > deadline.unwrap().0,
+            OrderError::OrderNotExpired(order_id),
+        );
+        let (_, keeper_reward) = deadline.unwrap();
+
+        let keeper = msg_sender().unwrap();
+        // An owner cancelling their own order gets the reward back through the refund
+        if keeper != order.owner && keeper_reward > 0 {
+            let _ = storage.order_deadlines.remove(order_id);
+            let mut owner_account = storage.account.get(order.owner).read();
+            owner_account.locked.debit(keeper_reward, AssetType::Quote);
+            storage.account.insert(order.owner, owner_account);
+            let accrued = storage.keeper_rewards.get(keeper).try_read().unwrap_or(0);
+            storage.keeper_rewards.insert(keeper, accrued + keeper_reward);
+        }
+
+        refund_order(order_id, order, keeper);
+
+        log(CancelExpiredOrderEvent {
+            order_id,
+            keeper,
+            keeper_reward,
+        });
+    }
+
+    /// @notice Transfers all keeper rewards accrued by the caller to the caller.
+    /// @return None - The function does not return a value.
+    #[storage(read, write)]
+    fn claim_keeper_rewards() {
+        reentrancy_guard();
+
+        let keeper = msg_sender().unwrap();
+        let amount = storage.keeper_rewards.get(keeper).try_read().unwrap_or(0);
+        require(amount > 0, ValueError::InvalidAmount);
+
+        let _ = storage.keeper_rewards.remove(keeper);
+
+        let asset = get_asset_id(AssetType::Quote);
+        transfer(keeper, asset, amount);
+
+        log(ClaimKeeperRewardsEvent {
+            keeper,
+            asset,
+            amount,
+        });
+    }
+
     /// @notice Matches two orders identified by their respective order IDs.
     /// @param order0_id The unique identifier of the first order to be matched.
     /// @param order1_id The unique identifier of the second order to be matched.
@@ -376,11 +539,12 @@ impl SparkMarket for Contract {
     /// @notice Sets the protocol fees based on volume thresholds.
     /// @dev This function allows the contract owner to set a list of protocol fees.
     ///      It ensures that the first fee in the list has a volume threshold of zero and that the fees are sorted by volume threshold.
-    ///      The function is restricted to the contract owner and logs an event after the protocol fees are set.
+    ///      The function is restricted to the contract owner and logs an event after the protocol fees are set,
+    ///      followed by a `ProtocolFeeChangeEvent` with the previous fees and the caller.
     /// @param protocol_fee A vector of 'ProtocolFee' structures that define the fee rates and their corresponding volume thresholds.
     ///                     The first element must have a volume threshold of zero, and the list must be sorted by volume threshold.
     /// @return None - The function does not return a value.
-    #[storage(write)]
+    #[storage(read, write)]
     fn set_protocol_fee(protocol_fee: Vec<ProtocolFee>) {
         only_owner();
 
@@ -398,29 +562,157 @@ impl SparkMarket for Contract {
                 .is_volume_threshold_sorted(),
             ValueError::InvalidFeeSorting,
         );
+        let old_fee = storage.protocol_fee.load_vec();
         storage.protocol_fee.store_vec(protocol_fee);
 
         log(SetProtocolFeeEvent { protocol_fee });
+        log(ProtocolFeeChangeEvent {
+            old_fee,
+            new_fee: protocol_fee,
+            changed_by: msg_sender().unwrap(),
+        });
     }
 
     /// @notice Sets the matcher fee to a specified amount.
     /// @dev This function allows the contract owner to update the matcher fee.
     ///      It checks that the new fee amount is different from the current one to avoid redundant updates.
-    ///      The function is restricted to the contract owner and logs an event after the matcher fee is set.
+    ///      The function is restricted to the contract owner and logs an event after the matcher fee is set,
+    ///      followed by a `MatcherFeeChangeEvent` with the previous fee and the caller.
     /// @param amount The new matcher fee amount to be set. It must be different from the current matcher fee.
     /// @return None - The function does not return a value.
     #[storage(read, write)]
     fn set_matcher_fee(amount: u64) {
+        only_owner();
+        let old_fee = storage.matcher_fee.read();
+        require(amount != old_fee, ValueError::InvalidValueSame);
+        storage.matcher_fee.write(amount);
+
+        log(SetMatcherRewardEvent { amount });
+        log(MatcherFeeChangeEvent {
+            old_fee,
+            new_fee: amount,
+            changed_by: msg_sender().unwrap(),
+        });
+    }
+
+    /// @notice Sets the dust threshold to a specified amount.
+    /// @dev This function allows the contract owner to update the dust threshold.
+    ///      When a partial match leaves an order with a residual amount below the threshold,
+    ///      the residual is cancelled and its locked funds are refunded to the owner's liquid balance.
+    ///      The function is restricted to the contract owner and logs an event after the dust threshold is set.
+    /// @param amount The new dust threshold in base asset units. It must be different from the current dust threshold.
+    /// @return None - The function does not return a value.
+    #[storage(read, write)]
+    fn set_dust_threshold(amount: u64) {
         only_owner();
         require(
             amount != storage
-                .matcher_fee
-                .read(),
+                .dust_threshold
+                .try_read()
+                .unwrap_or(0),
             ValueError::InvalidValueSame,
         );
-        storage.matcher_fee.write(amount);
+        storage.dust_threshold.write(amount);
 
-        log(SetMatcherRewardEvent { amount });
+        log(SetDustThresholdEvent { amount });
+    }
+
+    /// @notice Sets the maximum price deviation allowed for new orders.
+    /// @dev This function allows the contract owner to update the price band around the last traded price.
+    ///      New orders priced further than `bps` basis points from the last traded price revert with `PriceOutOfBand`.
+    ///      A value of zero disables the band. Before the first trade any price is accepted.
+    /// @param bps The new maximum deviation in basis points. It must be different from the current value.
+    /// @return None - The function does not return a value.
+    #[storage(read, write)]
+    fn set_max_price_deviation_bps(bps: u64) {
+        only_owner();
+        require(
+            bps != storage
+                .max_price_deviation_bps
+                .try_read()
+                .unwrap_or(0),
+            ValueError::InvalidValueSame,
+        );
+        storage.max_price_deviation_bps.write(bps);
+
+        log(SetMaxPriceDeviationEvent { bps });
+    }
+
+    /// @notice Sets the price grid for new orders.
+    /// @dev This function allows the contract owner to update the tick size.
+    ///      New orders whose price is not a multiple of `tick` revert with `InvalidTick`.
+    ///      A value of zero disables the grid. Existing orders are not affected.
+    /// @param tick The new tick size in price units. It must be different from the current tick size.
+    /// @return None - The function does not return a value.
+    #[storage(read, write)]
+    fn set_tick_size(tick: u64) {
+        only_owner();
+        require(
+            tick != storage
+                .tick_size
+                .try_read()
+                .unwrap_or(0),
+            ValueError::InvalidValueSame,
+        );
+        storage.tick_size.write(tick);
+
+        log(SetTickSizeEvent { tick });
+    }
+
+    /// @notice Exempts an account from the matcher and protocol fees.
+    /// @dev This function allows the contract owner to add an account, e.g. a market maker, to the fee-exempt list.
+    ///      Orders the account opens from then on carry no matcher or protocol fee, so none is locked or charged when they match.
+    ///      Orders opened before the account was added keep their fees.
+    /// @param user The account to exempt. It must not be exempt already.
+    /// @return None - The function does not return a value.
+    #[storage(read, write)]
+    fn add_fee_exempt(user: Identity) {
+        only_owner();
+        require(!is_fee_exempt(user), ValueError::InvalidValueSame);
+        storage.fee_exempt.insert(user, true);
+
+        log(SetFeeExemptEvent {
+            user,
+            exempt: true,
+        });
+    }
+
+    /// @notice Removes an account from the fee-exempt list.
+    /// @dev This function allows the contract owner to make an exempt account pay fees again.
+    ///      Orders the account opens from then on carry the matcher and protocol fees; orders opened while exempt stay fee-free.
+    /// @param user The account to remove. It must be exempt.
+    /// @return None - The function does not return a value.
+    #[storage(read, write)]
+    fn remove_fee_exempt(user: Identity) {
+        only_owner();
+        require(is_fee_exempt(user), ValueError::InvalidValueSame);
+        let _ = storage.fee_exempt.remove(user);
+
+        log(SetFeeExemptEvent {
+            user,
+            exempt: false,
+        });
+    }
+
+    /// @notice Chooses the price crossed orders trade at.
+    /// @dev This function allows the contract owner to switch maker-price execution on or off.
+    ///      When enabled, a match trades at the price of the order opened first, the resting maker, and the price
+    ///      improvement goes to the later taker. When disabled, the default, a match trades at the sell price.
+    /// @param enabled Whether matches trade at the maker price. It must be different from the current setting.
+    /// @return None - The function does not return a value.
+    #[storage(read, write)]
+    fn set_maker_price_execution(enabled: bool) {
+        only_owner();
+        require(
+            enabled != storage
+                .maker_price_execution
+                .try_read()
+                .unwrap_or(false),
+            ValueError::InvalidValueSame,
+        );
+        storage.maker_price_execution.write(enabled);
+
+        log(SetMakerPriceExecutionEvent { enabled });
     }
 }
 
@@ -430,16 +722,56 @@ impl SparkMarketInfo for Contract {
         storage.account.get(user).try_read().unwrap_or(Account::new())
     }
 
+    #[storage(read)]
+    fn dust_threshold() -> u64 {
+        storage.dust_threshold.try_read().unwrap_or(0)
+    }
+
     #[storage(read)]
     fn get_epoch() -> (u64, u64) {
         (storage.epoch.read(), storage.epoch_duration.read())
     }
 
+    #[storage(read)]
+    fn is_fee_exempt(user: Identity) -> bool {
+        is_fee_exempt(user)
+    }
+
+    #[storage(read)]
+    fn keeper_rewards(keeper: Identity) -> u64 {
+        storage.keeper_rewards.get(keeper).try_read().unwrap_or(0)
+    }
+
+    #[storage(read)]
+    fn last_trade_price() -> u64 {
+        storage.last_trade_price.try_read().unwrap_or(0)
+    }
+
     #[storage(read)]
     fn matcher_fee() -> u64 {
         storage.matcher_fee.read()
     }
 
+    #[storage(read)]
+    fn max_price_deviation_bps() -> u64 {
+        storage.max_price_deviation_bps.try_read().unwrap_or(0)
+    }
+
+    #[storage(read)]
+    fn tick_size() -> u64 {
+        storage.tick_size.try_read().unwrap_or(0)
+    }
+
+    #[storage(read)]
+    fn maker_price_execution() -> bool {
+        storage.maker_price_execution.try_read().unwrap_or(false)
+    }
+
+    #[storage(read)]
+    fn order_height() -> u64 {
+        storage.order_height.read()
+    }
+
     #[storage(read)]
     fn protocol_fee() -> Vec<ProtocolFee> {
         storage.protocol_fee.load_vec()
@@ -460,11 +792,29 @@ impl SparkMarketInfo for Contract {
         storage.orders.get(order).try_read()
     }
 
+    #[storage(read)]
+    fn order_deadline(order: b256) -> Option<(u32, u64)> {
+        storage.order_deadlines.get(order).try_read()
+    }
+
     #[storage(read)]
     fn user_orders(user: Identity) -> Vec<b256> {
         storage.user_orders.get(user).load_vec()
     }
 
+    #[storage(read)]
+    fn user_orders_paginated(user: Identity, offset: u64, limit: u64) -> Vec<b256> {
+        let user_orders = storage.user_orders.get(user);
+        let len = user_orders.len();
+        let mut orders = Vec::new();
+        let mut index = offset;
+        while index < len && orders.len() < limit {
+            orders.push(user_orders.get(index).unwrap().read());
+            index += 1;
+        }
+        orders
+    }
+
     #[storage(read)]
     fn order_change_info(order_id: b256) -> Vec<OrderChangeInfo> {
         storage.order_change_info.get(order_id).load_vec()
@@ -557,8 +907,16 @@ fn lock_order_amount(order: Order) -> u64 {
     }
 }
 
+#[storage(read)]
+fn is_fee_exempt(user: Identity) -> bool {
+    storage.fee_exempt.get(user).try_read().unwrap_or(false)
+}
+
 #[storage(read)]
 fn protocol_fee_user(user: Identity) -> (u64, u64) {
+    if is_fee_exempt(user) {
+        return (0, 0);
+    }
     let volume = storage.user_volumes.get(user).try_read().unwrap_or(UserVolume::new()).get(storage.epoch.read());
     let protocol_fee = storage.protocol_fee.get_volume_protocol_fee(volume);
     (protocol_fee.maker_fee, protocol_fee.taker_fee)
@@ -594,6 +952,31 @@ fn next_order_height() -> u64 {
     order_height
 }
 
+#[storage(read)]
+fn require_price_in_band(price: u64) {
+    let max_deviation = storage.max_price_deviation_bps.try_read().unwrap_or(0);
+    let last_price = storage.last_trade_price.try_read().unwrap_or(0);
+    // Disabled band or cold start before the first trade
+    if max_deviation == 0 || last_price == 0 {
+        return;
+    }
+    // Compare distance / last_price against max_deviation / HUNDRED_PERCENT without dividing
+    let deviation = U128::from((0, distance(price, last_price))) * U128::from((0, HUNDRED_PERCENT));
+    require(
+        deviation <= U128::from((0, max_deviation)) * U128::from((0, last_price)),
+        OrderError::PriceOutOfBand((price, last_price)),
+    );
+}
+
+#[storage(read)]
+fn require_price_on_tick(price: u64) {
+    let tick = storage.tick_size.try_read().unwrap_or(0);
+    require(
+        tick == 0 || price % tick == 0,
+        OrderError::InvalidTick((price, tick)),
+    );
+}
+
 #[storage(read, write)]
 fn open_order_internal(
     amount: u64,
@@ -602,9 +985,16 @@ fn open_order_internal(
     matcher_fee: u64,
 ) -> b256 {
     require(amount > 0, ValueError::InvalidAmount);
+    require_price_in_band(price);
+    require_price_on_tick(price);
 
     let user = msg_sender().unwrap();
     let (protocol_maker_fee, protocol_taker_fee) = protocol_fee_user(user);
+    let matcher_fee = if is_fee_exempt(user) {
+        0
+    } else {
+        matcher_fee
+    };
 
     let asset_type = AssetType::Base;
     let mut order = Order::new(
@@ -692,7 +1082,15 @@ fn cancel_order_internal(order_id: b256) {
     // Only the owner of the order may cancel their order
     require(user == order.owner, AuthError::Unauthorized);
 
-    // Safe to read() because user is the owner of the order
+    refund_order(order_id, order, user);
+}
+
+#[storage(read, write)]
+fn refund_order(order_id: b256, order: Order, sender: Identity) {
+    let user = order.owner;
+    release_keeper_reward(order_id, user);
+
+    // Safe to read() because an open order always has an account
     let mut account = storage.account.get(user).read();
 
     // Order is about to be cancelled, unlock illiquid funds
@@ -712,7 +1110,7 @@ fn cancel_order_internal(order_id: b256) {
         OrderChangeInfo::new(
             OrderChangeType::OrderCancelled,
             block_height(),
-            user,
+            sender,
             tx_id(),
             order.amount,
             0,
@@ -727,6 +1125,22 @@ fn cancel_order_internal(order_id: b256) {
     });
 }
 
+#[storage(read, write)]
+fn release_keeper_reward(order_id: b256, user: Identity) {
+    let deadline = storage.order_deadlines.get(order_id).try_read();
+    if deadline.is_none() {
+        return;
+    }
+    let _ = storage.order_deadlines.remove(order_id);
+
+    let (_, keeper_reward) = deadline.unwrap();
+    if keeper_reward > 0 {
+        let mut account = storage.account.get(user).read();
+        account.unlock_amount(keeper_reward, AssetType::Quote);
+        storage.account.insert(user, account);
+    }
+}
+
 #[storage(read, write)]
 fn increase_user_volume(user: Identity, volume: u64) {
     extend_epoch();
@@ -788,11 +1202,12 @@ fn execute_trade(
     s_order: Order,
     b_order: Order,
     trade_size: u64,
+    trade_price: u64,
     matcher: Identity,
 ) -> (u64, u64, u64) {
     let asset_type = s_order.asset_type;
-    // The volume of the trade for the seller
-    let s_trade_volume = quote_of_base_amount(trade_size, s_order.price);
+    // The volume of the trade for the seller, at the trade price (s_order.price <= trade_price <= b_order.price)
+    let s_trade_volume = quote_of_base_amount(trade_size, trade_price);
     // The volume of the trade reserved by the buyer for the trade size
     let b_trade_volume = quote_of_base_amount(trade_size, b_order.price);
     // The difference in trade volumes between the buyer and seller
@@ -946,12 +1361,20 @@ fn match_order_internal(
         return (MatchResult::ZeroMatch, b256::zero());
     }
 
-    let trade_price = s_order.price;
+    // A resting buy sets the price under maker-price execution, so the improvement goes to the
+    // later sell; otherwise the sell price is used and the buyer is refunded the difference
+    let maker_price_execution = storage.maker_price_execution.try_read().unwrap_or(false);
+    let trade_price = if maker_price_execution && b_order.order_height < s_order.order_height {
+        b_order.price
+    } else {
+        s_order.price
+    };
+    storage.last_trade_price.write(trade_price);
     // Determine trade amounts based on the minimum available
     let trade_size = min(s_order.amount, b_order.amount);
 
     // Execute the trade and update balances
-    let (trade_volume, s_order_matcher_fee, b_order_matcher_fee) = execute_trade(s_order, b_order, trade_size, matcher);
+    let (trade_volume, s_order_matcher_fee, b_order_matcher_fee) = execute_trade(s_order, b_order, trade_size, trade_price, matcher);
 
     increase_user_volume(s_order.owner, trade_volume);
     increase_user_volume(b_order.owner, trade_volume);
@@ -1001,22 +1424,35 @@ fn update_order_storage(
     // Case where the first order is completely filled
     if amount == order0.amount {
         remove_order(order0.owner, id0);
+        release_keeper_reward(id0, order0.owner);
     }
     // Case where the second order is completely filled
     if amount == order1.amount {
         remove_order(order1.owner, id1);
+        release_keeper_reward(id1, order1.owner);
     }
+    let dust_threshold = storage.dust_threshold.try_read().unwrap_or(0);
     if amount != order0.amount {
         // Case where the first order is partially filled
         order0.matcher_fee -= order_matcher_fee0;
         order0.amount -= amount;
         storage.orders.insert(id0, order0);
+        if order0.amount < dust_threshold {
+            // The residual is uneconomical to match, cancel it and refund the owner
+            refund_order(id0, order0, msg_sender().unwrap());
+            return (MatchResult::FullMatch, b256::zero());
+        }
         return (MatchResult::PartialMatch, id0);
     } else if amount != order1.amount {
         // Case where the second order is partially filled
         order1.matcher_fee -= order_matcher_fee1;
         order1.amount -= amount;
         storage.orders.insert(id1, order1);
+        if order1.amount < dust_threshold {
+            // The residual is uneconomical to match, cancel it and refund the owner
+            refund_order(id1, order1, msg_sender().unwrap());
+            return (MatchResult::FullMatch, b256::zero());
+        }
         return (MatchResult::PartialMatch, id1);
     }
     // Case where both orders are fully matched
@@ -0,0 +1,106 @@
+use crate::setup::{setup, Defaults};
+use spark_market_sdk::OrderType;
+
+mod success {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn cancels_order_by_its_parameters() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let deposit_amount = 1_000;
+        let order_amount = 1_000;
+        let price = 70_000_000_000_000_u64;
+        let contract = contract.with_account(&user.wallet).await?;
+        contract.deposit(deposit_amount, assets.base.id).await?;
+        let id = contract
+            .open_order(order_amount, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        let order = contract.order(id).await?.value.unwrap();
+
+        // The offline id matches both the stored id and the market's own computation
+        let offline_id =
+            contract.order_id_offline(OrderType::Sell, user.identity(), price, order.order_height);
+        assert_eq!(offline_id, id);
+        assert_eq!(
+            contract
+                .order_id(
+                    OrderType::Sell,
+                    user.identity(),
+                    price,
+                    order.block_height,
+                    order.order_height
+                )
+                .await?
+                .value,
+            id
+        );
+
+        contract
+            .cancel_by_params(
+                OrderType::Sell,
+                price,
+                order.block_height,
+                order.order_height,
+            )
+            .await?;
+
+        assert!(contract.order(id).await?.value.is_none());
+        assert!(contract
+            .user_orders(user.identity())
+            .await?
+            .value
+            .is_empty());
+
+        Ok(())
+    }
+}
+
+mod revert {
+
+    use super::*;
+
+    #[tokio::test]
+    #[should_panic(expected = "No open order of the caller matches the given parameters")]
+    async fn when_no_order_matches() {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        let price = 70_000_000_000_000_u64;
+        let contract = contract.with_account(&user.wallet).await.unwrap();
+        contract.deposit(1_000, assets.base.id).await.unwrap();
+        let id = contract
+            .open_order(1_000, OrderType::Sell, price)
+            .await
+            .unwrap()
+            .value
+            .order_id;
+        let order = contract.order(id).await.unwrap().value.unwrap();
+
+        // Wrong side
+        contract
+            .cancel_by_params(
+                OrderType::Buy,
+                price,
+                order.block_height,
+                order.order_height,
+            )
+            .await
+            .unwrap();
+    }
+}
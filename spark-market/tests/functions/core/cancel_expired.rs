@@ -0,0 +1,181 @@
+use crate::setup::{create_account, setup, Defaults};
+use fuels::accounts::ViewOnlyAccount;
+use spark_market_sdk::OrderType;
+
+mod success {
+
+    use super::*;
+    use spark_market_sdk::CancelExpiredOrderEvent;
+
+    #[tokio::test]
+    async fn keeper_earns_reward_and_owner_is_refunded() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+        let provider = user0.wallet.try_provider()?;
+
+        let price = 70_000 * 10_u64.pow(defaults.price_decimals);
+        let base_amount = 100_000_u64; // 0.001 BTC
+        let keeper_reward = 10;
+
+        let owner = contract.with_account(&user0.wallet).await?;
+        owner.deposit(base_amount, assets.base.id).await?;
+        owner.deposit(keeper_reward, assets.quote.id).await?;
+
+        let deadline = provider.latest_block_height().await? + 2;
+        let order_id = owner
+            .open_order_with_deadline(base_amount, OrderType::Sell, price, deadline, keeper_reward)
+            .await?
+            .value;
+
+        assert_eq!(
+            contract.account(user0.identity()).await?.value,
+            create_account(0, 0, base_amount, keeper_reward)
+        );
+        assert_eq!(
+            contract.order_deadline(order_id).await?.value,
+            Some((deadline, keeper_reward))
+        );
+
+        provider.produce_blocks(3, None).await?;
+
+        let response = contract
+            .with_account(&user1.wallet)
+            .await?
+            .cancel_expired(order_id)
+            .await?;
+
+        let log = response
+            .decode_logs_with_type::<CancelExpiredOrderEvent>()
+            .unwrap();
+        assert_eq!(
+            *log.first().unwrap(),
+            CancelExpiredOrderEvent {
+                order_id,
+                keeper: user1.identity(),
+                keeper_reward,
+            }
+        );
+
+        assert!(contract.order(order_id).await?.value.is_none());
+        assert!(contract.order_deadline(order_id).await?.value.is_none());
+        assert_eq!(
+            contract.account(user0.identity()).await?.value,
+            create_account(base_amount, 0, 0, 0)
+        );
+        assert_eq!(
+            contract.account(user1.identity()).await?.value,
+            create_account(0, 0, 0, 0)
+        );
+        assert_eq!(
+            contract.keeper_rewards(user1.identity()).await?.value,
+            keeper_reward
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn owner_cancel_returns_keeper_reward() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+        let provider = user0.wallet.try_provider()?;
+
+        let price = 70_000 * 10_u64.pow(defaults.price_decimals);
+        let base_amount = 100_000_u64; // 0.001 BTC
+        let keeper_reward = 10;
+
+        let owner = contract.with_account(&user0.wallet).await?;
+        owner.deposit(base_amount, assets.base.id).await?;
+        owner.deposit(keeper_reward, assets.quote.id).await?;
+
+        let deadline = provider.latest_block_height().await? + 100;
+        let order_id = owner
+            .open_order_with_deadline(base_amount, OrderType::Sell, price, deadline, keeper_reward)
+            .await?
+            .value;
+        owner.cancel_order(order_id).await?;
+
+        assert!(contract.order_deadline(order_id).await?.value.is_none());
+        assert_eq!(
+            contract.account(user0.identity()).await?.value,
+            create_account(base_amount, keeper_reward, 0, 0)
+        );
+
+        Ok(())
+    }
+}
+
+mod revert {
+
+    use super::*;
+
+    #[tokio::test]
+    #[should_panic(expected = "OrderNotExpired")]
+    async fn when_deadline_not_passed() {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+        let provider = user0.wallet.try_provider().unwrap();
+
+        let price = 70_000 * 10_u64.pow(defaults.price_decimals);
+        let owner = contract.with_account(&user0.wallet).await.unwrap();
+        owner.deposit(1_000, assets.base.id).await.unwrap();
+
+        let deadline = provider.latest_block_height().await.unwrap() + 100;
+        let order_id = owner
+            .open_order_with_deadline(1_000, OrderType::Sell, price, deadline, 0)
+            .await
+            .unwrap()
+            .value;
+
+        // Reverts
+        contract
+            .with_account(&user1.wallet)
+            .await
+            .unwrap()
+            .cancel_expired(order_id)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "InvalidDeadline")]
+    async fn when_deadline_in_the_past() {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+        let provider = user0.wallet.try_provider().unwrap();
+
+        let price = 70_000 * 10_u64.pow(defaults.price_decimals);
+        let owner = contract.with_account(&user0.wallet).await.unwrap();
+        owner.deposit(1_000, assets.base.id).await.unwrap();
+
+        let deadline = provider.latest_block_height().await.unwrap();
+
+        // Reverts
+        owner
+            .open_order_with_deadline(1_000, OrderType::Sell, price, deadline, 0)
+            .await
+            .unwrap();
+    }
+}
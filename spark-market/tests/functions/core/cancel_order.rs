@@ -39,7 +39,8 @@ mod success {
         let id = contract
             .open_order(order_amount, /*AssetType::Base,*/ order_type, price)
             .await?
-            .value;
+            .value
+            .order_id;
 
         let user_account = contract.account(owner.identity()).await?.value;
         let expected_account = create_account(deposit_amount - order_amount, 0, order_amount, 0);
@@ -135,7 +136,8 @@ mod success {
             let id = contract
                 .open_order(order_amount, order_type.clone(), price)
                 .await?
-                .value;
+                .value
+                .order_id;
             let expected_id = contract
                 .order_id(
                     order_type.clone(),
@@ -243,7 +245,8 @@ mod success {
         let id = contract
             .open_order(order_amount, order_type.clone(), price)
             .await?
-            .value;
+            .value
+            .order_id;
 
         let user_account = contract.account(owner.identity()).await?.value;
         let expected_account = create_account(0, 0, 0, deposit_amount);
@@ -340,7 +343,8 @@ mod success {
             let id = contract
                 .open_order(order_amount, order_type.clone(), price)
                 .await?
-                .value;
+                .value
+                .order_id;
             let expected_id = contract
                 .order_id(
                     order_type.clone(),
@@ -402,12 +406,113 @@ mod success {
         }
         Ok(())
     }
+
+    #[tokio::test]
+    async fn cancel_oldest_keeps_the_newest() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let price = 70_000_000_000_000_u64;
+        let _ = contract.deposit(100, assets.base.id).await?;
+        let mut ids = Vec::new();
+        for _ in 0..4 {
+            let id = contract
+                .open_order(1, OrderType::Sell, price)
+                .await?
+                .value
+                .order_id;
+            ids.push(id);
+        }
+
+        let cancelled = contract.cancel_oldest(2).await?;
+        assert_eq!(cancelled, ids[..2]);
+
+        let mut orders = contract.user_orders(owner.identity()).await?.value;
+        orders.sort_by_key(|id| ids.iter().position(|opened| opened == id));
+        assert_eq!(orders, ids[2..]);
+        assert!(contract.order(ids[0]).await?.value.is_none());
+        assert!(contract.order(ids[1]).await?.value.is_none());
+
+        let user_account = contract.account(owner.identity()).await?.value;
+        assert_eq!(user_account, create_account(98, 0, 2, 0));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn cancel_and_reopen_amends_price_in_place() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let price = 70_000_000_000_000_u64;
+        let new_price = 71_000_000_000_000_u64;
+        let base_amount = 100_u64;
+        let _ = contract.deposit(base_amount, assets.base.id).await?;
+        let id = contract
+            .open_order(base_amount, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        let wallet_base = owner.balance(&assets.base.id).await;
+
+        let new_id = contract
+            .cancel_and_reopen(id, new_price, base_amount)
+            .await?
+            .value;
+
+        assert!(contract.order(id).await?.value.is_none());
+        let order = contract.order(new_id).await?.value.unwrap();
+        assert_eq!(order.order_type, OrderType::Sell);
+        assert_eq!(order.amount, base_amount);
+        assert_eq!(order.price, new_price);
+        assert_eq!(
+            contract.user_orders(owner.identity()).await?.value,
+            vec![new_id]
+        );
+
+        // The refund covered the new lock, so no base moved in either direction
+        assert_eq!(
+            contract.account(owner.identity()).await?.value,
+            create_account(0, 0, base_amount, 0)
+        );
+        assert_eq!(owner.balance(&assets.base.id).await, wallet_base);
+        assert_eq!(contract.contract_balances().await?.base, base_amount);
+
+        // Growing the order attaches only the difference
+        let newer_id = contract
+            .cancel_and_reopen(new_id, price, base_amount + 40)
+            .await?
+            .value;
+
+        assert_eq!(
+            contract.order(newer_id).await?.value.unwrap().amount,
+            base_amount + 40
+        );
+        assert_eq!(
+            contract.account(owner.identity()).await?.value,
+            create_account(0, 0, base_amount + 40, 0)
+        );
+        assert_eq!(owner.balance(&assets.base.id).await, wallet_base - 40);
+
+        Ok(())
+    }
 }
 
 mod revert {
 
     use super::*;
     use fuels::types::Bits256;
+    use spark_market_sdk::Unauthorized;
 
     #[tokio::test]
     #[should_panic(expected = "OrderNotFound")]
@@ -426,7 +531,6 @@ mod revert {
     }
 
     #[tokio::test]
-    #[should_panic(expected = "Unauthorized")]
     async fn when_user_is_not_owner() {
         let defaults = Defaults::default();
         let (contract, _owner, user, _, _, assets) = setup(
@@ -448,15 +552,54 @@ mod revert {
             .open_order(order_amount, /*AssetType::Base,*/ order_type, price)
             .await
             .unwrap()
-            .value;
+            .value
+            .order_id;
 
         // Revert
-        contract
+        let error = contract
             .with_account(&user.wallet)
             .await
             .unwrap()
             .cancel_order(id)
             .await
-            .unwrap();
+            .unwrap_err();
+        assert!(error.downcast_ref::<Unauthorized>().is_some());
+    }
+
+    #[tokio::test]
+    async fn when_user_is_not_owner_error_names_order_and_caller() {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        let _ = contract.deposit(100, assets.base.id).await.unwrap();
+        let id = contract
+            .open_order(1, OrderType::Sell, 70_000_000_000_000_u64)
+            .await
+            .unwrap()
+            .value
+            .order_id;
+
+        // Revert
+        let error = contract
+            .with_account(&user.wallet)
+            .await
+            .unwrap()
+            .cancel_order(id)
+            .await
+            .unwrap_err();
+
+        let unauthorized = error.downcast_ref::<Unauthorized>().unwrap();
+        assert_eq!(unauthorized.order_id, id);
+        assert_eq!(unauthorized.caller, user.identity());
+        assert!(error.to_string().starts_with("Unauthorized"));
+
+        // The order stays open for its owner
+        assert!(contract.order(id).await.unwrap().value.is_some());
     }
 }
@@ -0,0 +1,128 @@
+use crate::setup::{setup, Defaults, User};
+use fuels::accounts::ViewOnlyAccount;
+use spark_market_sdk::{OrderType, SparkMarketContract};
+
+// Opens a sell order with a deadline for the owner, lets it expire and has the keeper cancel it
+async fn expire_and_cancel(
+    contract: &SparkMarketContract,
+    owner: &User,
+    keeper: &User,
+    base_amount: u64,
+    keeper_reward: u64,
+) -> anyhow::Result<()> {
+    let defaults = Defaults::default();
+    let provider = owner.wallet.try_provider()?;
+    let price = 70_000 * 10_u64.pow(defaults.price_decimals);
+
+    let deadline = provider.latest_block_height().await? + 2;
+    let order_id = contract
+        .with_account(&owner.wallet)
+        .await?
+        .open_order_with_deadline(base_amount, OrderType::Sell, price, deadline, keeper_reward)
+        .await?
+        .value;
+
+    provider.produce_blocks(3, None).await?;
+
+    contract
+        .with_account(&keeper.wallet)
+        .await?
+        .cancel_expired(order_id)
+        .await?;
+
+    Ok(())
+}
+
+mod success {
+
+    use super::*;
+    use spark_market_sdk::ClaimKeeperRewardsEvent;
+
+    #[tokio::test]
+    async fn keeper_claims_rewards_from_expired_orders() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let base_amount = 100_000_u64; // 0.001 BTC
+        let keeper_reward = 10;
+
+        let owner = contract.with_account(&user0.wallet).await?;
+        owner.deposit(2 * base_amount, assets.base.id).await?;
+        owner.deposit(2 * keeper_reward, assets.quote.id).await?;
+
+        // Rewards accrue across every order the keeper cancels
+        expire_and_cancel(&contract, &user0, &user1, base_amount, keeper_reward).await?;
+        expire_and_cancel(&contract, &user0, &user1, base_amount, keeper_reward).await?;
+        assert_eq!(
+            contract.keeper_rewards(user1.identity()).await?.value,
+            2 * keeper_reward
+        );
+
+        let wallet_balance = user1.balance(&assets.quote.id).await;
+
+        let response = contract
+            .with_account(&user1.wallet)
+            .await?
+            .claim_keeper_rewards()
+            .await?;
+
+        let log = response
+            .decode_logs_with_type::<ClaimKeeperRewardsEvent>()
+            .unwrap();
+        assert_eq!(
+            *log.first().unwrap(),
+            ClaimKeeperRewardsEvent {
+                keeper: user1.identity(),
+                asset: assets.quote.id,
+                amount: 2 * keeper_reward,
+            }
+        );
+
+        assert_eq!(contract.keeper_rewards(user1.identity()).await?.value, 0);
+        assert_eq!(
+            user1.balance(&assets.quote.id).await,
+            wallet_balance + 2 * keeper_reward
+        );
+
+        Ok(())
+    }
+}
+
+mod revert {
+
+    use super::*;
+
+    #[tokio::test]
+    #[should_panic(expected = "InvalidAmount")]
+    async fn when_caller_did_not_earn_rewards() {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, user1, user2, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        let owner = contract.with_account(&user0.wallet).await.unwrap();
+        owner.deposit(1_000, assets.base.id).await.unwrap();
+        owner.deposit(10, assets.quote.id).await.unwrap();
+        expire_and_cancel(&contract, &user0, &user1, 1_000, 10)
+            .await
+            .unwrap();
+
+        // Reverts
+        contract
+            .with_account(&user2.wallet)
+            .await
+            .unwrap()
+            .claim_keeper_rewards()
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,67 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use spark_market_sdk::{DeadMansSwitch, OrderType};
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    #[tokio::test]
+    async fn fires_once_after_inactivity() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let maker = contract.with_account(&user.wallet).await?;
+        maker.deposit(1_000, assets.base.id).await?;
+        maker
+            .open_order(1_000, OrderType::Sell, 70_000_000_000_000)
+            .await?;
+
+        // The shutdown pulls the maker's orders from the book
+        let fired = Arc::new(AtomicUsize::new(0));
+        let count = fired.clone();
+        let market = contract.with_account(&user.wallet).await?;
+        let switch = DeadMansSwitch::arm(Duration::from_secs(1), move || async move {
+            count.fetch_add(1, Ordering::SeqCst);
+            market.cancel_oldest(usize::MAX).await.unwrap();
+        });
+
+        // Successful actions within the interval keep the switch from firing
+        for _ in 0..4 {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            switch.watch(maker.deposit(1, assets.base.id)).await?;
+        }
+        assert!(!switch.has_fired());
+        assert_eq!(fired.load(Ordering::SeqCst), 0);
+
+        // A failed action does not count as activity
+        assert!(switch
+            .watch(maker.deposit(1, assets.random.id))
+            .await
+            .is_err());
+
+        tokio::time::sleep(Duration::from_millis(2_500)).await;
+        assert!(switch.has_fired());
+        assert_eq!(fired.load(Ordering::SeqCst), 1);
+        assert!(contract
+            .user_orders(user.identity())
+            .await?
+            .value
+            .is_empty());
+
+        // Staying inactive does not fire it again
+        tokio::time::sleep(Duration::from_millis(1_500)).await;
+        assert_eq!(fired.load(Ordering::SeqCst), 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use fuels::{prelude::AssetId, types::Bytes32};
+    use spark_market_sdk::SparkMarketContract;
+
+    #[tokio::test]
+    async fn deploy_with_receipt_reports_the_create_transaction() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (_contract, owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+        let fee_asset = AssetId::default();
+        let balance = owner.balance(&fee_asset).await;
+
+        let deployment = SparkMarketContract::deploy_with_receipt(
+            assets.base.id,
+            assets.base.decimals,
+            assets.quote.id,
+            assets.quote.decimals,
+            owner.wallet.clone(),
+            defaults.price_decimals,
+            0xFAFBFC,
+        )
+        .await?;
+
+        assert_eq!(
+            &deployment.contract_id,
+            deployment.market.get_instance().contract_id()
+        );
+        assert_ne!(deployment.tx_id, Bytes32::zeroed());
+        assert_eq!(deployment.fee, balance - owner.balance(&fee_asset).await);
+
+        // The handle is connected to the deployed market
+        let config = deployment.market.config().await?;
+        assert_eq!(config.base_asset, assets.base.id);
+        assert_eq!(config.quote_asset, assets.quote.id);
+
+        Ok(())
+    }
+}
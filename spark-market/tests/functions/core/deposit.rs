@@ -5,6 +5,7 @@ mod success {
 
     use super::*;
     use crate::setup::create_account;
+    use fuels::accounts::ViewOnlyAccount;
     use spark_market_sdk::DepositEvent;
 
     #[tokio::test]
@@ -195,6 +196,44 @@ mod success {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn transfers_only_the_funded_asset() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+        let provider = owner.wallet.try_provider()?;
+
+        let deposit_amount = 250;
+
+        let base_balance = owner.balance(&assets.base.id).await;
+        let quote_balance = owner.balance(&assets.quote.id).await;
+        let _ = contract.deposit(deposit_amount, assets.quote.id).await?;
+
+        assert_eq!(owner.balance(&assets.base.id).await, base_balance);
+        assert_eq!(
+            owner.balance(&assets.quote.id).await,
+            quote_balance - deposit_amount
+        );
+        assert_eq!(
+            provider
+                .get_contract_asset_balance(contract.contract_id(), assets.quote.id)
+                .await?,
+            deposit_amount
+        );
+        assert_eq!(
+            provider
+                .get_contract_asset_balance(contract.contract_id(), assets.base.id)
+                .await?,
+            0
+        );
+
+        Ok(())
+    }
 }
 
 mod revert {
@@ -0,0 +1,56 @@
+use crate::setup::{create_account, setup, Defaults};
+use spark_market_sdk::OrderType;
+
+mod success {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn buy_attaches_required_quote() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let base_amount = 100_000_u64; // 0.001 BTC
+        let price = 70_000_000_000_000_u64; // 70,000$ price
+        let quote_amount = price / to_quote_scale * base_amount;
+
+        let contract = contract.with_account(&user.wallet).await?;
+        let (asset, deposit) = contract
+            .required_deposit(base_amount, OrderType::Buy, price)
+            .await?;
+        assert_eq!(asset, assets.quote.id);
+        assert_eq!(deposit, quote_amount);
+
+        let wallet_quote = user.balance(&assets.quote.id).await;
+        let opened = contract
+            .deposit_and_open(OrderType::Buy, price, base_amount)
+            .await?
+            .value;
+
+        // Only the required quote left the wallet, and all of it was locked by the order
+        assert_eq!(
+            user.balance(&assets.quote.id).await,
+            wallet_quote - quote_amount
+        );
+        assert_eq!(opened.base_deposited, 0);
+        assert_eq!(opened.quote_deposited, quote_amount);
+        assert_eq!(
+            contract.account(user.identity()).await?.value,
+            create_account(0, 0, 0, quote_amount)
+        );
+
+        let order = contract.order(opened.order_id).await?.value.unwrap();
+        assert_eq!(order.amount, base_amount);
+        assert_eq!(order.order_type, OrderType::Buy);
+        assert_eq!(order.price, price);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use crate::setup::{create_account, setup, Defaults};
+use spark_market_sdk::{FeeConfirmation, FeeDeclined, OrderType};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+mod revert {
+
+    use super::*;
+
+    // Declines every call and counts how often it was asked
+    fn declining(asked: &Arc<AtomicUsize>) -> Option<FeeConfirmation> {
+        let asked = asked.clone();
+        Some(Arc::new(move |_estimated: u64| {
+            asked.fetch_add(1, Ordering::SeqCst);
+            false
+        }) as FeeConfirmation)
+    }
+
+    fn is_declined(error: &anyhow::Error) -> bool {
+        error.downcast_ref::<FeeDeclined>().is_some()
+    }
+
+    #[tokio::test]
+    async fn multicalls_and_submitted_calls_are_checked() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let price = 70_000_000_000_000_u64;
+        let _ = contract.deposit(10, assets.base.id).await?;
+        let sell = contract
+            .open_order(5, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        let buyer = contract.with_account(&user.wallet).await?;
+        let _ = buyer.deposit(3_500, assets.quote.id).await?;
+        let buy = buyer
+            .open_order(5, OrderType::Buy, price)
+            .await?
+            .value
+            .order_id;
+
+        let asked = Arc::new(AtomicUsize::new(0));
+        let declined = contract
+            .with_account(&owner.wallet)
+            .await?
+            .with_fee_confirmation(declining(&asked));
+
+        let error = declined
+            .deposit_and_open(OrderType::Sell, price, 1)
+            .await
+            .unwrap_err();
+        assert!(is_declined(&error));
+        let error = declined
+            .open_orders(vec![(1, OrderType::Sell, price)])
+            .await
+            .unwrap_err();
+        assert!(is_declined(&error));
+        let error = declined.cancel_oldest(1).await.unwrap_err();
+        assert!(is_declined(&error));
+        let error = declined
+            .cancel_and_reopen(sell, price, 5)
+            .await
+            .unwrap_err();
+        assert!(is_declined(&error));
+        let results = declined
+            .match_order_pairs_concurrent(vec![(sell, buy)], 1)
+            .await?;
+        assert!(is_declined(results[0].as_ref().unwrap_err()));
+
+        // Each path asked once and nothing was sent
+        assert!(contract.order(buy).await?.value.is_some());
+        assert_eq!(asked.load(Ordering::SeqCst), 5);
+        assert_eq!(
+            contract.user_orders(owner.identity()).await?.value,
+            vec![sell]
+        );
+        assert_eq!(
+            contract.account(owner.identity()).await?.value,
+            create_account(5, 0, 5, 0)
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,262 @@
+use crate::setup::{create_account, setup, Defaults};
+use spark_market_sdk::OrderType;
+
+mod success {
+
+    use super::*;
+    use spark_market_sdk::SetFeeExemptEvent;
+
+    #[tokio::test]
+    async fn adds_and_removes_fee_exempt() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, _assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        // Assert precondition of no exemption
+        assert!(!contract.is_fee_exempt(user.identity()).await?.value);
+
+        let response = contract.add_fee_exempt(user.identity()).await?;
+
+        // Log should be emitted when the account is exempted
+        let log = response
+            .decode_logs_with_type::<SetFeeExemptEvent>()
+            .unwrap();
+        let event = log.first().unwrap();
+        assert_eq!(
+            *event,
+            SetFeeExemptEvent {
+                user: user.identity(),
+                exempt: true,
+            }
+        );
+        assert!(contract.is_fee_exempt(user.identity()).await?.value);
+
+        let response = contract.remove_fee_exempt(user.identity()).await?;
+
+        let log = response
+            .decode_logs_with_type::<SetFeeExemptEvent>()
+            .unwrap();
+        let event = log.first().unwrap();
+        assert_eq!(
+            *event,
+            SetFeeExemptEvent {
+                user: user.identity(),
+                exempt: false,
+            }
+        );
+        assert!(!contract.is_fee_exempt(user.identity()).await?.value);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn exempt_taker_pays_no_fees() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, user0, user1, matcher, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let taker_fee = 25;
+        let matcher_fee = 1_000;
+        contract.set_taker_fee(taker_fee, 0).await?;
+        contract.set_matcher_fee(matcher_fee).await?;
+
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let price = 70_000 * 10_u64.pow(defaults.price_decimals);
+        let base_amount = 100_000_u64; // 0.001 BTC
+        let quote_amount = price / to_quote_scale * base_amount;
+        let fee_amount = quote_amount * taker_fee / 10_000;
+
+        // A non-exempt buyer would be charged both fees on the same trade
+        let breakdown = contract
+            .with_account(&user1.wallet)
+            .await?
+            .quote_trade_breakdown(base_amount, price)
+            .await?;
+        assert_eq!(breakdown.taker_fee, fee_amount);
+        assert_eq!(breakdown.matcher_fee, matcher_fee);
+
+        contract.add_fee_exempt(user0.identity()).await?;
+        contract.add_fee_exempt(user1.identity()).await?;
+
+        // The exact trade value is enough for the exempt buyer
+        contract
+            .with_account(&user0.wallet)
+            .await?
+            .deposit(base_amount, assets.base.id)
+            .await?;
+        contract
+            .with_account(&user1.wallet)
+            .await?
+            .deposit(quote_amount, assets.quote.id)
+            .await?;
+
+        let id0 = contract
+            .with_account(&user0.wallet)
+            .await?
+            .open_order(base_amount, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        let id1 = contract
+            .with_account(&user1.wallet)
+            .await?
+            .open_order(base_amount, OrderType::Buy, price)
+            .await?
+            .value
+            .order_id;
+
+        contract
+            .with_account(&matcher.wallet)
+            .await?
+            .match_order_pair(id0, id1)
+            .await?;
+
+        assert_eq!(
+            contract.account(user0.identity()).await?.value,
+            create_account(0, quote_amount, 0, 0)
+        );
+        assert_eq!(
+            contract.account(user1.identity()).await?.value,
+            create_account(base_amount, 0, 0, 0)
+        );
+        assert_eq!(
+            contract.account(owner.identity()).await?.value,
+            create_account(0, 0, 0, 0)
+        );
+        assert_eq!(
+            contract.account(matcher.identity()).await?.value,
+            create_account(0, 0, 0, 0)
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn non_exempt_taker_pays_fees() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, user0, user1, matcher, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let taker_fee = 25;
+        let matcher_fee = 1_000;
+        contract.set_taker_fee(taker_fee, 0).await?;
+        contract.set_matcher_fee(matcher_fee).await?;
+
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let price = 70_000 * 10_u64.pow(defaults.price_decimals);
+        let base_amount = 100_000_u64; // 0.001 BTC
+        let quote_amount = price / to_quote_scale * base_amount;
+        let fee_amount = quote_amount * taker_fee / 10_000;
+
+        // Only the seller is exempt, the buyer stays the control
+        contract.add_fee_exempt(user0.identity()).await?;
+
+        contract
+            .with_account(&user0.wallet)
+            .await?
+            .deposit(base_amount, assets.base.id)
+            .await?;
+        contract
+            .with_account(&user1.wallet)
+            .await?
+            .deposit(quote_amount + fee_amount + matcher_fee, assets.quote.id)
+            .await?;
+
+        let id0 = contract
+            .with_account(&user0.wallet)
+            .await?
+            .open_order(base_amount, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        let id1 = contract
+            .with_account(&user1.wallet)
+            .await?
+            .open_order(base_amount, OrderType::Buy, price)
+            .await?
+            .value
+            .order_id;
+
+        contract
+            .with_account(&matcher.wallet)
+            .await?
+            .match_order_pair(id0, id1)
+            .await?;
+
+        assert_eq!(
+            contract.account(user0.identity()).await?.value,
+            create_account(0, quote_amount, 0, 0)
+        );
+        assert_eq!(
+            contract.account(user1.identity()).await?.value,
+            create_account(base_amount, 0, 0, 0)
+        );
+        assert_eq!(
+            contract.account(owner.identity()).await?.value,
+            create_account(0, fee_amount, 0, 0)
+        );
+        assert_eq!(
+            contract.account(matcher.identity()).await?.value,
+            create_account(0, matcher_fee, 0, 0)
+        );
+
+        Ok(())
+    }
+}
+
+mod revert {
+
+    use super::*;
+
+    #[tokio::test]
+    #[should_panic(expected = "Unauthorized")]
+    async fn reverts_when_non_owner() {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, _) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        // Reverts
+        contract
+            .with_account(&user.wallet)
+            .await
+            .unwrap()
+            .add_fee_exempt(user.identity())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "InvalidValueSame")]
+    async fn reverts_when_removing_non_exempt() {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, _) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        // Reverts
+        contract.remove_fee_exempt(user.identity()).await.unwrap();
+    }
+}
@@ -0,0 +1,194 @@
+use crate::setup::{create_account, setup, Defaults};
+use spark_market_sdk::{FulfillLimits, FulfillStop, LimitType, OrderType};
+
+mod success {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn halts_on_max_quote_spend_before_price_limit() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let base_amount = 100_000_u64; // 0.001 BTC
+        let prices = [
+            70_000_000_000_000_u64, // 70,000$ price
+            71_000_000_000_000_u64, // 71,000$ price
+            72_000_000_000_000_u64, // 72,000$ price
+        ];
+        let quote_deposit = 216_000_000_u64; // 216$, 0.003 BTC at 72,000$
+
+        let maker = contract.with_account(&user0.wallet).await?;
+        maker.deposit(3 * base_amount, assets.base.id).await?;
+        let mut order_ids = Vec::new();
+        for price in prices {
+            order_ids.push(
+                maker
+                    .open_order(base_amount, OrderType::Sell, price)
+                    .await?
+                    .value
+                    .order_id,
+            );
+        }
+
+        let taker = contract.with_account(&user1.wallet).await?;
+        taker.deposit(quote_deposit, assets.quote.id).await?;
+
+        // 70$ takes the first order and 35.5$ half of the second, all three are within the price
+        let max_quote_spend = 105_500_000_u64;
+        let fill = taker
+            .fulfill_many_with_limits(
+                3 * base_amount,
+                OrderType::Buy,
+                LimitType::IOC,
+                prices[2],
+                500,
+                order_ids.clone(),
+                FulfillLimits {
+                    max_quote_spend: Some(max_quote_spend),
+                    ..Default::default()
+                },
+            )
+            .await?
+            .value;
+
+        assert_eq!(fill.stop, FulfillStop::MaxQuoteSpend);
+        assert_eq!(fill.base_filled, base_amount + base_amount / 2);
+        assert_eq!(fill.quote, max_quote_spend);
+
+        assert!(contract.order(order_ids[0]).await?.value.is_none());
+        assert_eq!(
+            contract.order(order_ids[1]).await?.value.unwrap().amount,
+            base_amount / 2
+        );
+        assert_eq!(
+            contract.order(order_ids[2]).await?.value.unwrap().amount,
+            base_amount
+        );
+        assert_eq!(
+            contract.account(user0.identity()).await?.value,
+            create_account(0, max_quote_spend, 3 * base_amount / 2, 0)
+        );
+        assert_eq!(
+            contract.account(user1.identity()).await?.value,
+            create_account(3 * base_amount / 2, quote_deposit - max_quote_spend, 0, 0)
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sell_plans_at_the_maker_price_when_enabled() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let base_amount = 100_000_u64; // 0.001 BTC
+        let buy_price = 72_000_000_000_000_u64; // 72,000$ price
+        let sell_price = 70_000_000_000_000_u64; // 70,000$ price
+        let quote_amount = 72_000_000_u64; // 72$, 0.001 BTC at 72,000$
+
+        contract.set_maker_price_execution(true).await?;
+
+        let maker = contract.with_account(&user0.wallet).await?;
+        maker.deposit(quote_amount, assets.quote.id).await?;
+        let order_id = maker
+            .open_order(base_amount, OrderType::Buy, buy_price)
+            .await?
+            .value
+            .order_id;
+
+        let taker = contract.with_account(&user1.wallet).await?;
+        taker.deposit(base_amount, assets.base.id).await?;
+
+        let fill = taker
+            .fulfill_many_with_limits(
+                base_amount,
+                OrderType::Sell,
+                LimitType::IOC,
+                sell_price,
+                500,
+                vec![order_id],
+                FulfillLimits::default(),
+            )
+            .await?
+            .value;
+
+        // The planned quote is what the market pays at the older buy's price
+        assert_eq!(fill.stop, FulfillStop::Filled);
+        assert_eq!(fill.quote, quote_amount);
+        assert_eq!(
+            contract.account(user1.identity()).await?.value,
+            create_account(0, quote_amount, 0, 0)
+        );
+
+        Ok(())
+    }
+}
+
+mod revert {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn when_min_base_received_is_not_reached() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let base_amount = 100_000_u64; // 0.001 BTC
+        let price = 70_000_000_000_000_u64; // 70,000$ price
+
+        let maker = contract.with_account(&user0.wallet).await?;
+        maker.deposit(base_amount, assets.base.id).await?;
+        let order_id = maker
+            .open_order(base_amount, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+
+        let taker = contract.with_account(&user1.wallet).await?;
+        taker.deposit(70_000_000, assets.quote.id).await?;
+
+        // Only one order is allowed, which holds less than the minimum
+        let result = taker
+            .fulfill_many_with_limits(
+                2 * base_amount,
+                OrderType::Buy,
+                LimitType::IOC,
+                price,
+                0,
+                vec![order_id],
+                FulfillLimits {
+                    min_base_received: Some(2 * base_amount),
+                    max_orders_to_consume: Some(1),
+                    ..Default::default()
+                },
+            )
+            .await;
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .starts_with("MinBaseReceived"));
+        assert_eq!(
+            contract.order(order_id).await?.value.unwrap().amount,
+            base_amount
+        );
+
+        Ok(())
+    }
+}
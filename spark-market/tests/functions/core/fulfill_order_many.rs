@@ -79,7 +79,8 @@ mod success_ioc {
                     .await?
                     .open_order(config.amount, config.order_type, config.price)
                     .await?
-                    .value,
+                    .value
+                    .order_id,
             );
         }
 
@@ -101,8 +102,7 @@ mod success_ioc {
                 100,
                 order_ids,
             )
-            .await?
-            .value;
+            .await?;
 
         let expected_account0 = create_account(base_deposit, quote_delta, 0, 0);
         let expected_account1 = create_account(0, quote_deposit - quote_delta, 0, 0);
@@ -180,7 +180,8 @@ mod success_ioc {
                     .await?
                     .open_order(config.amount, config.order_type, config.price)
                     .await?
-                    .value,
+                    .value
+                    .order_id,
             );
         }
 
@@ -202,8 +203,7 @@ mod success_ioc {
                 100,
                 order_ids,
             )
-            .await?
-            .value;
+            .await?;
 
         let expected_account0 = create_account(base_deposit, quote_delta, 0, quote_locked);
         let expected_account1 = create_account(0, quote_deposit - quote_delta - quote_locked, 0, 0);
@@ -286,7 +286,8 @@ mod success_ioc {
                     .await?
                     .open_order(config.amount, config.order_type, config.price)
                     .await?
-                    .value,
+                    .value
+                    .order_id,
             );
         }
 
@@ -308,8 +309,7 @@ mod success_ioc {
                 100,
                 order_ids,
             )
-            .await?
-            .value;
+            .await?;
 
         let expected_account0 = create_account(base_deposit, quote_delta, 0, quote_locked);
         let expected_account1 = create_account(0, quote_deposit - quote_delta - quote_locked, 0, 0);
@@ -392,7 +392,8 @@ mod success_ioc {
                         .await?
                         .open_order(config.amount, config.order_type, config.price)
                         .await?
-                        .value,
+                        .value
+                        .order_id,
                 );
             }
 
@@ -410,8 +411,7 @@ mod success_ioc {
                     100,
                     order_ids,
                 )
-                .await?
-                .value;
+                .await?;
 
             let user0_account_t1 = contract.account(user0.identity()).await?.value;
             let user1_account_t1 = contract.account(user1.identity()).await?.value;
@@ -510,7 +510,8 @@ mod success_ioc {
                 .await?
                 .open_order(config.amount, config.order_type.clone(), config.price)
                 .await?
-                .value;
+                .value
+                .order_id;
             order_ids.push(order_id);
         }
 
@@ -636,7 +637,8 @@ mod success_fok {
                     .await?
                     .open_order(config.amount, config.order_type, config.price)
                     .await?
-                    .value,
+                    .value
+                    .order_id,
             );
         }
 
@@ -658,8 +660,7 @@ mod success_fok {
                 100,
                 order_ids,
             )
-            .await?
-            .value;
+            .await?;
 
         let expected_account0 = create_account(base_deposit, quote_delta, 0, 0);
         let expected_account1 = create_account(0, quote_deposit - quote_delta, 0, 0);
@@ -737,7 +738,8 @@ mod success_fok {
                     .await?
                     .open_order(config.amount, config.order_type, config.price)
                     .await?
-                    .value,
+                    .value
+                    .order_id,
             );
         }
 
@@ -759,8 +761,7 @@ mod success_fok {
                 100,
                 order_ids,
             )
-            .await?
-            .value;
+            .await?;
 
         let expected_account0 = create_account(base_deposit, quote_delta, 0, quote_locked);
         let expected_account1 = create_account(0, quote_deposit - quote_delta - quote_locked, 0, 0);
@@ -843,7 +844,8 @@ mod success_fok {
                     .await?
                     .open_order(config.amount, config.order_type, config.price)
                     .await?
-                    .value,
+                    .value
+                    .order_id,
             );
         }
 
@@ -865,8 +867,7 @@ mod success_fok {
                 100,
                 order_ids,
             )
-            .await?
-            .value;
+            .await?;
 
         let expected_account0 = create_account(base_deposit, quote_delta, 0, quote_locked);
         let expected_account1 = create_account(0, quote_deposit - quote_delta - quote_locked, 0, 0);
@@ -964,7 +965,8 @@ mod success_fok {
                 .await?
                 .open_order(config.amount, config.order_type.clone(), config.price)
                 .await?
-                .value;
+                .value
+                .order_id;
             order_ids.push(order_id);
         }
 
@@ -1095,7 +1097,8 @@ mod success_fok {
                 .await?
                 .open_order(config.amount, config.order_type.clone(), config.price)
                 .await?
-                .value;
+                .value
+                .order_id;
             order_ids.push(order_id);
         }
 
@@ -1227,7 +1230,8 @@ mod revert {
                     .open_order(config.amount, config.order_type, config.price)
                     .await
                     .unwrap()
-                    .value,
+                    .value
+                    .order_id,
             );
         }
 
@@ -1313,7 +1317,8 @@ mod revert {
                     .open_order(config.amount, config.order_type, config.price)
                     .await
                     .unwrap()
-                    .value,
+                    .value
+                    .order_id,
             );
         }
 
@@ -1412,7 +1417,8 @@ mod revert {
                     .open_order(config.amount, config.order_type, config.price)
                     .await
                     .unwrap()
-                    .value,
+                    .value
+                    .order_id,
             );
         }
 
@@ -1436,7 +1442,6 @@ mod revert {
                 order_ids,
             )
             .await
-            .unwrap()
-            .value;
+            .unwrap();
     }
 }
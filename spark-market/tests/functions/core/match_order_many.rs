@@ -77,7 +77,8 @@ mod success {
                     .await?
                     .open_order(config.amount, config.order_type, config.price)
                     .await?
-                    .value,
+                    .value
+                    .order_id,
             );
         }
 
@@ -163,7 +164,8 @@ mod success {
                     .await?
                     .open_order(config.amount, config.order_type, config.price)
                     .await?
-                    .value,
+                    .value
+                    .order_id,
             );
         }
 
@@ -251,7 +253,8 @@ mod success {
                     .await?
                     .open_order(config.amount, config.order_type, config.price)
                     .await?
-                    .value,
+                    .value
+                    .order_id,
             );
         }
 
@@ -277,7 +280,7 @@ mod success {
 
             // Specify the range for order amounts and prices
             let amount_range = 100_000..100_000_000; // 0.001 BTC to 1 BTC
-            let price_range = 1_64..100_000_000_000_000_i64; // 0.000000001 USDC to 100k USDC
+            let price_range = 164_i64..100_000_000_000_000_i64; // 0.000000164 USDC to 100k USDC
             let price_variation_range = -500..=500; // Range for price variation
 
             let mut rng = rand::thread_rng();
@@ -331,7 +334,8 @@ mod success {
                         .await?
                         .open_order(config.amount, config.order_type.clone(), config.price)
                         .await?
-                        .value,
+                        .value
+                        .order_id,
                 );
             }
 
@@ -354,7 +358,8 @@ mod success {
                         .await?
                         .open_order(config.amount, config.order_type.clone(), config.price)
                         .await?
-                        .value,
+                        .value
+                        .order_id,
                 );
             }
 
@@ -452,7 +457,8 @@ mod success {
                     .await?
                     .open_order(config.amount, config.order_type, config.price)
                     .await?
-                    .value,
+                    .value
+                    .order_id,
             );
         }
 
@@ -556,7 +562,8 @@ mod revert {
                     .open_order(config.amount, config.order_type, config.price)
                     .await
                     .unwrap()
-                    .value,
+                    .value
+                    .order_id,
             );
         }
 
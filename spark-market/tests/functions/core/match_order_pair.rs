@@ -1,5 +1,5 @@
-use crate::setup::{create_account, setup, Defaults};
-use spark_market_sdk::{OrderType, ProtocolFee};
+use crate::setup::{create_account, fund, setup, Defaults};
+use spark_market_sdk::{CancelOrderEvent, OrderType, ProtocolFee};
 
 mod success_same_asset_type {
 
@@ -36,13 +36,15 @@ mod success_same_asset_type {
             .await?
             .open_order(base_amount, OrderType::Sell, price)
             .await?
-            .value;
+            .value
+            .order_id;
         let id1 = contract
             .with_account(&user1.wallet)
             .await?
             .open_order(base_amount, OrderType::Buy, price)
             .await?
-            .value;
+            .value
+            .order_id;
 
         let expected_account0 = create_account(0, 0, base_amount, 0);
         let expected_account1 = create_account(0, 0, 0, quote_amount);
@@ -73,6 +75,81 @@ mod success_same_asset_type {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn match_between_funded_wallets() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, user0, user1, matcher, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let price = 70_000 * 10_u64.pow(defaults.price_decimals);
+        let base_amount = 100_000_u64; // 0.001 BTC
+        let quote_amount = price / to_quote_scale * base_amount;
+        let random_amount = 5_000_u64;
+
+        let before = (
+            user0.balance(&assets.base.id).await,
+            user1.balance(&assets.quote.id).await,
+            matcher.balance(&assets.random.id).await,
+        );
+        fund(
+            &owner.wallet,
+            &[
+                (&user0.wallet, &assets.base, base_amount),
+                (&user1.wallet, &assets.quote, quote_amount),
+                (&matcher.wallet, &assets.random, random_amount),
+            ],
+        )
+        .await?;
+
+        assert_eq!(user0.balance(&assets.base.id).await, before.0 + base_amount);
+        assert_eq!(
+            user1.balance(&assets.quote.id).await,
+            before.1 + quote_amount
+        );
+        assert_eq!(
+            matcher.balance(&assets.random.id).await,
+            before.2 + random_amount
+        );
+
+        let seller = contract.with_account(&user0.wallet).await?;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        seller.deposit(base_amount, assets.base.id).await?;
+        buyer.deposit(quote_amount, assets.quote.id).await?;
+        let id0 = seller
+            .open_order(base_amount, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        let id1 = buyer
+            .open_order(base_amount, OrderType::Buy, price)
+            .await?
+            .value
+            .order_id;
+
+        contract
+            .with_account(&matcher.wallet)
+            .await?
+            .match_order_pair(id0, id1)
+            .await?;
+
+        assert_eq!(
+            contract.account(user0.identity()).await?.value,
+            create_account(0, quote_amount, 0, 0)
+        );
+        assert_eq!(
+            contract.account(user1.identity()).await?.value,
+            create_account(base_amount, 0, 0, 0)
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn match_same_base_asset_type_orders_same_price_same_user() -> anyhow::Result<()> {
         let defaults = Defaults::default();
@@ -111,13 +188,15 @@ mod success_same_asset_type {
             .await?
             .open_order(base_amount, OrderType::Sell, price)
             .await?
-            .value;
+            .value
+            .order_id;
         let id1 = contract
             .with_account(&user0.wallet)
             .await?
             .open_order(base_amount, OrderType::Buy, price)
             .await?
-            .value;
+            .value
+            .order_id;
 
         let expected_account0 = create_account(0, 0, base_amount, quote_amount);
 
@@ -171,13 +250,15 @@ mod success_same_asset_type {
             .await?
             .open_order(base_amount, OrderType::Sell, sell_price)
             .await?
-            .value;
+            .value
+            .order_id;
         let id1 = contract
             .with_account(&user1.wallet)
             .await?
             .open_order(base_amount, OrderType::Buy, buy_price)
             .await?
-            .value;
+            .value
+            .order_id;
 
         let expected_account0 = create_account(0, 0, base_amount, 0);
         let expected_account1 = create_account(0, 0, 0, buy_quote_amount);
@@ -209,6 +290,114 @@ mod success_same_asset_type {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn match_buy_above_resting_sell_at_maker_price() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+        contract.set_maker_price_execution(true).await?;
+
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let sell_price = 70_000 * 10_u64.pow(defaults.price_decimals);
+        let buy_price = 77_000 * 10_u64.pow(defaults.price_decimals);
+        let base_amount = 100_000_u64;
+        let sell_quote_amount = sell_price / to_quote_scale * base_amount;
+        let buy_quote_amount = buy_price / to_quote_scale * base_amount;
+
+        // The sell rests on the book before the buy arrives
+        let seller = contract.with_account(&user0.wallet).await?;
+        seller.deposit(base_amount, assets.base.id).await?;
+        let id0 = seller
+            .open_order(base_amount, OrderType::Sell, sell_price)
+            .await?
+            .value
+            .order_id;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        buyer.deposit(buy_quote_amount, assets.quote.id).await?;
+        let id1 = buyer
+            .open_order(base_amount, OrderType::Buy, buy_price)
+            .await?
+            .value
+            .order_id;
+
+        let execution = contract.match_order_pair_execution(id0, id1).await?;
+        assert_eq!(execution.sell_order_id, id0);
+        assert_eq!(execution.buy_order_id, id1);
+        assert_eq!(execution.trade_size, base_amount);
+        assert_eq!(execution.execution_price, sell_price);
+
+        // The taking buyer pays the maker price and keeps the improvement below its limit
+        let paid = buy_quote_amount - contract.account(user1.identity()).await?.value.liquid.quote;
+        assert_eq!(paid, sell_quote_amount);
+        assert!(paid < buy_quote_amount);
+        assert_eq!(
+            contract.account(user0.identity()).await?.value,
+            create_account(0, sell_quote_amount, 0, 0)
+        );
+        assert_eq!(
+            contract.account(user1.identity()).await?.value,
+            create_account(base_amount, buy_quote_amount - sell_quote_amount, 0, 0)
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn match_sell_below_resting_buy_at_maker_price() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+        contract.set_maker_price_execution(true).await?;
+
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let sell_price = 70_000 * 10_u64.pow(defaults.price_decimals);
+        let buy_price = 77_000 * 10_u64.pow(defaults.price_decimals);
+        let base_amount = 100_000_u64;
+        let buy_quote_amount = buy_price / to_quote_scale * base_amount;
+
+        // The buy rests on the book before the sell arrives
+        let buyer = contract.with_account(&user1.wallet).await?;
+        buyer.deposit(buy_quote_amount, assets.quote.id).await?;
+        let id1 = buyer
+            .open_order(base_amount, OrderType::Buy, buy_price)
+            .await?
+            .value
+            .order_id;
+        let seller = contract.with_account(&user0.wallet).await?;
+        seller.deposit(base_amount, assets.base.id).await?;
+        let id0 = seller
+            .open_order(base_amount, OrderType::Sell, sell_price)
+            .await?
+            .value
+            .order_id;
+
+        let execution = contract.match_order_pair_execution(id0, id1).await?;
+        assert_eq!(execution.execution_price, buy_price);
+        assert_eq!(contract.last_trade_price().await?.value, buy_price);
+
+        // The taking seller receives the maker price, above its own limit
+        assert_eq!(
+            contract.account(user0.identity()).await?.value,
+            create_account(0, buy_quote_amount, 0, 0)
+        );
+        assert_eq!(
+            contract.account(user1.identity()).await?.value,
+            create_account(base_amount, 0, 0, 0)
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn match_same_base_asset_type_orders_size_not_equal() -> anyhow::Result<()> {
         let defaults = Defaults::default();
@@ -242,13 +431,15 @@ mod success_same_asset_type {
             .await?
             .open_order(sell_base_amount, OrderType::Sell, price)
             .await?
-            .value;
+            .value
+            .order_id;
         let id1 = contract
             .with_account(&user1.wallet)
             .await?
             .open_order(buy_base_amount, OrderType::Buy, price)
             .await?
-            .value;
+            .value
+            .order_id;
 
         let expected_account0 = create_account(0, 0, sell_base_amount, 0);
         let expected_account1 =
@@ -287,6 +478,96 @@ mod success_same_asset_type {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn match_same_base_asset_type_orders_size_not_equal_residual_below_dust_threshold(
+    ) -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let price = 70_000 * 10_u64.pow(defaults.price_decimals);
+        let sell_base_amount = 100_000_u64; // 0.001 BTC
+        let buy_base_amount = 90_000_u64; // 0.0009 BTC
+        let sell_quote_amount = price / to_quote_scale * sell_base_amount;
+        let buy_quote_amount = price / to_quote_scale * buy_base_amount;
+
+        // The 10_000 residual of the sell order is below the threshold
+        contract.set_dust_threshold(20_000).await?;
+
+        contract
+            .with_account(&user0.wallet)
+            .await?
+            .deposit(sell_base_amount, assets.base.id)
+            .await?;
+        contract
+            .with_account(&user1.wallet)
+            .await?
+            .deposit(sell_quote_amount, assets.quote.id)
+            .await?;
+
+        let id0 = contract
+            .with_account(&user0.wallet)
+            .await?
+            .open_order(sell_base_amount, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        let id1 = contract
+            .with_account(&user1.wallet)
+            .await?
+            .open_order(buy_base_amount, OrderType::Buy, price)
+            .await?
+            .value
+            .order_id;
+
+        // The residual is unlocked back to the liquid balance instead of resting on the book
+        let expected_account0 =
+            create_account(sell_base_amount - buy_base_amount, buy_quote_amount, 0, 0);
+        let expected_account1 =
+            create_account(buy_base_amount, sell_quote_amount - buy_quote_amount, 0, 0);
+
+        let response = contract.match_order_pair(id0, id1).await?;
+
+        let log = response
+            .decode_logs_with_type::<CancelOrderEvent>()
+            .unwrap();
+        let event = log.first().unwrap();
+        assert_eq!(
+            *event,
+            CancelOrderEvent {
+                order_id: id0,
+                user: user0.identity(),
+                liquid_base: expected_account0.liquid.base,
+                liquid_quote: expected_account0.liquid.quote,
+            }
+        );
+
+        assert_eq!(
+            contract.account(user0.identity()).await?.value,
+            expected_account0
+        );
+        assert_eq!(
+            contract.account(user1.identity()).await?.value,
+            expected_account1
+        );
+
+        assert!(contract.order(id0).await?.value.is_none());
+        assert!(contract.order(id1).await?.value.is_none());
+        assert!(contract
+            .user_orders(user0.identity())
+            .await?
+            .value
+            .is_empty());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn match_same_base_asset_type_orders_same_price_with_matcher_fee() -> anyhow::Result<()> {
         let defaults = Defaults::default();
@@ -321,13 +602,15 @@ mod success_same_asset_type {
             .await?
             .open_order(base_amount, OrderType::Sell, price)
             .await?
-            .value;
+            .value
+            .order_id;
         let id1 = contract
             .with_account(&user1.wallet)
             .await?
             .open_order(base_amount, OrderType::Buy, price)
             .await?
-            .value;
+            .value
+            .order_id;
 
         let expected_account0 = create_account(0, 0, base_amount, 0);
         let expected_account1 = create_account(0, 0, 0, quote_amount);
@@ -403,13 +686,15 @@ mod success_same_asset_type {
             .await?
             .open_order(base_amount, OrderType::Sell, price)
             .await?
-            .value;
+            .value
+            .order_id;
         let id1 = contract
             .with_account(&user0.wallet)
             .await?
             .open_order(base_amount, OrderType::Buy, price)
             .await?
-            .value;
+            .value
+            .order_id;
 
         let expected_account0 = create_account(0, 0, base_amount, quote_amount);
 
@@ -476,13 +761,15 @@ mod success_same_asset_type {
             .await?
             .open_order(base_amount, OrderType::Sell, price)
             .await?
-            .value;
+            .value
+            .order_id;
         let id1 = contract
             .with_account(&user1.wallet)
             .await?
             .open_order(base_amount, OrderType::Buy, price)
             .await?
-            .value;
+            .value
+            .order_id;
 
         let expected_account0 = create_account(0, 0, base_amount, 0);
         let expected_account1 = create_account(0, 0, 0, quote_amount);
@@ -570,13 +857,15 @@ mod success_same_asset_type {
             .await?
             .open_order(base_amount, OrderType::Sell, price)
             .await?
-            .value;
+            .value
+            .order_id;
         let id1 = contract
             .with_account(&user0.wallet)
             .await?
             .open_order(base_amount, OrderType::Buy, price)
             .await?
-            .value;
+            .value
+            .order_id;
 
         let expected_account0 = create_account(0, 0, base_amount, quote_amount);
 
@@ -657,7 +946,8 @@ mod revert {
             .open_order(base_amount, OrderType::Sell, sell_price)
             .await
             .unwrap()
-            .value;
+            .value
+            .order_id;
         let id1 = contract
             .with_account(&user1.wallet)
             .await
@@ -665,7 +955,8 @@ mod revert {
             .open_order(base_amount, OrderType::Buy, buy_price)
             .await
             .unwrap()
-            .value;
+            .value
+            .order_id;
 
         let expected_account0 = create_account(0, 0, base_amount, 0);
         let expected_account1 = create_account(0, 0, 0, quote_amount);
@@ -0,0 +1,139 @@
+use crate::setup::{setup, Defaults};
+use spark_market_sdk::OrderType;
+
+mod success {
+
+    use super::*;
+    use fuels::{
+        accounts::Account,
+        prelude::{AssetId, TxPolicies},
+    };
+    use spark_market_sdk::run_bounded;
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    #[tokio::test]
+    async fn matches_every_pair() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, user1, matcher, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let base_amount = 1_000_u64; // 0.00001 BTC
+        let price = 70_000_000_000_000_u64; // 70,000$ price
+        let pair_count = 6;
+
+        let seller = contract.with_account(&user0.wallet).await?;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        seller
+            .deposit(base_amount * pair_count, assets.base.id)
+            .await?;
+        buyer
+            .deposit(
+                price / to_quote_scale * base_amount * pair_count,
+                assets.quote.id,
+            )
+            .await?;
+
+        let mut pairs = Vec::new();
+        for _ in 0..pair_count {
+            let sell = seller
+                .open_order(base_amount, OrderType::Sell, price)
+                .await?
+                .value
+                .order_id;
+            let buy = buyer
+                .open_order(base_amount, OrderType::Buy, price)
+                .await?
+                .value
+                .order_id;
+            pairs.push((sell, buy));
+        }
+
+        // Every in-flight transaction spends its own gas coin, so split the matcher's single coin
+        let max_in_flight = 3;
+        for _ in 0..max_in_flight {
+            matcher
+                .wallet
+                .transfer(
+                    matcher.wallet.address(),
+                    1_000_000_000,
+                    AssetId::default(),
+                    TxPolicies::default(),
+                )
+                .await?;
+        }
+
+        let results = contract
+            .with_account(&matcher.wallet)
+            .await?
+            .match_order_pairs_concurrent(pairs.clone(), max_in_flight)
+            .await?;
+
+        assert_eq!(results.len(), pairs.len());
+        for result in results {
+            result?;
+        }
+        for (sell, buy) in pairs {
+            assert!(contract.order(sell).await?.value.is_none());
+            assert!(contract.order(buy).await?.value.is_none());
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn never_exceeds_max_in_flight() {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let outputs = run_bounded((0..20).collect(), 4, |item: u32| {
+            let (in_flight, peak) = (in_flight.clone(), peak.clone());
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                item * 2
+            }
+        })
+        .await;
+
+        assert_eq!(outputs, (0..20).map(|item| item * 2).collect::<Vec<_>>());
+        assert_eq!(peak.load(Ordering::SeqCst), 4);
+    }
+}
+
+mod revert {
+
+    use super::*;
+
+    #[tokio::test]
+    #[should_panic(expected = "Max in flight must be greater than zero")]
+    async fn when_max_in_flight_is_zero() {
+        let defaults = Defaults::default();
+        let (contract, _owner, _, _, _, _) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        // Reverts
+        contract
+            .match_order_pairs_concurrent(vec![], 0)
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,175 @@
+use crate::setup::{setup, Defaults};
+use fuels::accounts::ViewOnlyAccount;
+use spark_market_sdk::{MatchOutcome, OrderType};
+
+mod success {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn submits_when_orders_still_cross() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let base_amount = 1_000_u64; // 0.00001 BTC
+        let price = 70_000_000_000_000_u64; // 70,000$ price
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let quote_amount = price / to_quote_scale * base_amount;
+
+        let seller = contract.with_account(&user0.wallet).await?;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        seller.deposit(base_amount, assets.base.id).await?;
+        buyer.deposit(quote_amount, assets.quote.id).await?;
+        let id0 = seller
+            .open_order(base_amount, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        let id1 = buyer
+            .open_order(base_amount, OrderType::Buy, price)
+            .await?
+            .value
+            .order_id;
+
+        let outcome = contract.match_orders(vec![id0, id1], true).await?;
+
+        assert!(matches!(outcome, MatchOutcome::Submitted(_)));
+        assert!(contract.order(id0).await?.value.is_none());
+        assert!(contract.order(id1).await?.value.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn skips_when_an_order_was_cancelled_after_discovery() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+        let provider = owner.wallet.try_provider()?;
+
+        let base_amount = 1_000_u64; // 0.00001 BTC
+        let price = 70_000_000_000_000_u64; // 70,000$ price
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let quote_amount = price / to_quote_scale * base_amount;
+
+        let seller = contract.with_account(&user0.wallet).await?;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        seller.deposit(base_amount, assets.base.id).await?;
+        buyer.deposit(quote_amount, assets.quote.id).await?;
+        let id0 = seller
+            .open_order(base_amount, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        let id1 = buyer
+            .open_order(base_amount, OrderType::Buy, price)
+            .await?
+            .value
+            .order_id;
+
+        // The seller cancels between discovery and matching
+        seller.cancel_order(id0).await?;
+        let height = provider.latest_block_height().await?;
+
+        let outcome = contract.match_orders(vec![id0, id1], true).await?;
+
+        // Nothing was submitted, so no block was produced for a reverting transaction
+        assert!(matches!(outcome, MatchOutcome::NoLongerCrossing));
+        assert_eq!(provider.latest_block_height().await?, height);
+        assert!(contract.order(id1).await?.value.is_some());
+
+        Ok(())
+    }
+}
+
+mod revert {
+
+    use super::*;
+
+    #[tokio::test]
+    #[should_panic(expected = "CantMatchMany")]
+    async fn when_unchecked_and_no_longer_crossing() {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        let base_amount = 1_000_u64; // 0.00001 BTC
+        let price = 70_000_000_000_000_u64; // 70,000$ price
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let quote_amount = price / to_quote_scale * base_amount;
+
+        let seller = contract.with_account(&user0.wallet).await.unwrap();
+        let buyer = contract.with_account(&user1.wallet).await.unwrap();
+        seller.deposit(base_amount, assets.base.id).await.unwrap();
+        buyer.deposit(quote_amount, assets.quote.id).await.unwrap();
+        let id0 = seller
+            .open_order(base_amount, OrderType::Sell, price)
+            .await
+            .unwrap()
+            .value
+            .order_id;
+        let id1 = buyer
+            .open_order(base_amount, OrderType::Buy, price)
+            .await
+            .unwrap()
+            .value
+            .order_id;
+        seller.cancel_order(id0).await.unwrap();
+
+        // Reverts
+        contract.match_orders(vec![id0, id1], false).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn when_the_same_order_is_given_twice() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, user0, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+        let provider = owner.wallet.try_provider()?;
+
+        let base_amount = 1_000_u64; // 0.00001 BTC
+        let price = 70_000_000_000_000_u64; // 70,000$ price
+
+        let seller = contract.with_account(&user0.wallet).await?;
+        seller.deposit(base_amount, assets.base.id).await?;
+        let id = seller
+            .open_order(base_amount, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        let height = provider.latest_block_height().await?;
+
+        let error = contract
+            .match_orders(vec![id, id], false)
+            .await
+            .unwrap_err();
+
+        // Rejected before anything reaches the chain
+        assert!(error.to_string().starts_with("SameOrder"));
+        assert_eq!(provider.latest_block_height().await?, height);
+        assert!(contract.order(id).await?.value.is_some());
+
+        Ok(())
+    }
+}
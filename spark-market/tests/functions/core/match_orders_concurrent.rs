@@ -0,0 +1,118 @@
+use crate::setup::{assert_solvent, setup, Defaults};
+use spark_market_sdk::{MatchOutcome, OrderType, SparkMarketContract, TradeOrderEvent};
+
+mod success {
+
+    use super::*;
+    use fuels::types::Bits256;
+
+    #[tokio::test]
+    async fn overlapping_concurrent_matches_fill_each_order_at_most_once() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, user0, user1, matcher, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let base_amount = 1_000_u64; // 0.00001 BTC
+        let price = 70_000_000_000_000_u64; // 70,000$ price
+        let pair_count = 8;
+
+        let seller = contract.with_account(&user0.wallet).await?;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        seller
+            .deposit(base_amount * pair_count as u64, assets.base.id)
+            .await?;
+        buyer
+            .deposit(
+                price / to_quote_scale * base_amount * pair_count as u64,
+                assets.quote.id,
+            )
+            .await?;
+
+        let (mut sells, mut buys) = (Vec::new(), Vec::new());
+        for _ in 0..pair_count {
+            sells.push(
+                seller
+                    .open_order(base_amount, OrderType::Sell, price)
+                    .await?
+                    .value
+                    .order_id,
+            );
+            buys.push(
+                buyer
+                    .open_order(base_amount, OrderType::Buy, price)
+                    .await?
+                    .value
+                    .order_id,
+            );
+        }
+
+        // Two matchers race over pairs that share every order with the other's pairs
+        let straight: Vec<Vec<Bits256>> =
+            (0..pair_count).map(|i| vec![sells[i], buys[i]]).collect();
+        let shifted: Vec<Vec<Bits256>> = (0..pair_count)
+            .map(|i| vec![sells[i], buys[(i + 1) % pair_count]])
+            .collect();
+        let matcher0 = contract.with_account(&matcher.wallet).await?;
+        let matcher1 = contract.with_account(&owner.wallet).await?;
+        let run = |market: SparkMarketContract, batches: Vec<Vec<Bits256>>| async move {
+            let mut outcomes = Vec::new();
+            for orders in batches {
+                outcomes.push(market.match_orders(orders, true).await);
+            }
+            outcomes
+        };
+        let (outcomes0, outcomes1) = tokio::join!(run(matcher0, straight), run(matcher1, shifted));
+
+        let mut trades = Vec::new();
+        for outcome in outcomes0.into_iter().chain(outcomes1) {
+            match outcome {
+                Ok(MatchOutcome::Submitted(response)) => {
+                    trades.extend(response.decode_logs_with_type::<TradeOrderEvent>()?)
+                }
+                Ok(MatchOutcome::NoLongerCrossing) => {}
+                // The other matcher filled the orders between the check and the submission
+                Err(error) => assert!(error.to_string().contains("CantMatchMany"), "{}", error),
+            }
+        }
+
+        // Each order is either untouched or gone after exactly one full trade
+        for id in sells.iter().chain(&buys) {
+            let fills = trades
+                .iter()
+                .filter(|trade| trade.base_sell_order_id == *id || trade.base_buy_order_id == *id)
+                .count();
+            match contract.order(*id).await?.value {
+                Some(order) => {
+                    assert_eq!(fills, 0);
+                    assert_eq!(order.amount, base_amount);
+                }
+                None => assert_eq!(fills, 1),
+            }
+        }
+        assert!(!trades.is_empty());
+
+        let traded: u64 = trades.iter().map(|trade| trade.trade_size).sum();
+        assert_eq!(
+            contract.account(user1.identity()).await?.value.liquid.base,
+            traded
+        );
+        assert_solvent(
+            &contract,
+            &[
+                owner.identity(),
+                user0.identity(),
+                user1.identity(),
+                matcher.identity(),
+            ],
+        )
+        .await?;
+
+        Ok(())
+    }
+}
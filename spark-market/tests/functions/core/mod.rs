@@ -1,10 +1,31 @@
+mod cancel_by_params;
+mod cancel_expired;
 mod cancel_order;
+mod claim_keeper_rewards;
+mod dead_mans_switch;
+mod deploy;
 mod deposit;
+mod deposit_and_open;
+mod fee_checks;
+mod fee_exempt;
+mod fulfill_many_with_limits;
 mod fulfill_order_many;
 mod match_order_many;
 mod match_order_pair;
+mod match_order_pairs_concurrent;
+mod match_orders;
+mod match_orders_concurrent;
+mod open_if_triggered;
 mod open_order;
+mod open_order_from;
+mod open_order_post_only;
+mod replay;
+mod set_dust_threshold;
 mod set_epoch;
+mod set_maker_price_execution;
 mod set_matcher_fee;
+mod set_max_price_deviation;
 mod set_protocol_fee;
+mod set_taker_fee;
+mod set_tick_size;
 mod withdraw;
@@ -0,0 +1,98 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use spark_market_sdk::{OrderType, ReferencePrice, TriggerCondition, TriggerOrder};
+
+    #[tokio::test]
+    async fn triggers_on_best_ask_instead_of_last_trade() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let unit = 10_u64.pow(defaults.price_decimals);
+        let seller = contract.with_account(&user0.wallet).await?;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        seller.deposit(1_000_000, assets.base.id).await?;
+        buyer.deposit(1_000_000, assets.quote.id).await?;
+
+        // The last trade is at 70_000 and the book is left with an ask at 71_000
+        let ask = seller
+            .open_order(100, OrderType::Sell, 70_000 * unit)
+            .await?
+            .value
+            .order_id;
+        let bid = buyer
+            .open_order(100, OrderType::Buy, 70_000 * unit)
+            .await?
+            .value
+            .order_id;
+        contract.match_order_pair(ask, bid).await?;
+        let high_ask = seller
+            .open_order(100, OrderType::Sell, 71_000 * unit)
+            .await?
+            .value
+            .order_id;
+
+        let on_ask = TriggerOrder {
+            amount: 100,
+            order_type: OrderType::Buy,
+            price: 69_000 * unit,
+            trigger_price: 69_000 * unit,
+            condition: TriggerCondition::AtOrBelow,
+            reference: Some(ReferencePrice::BestAsk),
+        };
+        // Falls back to the market's reference price, the last trade by default
+        let on_market = TriggerOrder {
+            reference: None,
+            ..on_ask.clone()
+        };
+
+        assert!(!buyer.is_triggered(&on_ask, vec![high_ask]).await?);
+        assert!(!buyer.is_triggered(&on_market, vec![high_ask]).await?);
+
+        // The ask moves below the trigger price while the last trade stays at 70_000
+        let low_ask = seller
+            .open_order(100, OrderType::Sell, 68_500 * unit)
+            .await?
+            .value
+            .order_id;
+        let book = vec![high_ask, low_ask];
+        assert_eq!(
+            buyer
+                .reference_price(ReferencePrice::BestAsk, book.clone())
+                .await?,
+            Some(68_500 * unit)
+        );
+        assert_eq!(
+            buyer
+                .reference_price(ReferencePrice::LastTrade, book.clone())
+                .await?,
+            Some(70_000 * unit)
+        );
+
+        assert!(buyer
+            .open_if_triggered(&on_market, book.clone())
+            .await?
+            .is_none());
+        let buyer_on_ask = contract
+            .with_account(&user1.wallet)
+            .await?
+            .with_reference_price(ReferencePrice::BestAsk);
+        assert!(buyer_on_ask.is_triggered(&on_market, book.clone()).await?);
+
+        let opened = buyer
+            .open_if_triggered(&on_ask, book)
+            .await?
+            .expect("triggered by the best ask")
+            .value;
+        let order = contract.order(opened.order_id).await?.value.unwrap();
+        assert_eq!(order.amount, 100);
+        assert_eq!(order.price, 69_000 * unit);
+
+        Ok(())
+    }
+}
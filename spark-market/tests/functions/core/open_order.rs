@@ -1,10 +1,10 @@
 use crate::setup::{setup, Defaults};
 use fuels::accounts::ViewOnlyAccount;
-use spark_market_sdk::{OpenOrderEvent, OrderChangeType, OrderType, ProtocolFee};
+use spark_market_sdk::{OpenOrderEvent, OrderChangeType, OrderType, ProtocolFee, ZeroAmount};
 
 mod success {
 
-    use spark_market_sdk::AssetType;
+    use spark_market_sdk::{AssetType, Order};
 
     use super::*;
     use crate::setup::create_account;
@@ -37,7 +37,7 @@ mod success {
         let response = contract
             .open_order(order_amount, order_type.clone(), price)
             .await?;
-        let id = response.value;
+        let id = response.value.order_id;
         let expected_id = contract
             .order_id(
                 order_type.clone(),
@@ -131,7 +131,7 @@ mod success {
         let response = contract
             .open_order(order_amount, order_type.clone(), price)
             .await?;
-        let id = response.value;
+        let id = response.value.order_id;
         let expected_id = contract
             .order_id(
                 order_type.clone(),
@@ -183,6 +183,41 @@ mod success {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn reports_deposit_consumed_by_additional_size() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let matcher_fee = 100_u64;
+        let _ = contract.set_matcher_fee(matcher_fee).await?;
+
+        let price = 70000 * 10_u64.pow(defaults.price_decimals);
+        let deposit_amount = 70000 + 35000 + 2 * matcher_fee;
+        let _ = contract.deposit(deposit_amount, assets.quote.id).await?;
+
+        let first = contract.open_order(100, OrderType::Buy, price).await?.value;
+        assert_eq!(first.base_deposited, 0);
+        assert_eq!(first.quote_deposited, 70000 + matcher_fee);
+
+        // Adding size at the same price only consumes the lock of the new size
+        let second = contract.open_order(50, OrderType::Buy, price).await?.value;
+        assert_ne!(second.order_id, first.order_id);
+        assert_eq!(second.base_deposited, 0);
+        assert_eq!(second.quote_deposited, 35000 + matcher_fee);
+
+        assert_eq!(
+            contract.account(owner.identity()).await?.value,
+            create_account(0, 0, 0, first.quote_deposited + second.quote_deposited)
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn sell_base_with_fee() -> anyhow::Result<()> {
         let defaults = Defaults::default();
@@ -234,7 +269,7 @@ mod success {
             .open_order(order_amount, order_type.clone(), price)
             .await?;
 
-        let id = response.value;
+        let id = response.value.order_id;
         let block_height = _user.wallet.try_provider()?.latest_block_height().await?;
         let expected_id = contract
             .order_id(order_type.clone(), _user.identity(), price, block_height, 0)
@@ -289,16 +324,115 @@ mod success {
         assert_eq!(info[0].amount_after, order_amount);
 
         let order = contract.order(id).await?.value.unwrap();
-        assert_eq!(order.amount, order_amount);
-        assert_eq!(order.asset_type, AssetType::Base);
-        assert_eq!(order.order_type, OrderType::Sell);
-        assert_eq!(order.owner, _user.identity());
-        assert_eq!(order.price, price);
-        assert_eq!(order.block_height, block_height);
-        assert_eq!(order.order_height, 0);
-        assert_eq!(order.matcher_fee, matcher_fee);
-        assert_eq!(order.protocol_maker_fee, protocol_fee[0].maker_fee);
-        assert_eq!(order.protocol_taker_fee, protocol_fee[0].taker_fee);
+        let expected_order = Order::builder()
+            .amount(order_amount)
+            .asset_type(AssetType::Base)
+            .order_type(OrderType::Sell)
+            .owner(_user.identity())
+            .price(price)
+            .block_height(block_height)
+            .matcher_fee(matcher_fee)
+            .protocol_fee(protocol_fee[0].maker_fee, protocol_fee[0].taker_fee)
+            .build();
+        assert_eq!(order, expected_order);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn same_price_orders_stay_separate() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let price = 70_000_000_000_000_u64;
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let buy_quote = 3 * price / to_quote_scale;
+        contract.deposit(10, assets.base.id).await?;
+        contract.deposit(buy_quote, assets.quote.id).await?;
+
+        // Sell 5, sell 5 and buy 3 at one price by one trader. The market keys each order by
+        // its order height, so repeated orders are not merged into one signed size and opposite
+        // sides are not netted; every order keeps its own side and amount
+        let mut ids = Vec::new();
+        for (amount, order_type) in [
+            (5, OrderType::Sell),
+            (5, OrderType::Sell),
+            (3, OrderType::Buy),
+        ] {
+            ids.push(
+                contract
+                    .open_order(amount, order_type, price)
+                    .await?
+                    .value
+                    .order_id,
+            );
+        }
+
+        assert_eq!(contract.user_orders(owner.identity()).await?.value, ids);
+        for (id, (amount, order_type, order_height)) in ids.iter().zip([
+            (5, OrderType::Sell, 0),
+            (5, OrderType::Sell, 1),
+            (3, OrderType::Buy, 2),
+        ]) {
+            let order = contract.order(*id).await?.value.unwrap();
+            assert_eq!(order.amount, amount);
+            assert_eq!(order.order_type, order_type);
+            assert_eq!(order.order_height, order_height);
+        }
+
+        // Both sides stay locked in full rather than a net of -7 base
+        let user_account = contract.account(owner.identity()).await?.value;
+        assert_eq!(user_account, create_account(0, 0, 10, buy_quote));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn with_account_signs_as_the_new_wallet() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let price = 70_000_000_000_000_u64;
+        let user_contract = contract.with_account(&user.wallet).await?;
+        assert_eq!(user_contract.id(), contract.id());
+
+        user_contract.deposit(10, assets.base.id).await?;
+        let id = user_contract
+            .open_order(10, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+
+        // The order and the deposit belong to the new signer, not the original one
+        assert_eq!(
+            contract.order(id).await?.value.unwrap().owner,
+            user.identity()
+        );
+        assert_eq!(contract.user_orders(user.identity()).await?.value, vec![id]);
+        assert!(contract
+            .user_orders(owner.identity())
+            .await?
+            .value
+            .is_empty());
+        assert_eq!(
+            contract.account(user.identity()).await?.value,
+            create_account(0, 0, 10, 0)
+        );
+        assert_eq!(
+            contract.account(owner.identity()).await?.value,
+            create_account(0, 0, 0, 0)
+        );
 
         Ok(())
     }
@@ -433,4 +567,33 @@ mod revert {
             .await
             .unwrap();
     }
+
+    #[tokio::test]
+    async fn when_amount_is_zero() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+        let provider = owner.wallet.try_provider()?;
+        contract.deposit(100, assets.base.id).await?;
+        let height = provider.latest_block_height().await?;
+
+        // Fails before a transaction is sent
+        let error = contract
+            .open_order(0, OrderType::Sell, 70_000_000_000_000_u64)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.downcast::<ZeroAmount>()?,
+            ZeroAmount {
+                operation: "open_order"
+            }
+        );
+        assert_eq!(provider.latest_block_height().await?, height);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,107 @@
+use crate::setup::{create_account, setup, Defaults};
+use spark_market_sdk::{DepositSource, OrderType};
+
+mod success {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn balance_source_uses_deposited_funds() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let base_amount = 100_000_u64; // 0.001 BTC
+        let price = 70_000_000_000_000_u64; // 70,000$ price
+
+        let contract = contract.with_account(&user.wallet).await?;
+        contract.deposit(base_amount, assets.base.id).await?;
+
+        let wallet_base = user.balance(&assets.base.id).await;
+        let opened = contract
+            .open_order_from(base_amount, OrderType::Sell, price, DepositSource::Balance)
+            .await?
+            .value;
+
+        // Nothing new left the wallet, the order locked the earlier deposit
+        assert_eq!(user.balance(&assets.base.id).await, wallet_base);
+        assert_eq!(opened.base_deposited, base_amount);
+        assert_eq!(
+            contract.account(user.identity()).await?.value,
+            create_account(0, 0, base_amount, 0)
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn attached_source_transfers_funds() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let base_amount = 100_000_u64; // 0.001 BTC
+        let price = 70_000_000_000_000_u64; // 70,000$ price
+
+        let contract = contract.with_account(&user.wallet).await?;
+
+        let wallet_base = user.balance(&assets.base.id).await;
+        let opened = contract
+            .open_order_from(base_amount, OrderType::Sell, price, DepositSource::Attached)
+            .await?
+            .value;
+
+        // The order's base was transferred from the wallet along with the call
+        assert_eq!(
+            user.balance(&assets.base.id).await,
+            wallet_base - base_amount
+        );
+        assert_eq!(opened.base_deposited, base_amount);
+        assert_eq!(
+            contract.account(user.identity()).await?.value,
+            create_account(0, 0, base_amount, 0)
+        );
+
+        Ok(())
+    }
+}
+
+mod revert {
+
+    use super::*;
+
+    #[tokio::test]
+    #[should_panic(expected = "InsufficientBalance")]
+    async fn when_balance_source_without_deposit() {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, _assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        // Revert
+        contract
+            .with_account(&user.wallet)
+            .await
+            .unwrap()
+            .open_order_from(
+                100_000,
+                OrderType::Sell,
+                70_000_000_000_000,
+                DepositSource::Balance,
+            )
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,98 @@
+use crate::setup::{create_account, setup, Defaults};
+use spark_market_sdk::OrderType;
+
+mod success {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn buy_below_best_ask_rests() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let ask_price = 70_000 * 10_u64.pow(defaults.price_decimals);
+        let bid_price = 69_000 * 10_u64.pow(defaults.price_decimals);
+        let base_amount = 100_000_u64; // 0.001 BTC
+        let quote_amount = bid_price / to_quote_scale * base_amount;
+
+        let seller = contract.with_account(&user0.wallet).await?;
+        seller.deposit(base_amount, assets.base.id).await?;
+        let ask_id = seller
+            .open_order(base_amount, OrderType::Sell, ask_price)
+            .await?
+            .value
+            .order_id;
+
+        let buyer = contract.with_account(&user1.wallet).await?;
+        buyer.deposit(quote_amount, assets.quote.id).await?;
+        let bid_id = buyer
+            .open_order_post_only(base_amount, OrderType::Buy, bid_price, vec![ask_id])
+            .await?
+            .value;
+
+        assert_eq!(
+            contract.order(bid_id).await?.value.unwrap().price,
+            bid_price
+        );
+        assert_eq!(
+            contract.account(user1.identity()).await?.value,
+            create_account(0, 0, 0, quote_amount)
+        );
+        assert_eq!(
+            contract.user_orders(user1.identity()).await?.value,
+            vec![bid_id]
+        );
+
+        Ok(())
+    }
+}
+
+mod revert {
+
+    use super::*;
+
+    #[tokio::test]
+    #[should_panic(expected = "WouldCross")]
+    async fn when_buy_above_best_ask() {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let ask_price = 70_000 * 10_u64.pow(defaults.price_decimals);
+        let bid_price = 71_000 * 10_u64.pow(defaults.price_decimals);
+        let base_amount = 100_000_u64; // 0.001 BTC
+        let quote_amount = bid_price / to_quote_scale * base_amount;
+
+        let seller = contract.with_account(&user0.wallet).await.unwrap();
+        seller.deposit(base_amount, assets.base.id).await.unwrap();
+        let ask_id = seller
+            .open_order(base_amount, OrderType::Sell, ask_price)
+            .await
+            .unwrap()
+            .value
+            .order_id;
+
+        let buyer = contract.with_account(&user1.wallet).await.unwrap();
+        buyer.deposit(quote_amount, assets.quote.id).await.unwrap();
+
+        // Reverts
+        buyer
+            .open_order_post_only(base_amount, OrderType::Buy, bid_price, vec![ask_id])
+            .await
+            .unwrap();
+    }
+}
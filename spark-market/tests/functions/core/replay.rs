@@ -0,0 +1,99 @@
+use crate::setup::{setup, Defaults};
+use fuels::types::Bits256;
+use spark_market_sdk::{LimitType, MarketCall, OrderType};
+
+mod success {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn replays_saved_open_order() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let _ = contract.deposit(10, assets.base.id).await?;
+
+        let call = MarketCall::OpenOrder {
+            amount: 5,
+            order_type: OrderType::Sell,
+            price: 70_000_000_000_000_u64,
+        };
+        let saved = call.to_json();
+        let replayed = MarketCall::from_json(&saved)?;
+        assert_eq!(replayed, call);
+
+        let order_id = contract.replay(&replayed, 1.5).await?.unwrap();
+
+        let order = contract.order(order_id).await?.value.unwrap();
+        assert_eq!(order.amount, 5);
+        assert_eq!(order.order_type, OrderType::Sell);
+        assert_eq!(order.price, 70_000_000_000_000_u64);
+        assert_eq!(
+            contract.user_orders(owner.identity()).await?.value,
+            vec![order_id]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trips_every_call() -> anyhow::Result<()> {
+        let id0 = Bits256([1; 32]);
+        let id1 = Bits256([2; 32]);
+        let calls = vec![
+            MarketCall::CancelOrder { order_id: id0 },
+            MarketCall::MatchOrderPair {
+                order_id0: id0,
+                order_id1: id1,
+            },
+            MarketCall::MatchOrderMany {
+                orders: vec![id0, id1],
+            },
+            MarketCall::FulfillMany {
+                amount: 3,
+                order_type: OrderType::Buy,
+                limit_type: LimitType::IOC,
+                price: 1,
+                slippage: 100,
+                orders: vec![id1],
+            },
+        ];
+
+        for call in calls {
+            assert_eq!(MarketCall::from_json(&call.to_json())?, call);
+        }
+
+        Ok(())
+    }
+}
+
+mod revert {
+
+    use super::*;
+
+    #[tokio::test]
+    #[should_panic(expected = "Gas multiplier must be at least 1")]
+    async fn when_gas_multiplier_below_one() {
+        let defaults = Defaults::default();
+        let (contract, _owner, _user, _, _, _assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        let call = MarketCall::OpenOrder {
+            amount: 5,
+            order_type: OrderType::Sell,
+            price: 70_000_000_000_000_u64,
+        };
+
+        contract.replay(&call, 0.5).await.unwrap();
+    }
+}
@@ -0,0 +1,85 @@
+use crate::setup::{setup, Defaults};
+
+mod success {
+
+    use super::*;
+    use spark_market_sdk::SetDustThresholdEvent;
+
+    #[tokio::test]
+    async fn sets_dust_threshold() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, _, _, _, _assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let initial_threshold = 0;
+        let new_threshold = 1_000;
+
+        // Assert precondition of initial threshold
+        assert_eq!(contract.dust_threshold().await?.value, initial_threshold);
+
+        let response = contract.set_dust_threshold(new_threshold).await?;
+
+        // Log should be emitted when threshold is changed
+        let log = response
+            .decode_logs_with_type::<SetDustThresholdEvent>()
+            .unwrap();
+        let event = log.first().unwrap();
+        assert_eq!(
+            *event,
+            SetDustThresholdEvent {
+                amount: new_threshold
+            }
+        );
+
+        assert_eq!(contract.dust_threshold().await?.value, new_threshold);
+
+        Ok(())
+    }
+}
+
+mod revert {
+
+    use super::*;
+
+    #[tokio::test]
+    #[should_panic(expected = "Unauthorized")]
+    async fn reverts_when_non_owner() {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, _) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        // Reverts
+        contract
+            .with_account(&user.wallet)
+            .await
+            .unwrap()
+            .set_dust_threshold(1_000)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "InvalidValueSame")]
+    async fn reverts_when_value_is_same() {
+        let defaults = Defaults::default();
+        let (contract, _owner, _, _, _, _) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        // Reverts
+        contract.set_dust_threshold(0).await.unwrap();
+    }
+}
@@ -1,7 +1,6 @@
 use crate::setup::{setup, Defaults};
 use spark_market_sdk::SetEpochEvent;
 use std::time::{SystemTime, UNIX_EPOCH};
-use tokio;
 
 // TAI is currently 37 seconds ahead of UTC
 const TAI_OFFSET: u64 = 37;
@@ -0,0 +1,77 @@
+use crate::setup::{setup, Defaults};
+
+mod success {
+
+    use super::*;
+    use spark_market_sdk::SetMakerPriceExecutionEvent;
+
+    #[tokio::test]
+    async fn sets_maker_price_execution() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, _, _, _, _assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        // Assert precondition of matches at the sell price
+        assert!(!contract.maker_price_execution().await?.value);
+
+        let response = contract.set_maker_price_execution(true).await?;
+
+        // Log should be emitted when the setting is changed
+        let log = response
+            .decode_logs_with_type::<SetMakerPriceExecutionEvent>()
+            .unwrap();
+        let event = log.first().unwrap();
+        assert_eq!(*event, SetMakerPriceExecutionEvent { enabled: true });
+
+        assert!(contract.maker_price_execution().await?.value);
+
+        Ok(())
+    }
+}
+
+mod revert {
+
+    use super::*;
+
+    #[tokio::test]
+    #[should_panic(expected = "Unauthorized")]
+    async fn reverts_when_non_owner() {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, _) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        // Reverts
+        contract
+            .with_account(&user.wallet)
+            .await
+            .unwrap()
+            .set_maker_price_execution(true)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "InvalidValueSame")]
+    async fn reverts_when_value_is_same() {
+        let defaults = Defaults::default();
+        let (contract, _owner, _, _, _, _) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        // Reverts
+        contract.set_maker_price_execution(false).await.unwrap();
+    }
+}
@@ -0,0 +1,210 @@
+use crate::setup::{setup, Defaults};
+use fuels::prelude::{AssetId, WalletUnlocked};
+use spark_market_sdk::{OrderType, SparkMarketContract};
+
+// Opens a sell and a buy order at the same price and matches them so the market has a last trade
+async fn trade_once(
+    contract: &SparkMarketContract,
+    seller: &WalletUnlocked,
+    buyer: &WalletUnlocked,
+    base_asset: AssetId,
+    quote_asset: AssetId,
+    price: u64,
+) -> anyhow::Result<()> {
+    let defaults = Defaults::default();
+    let to_quote_scale =
+        10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+    let base_amount = 100_000_u64; // 0.001 BTC
+    let quote_amount = price / to_quote_scale * base_amount;
+
+    let seller = contract.with_account(seller).await?;
+    let buyer = contract.with_account(buyer).await?;
+    seller.deposit(base_amount, base_asset).await?;
+    buyer.deposit(quote_amount, quote_asset).await?;
+
+    let id0 = seller
+        .open_order(base_amount, OrderType::Sell, price)
+        .await?
+        .value
+        .order_id;
+    let id1 = buyer
+        .open_order(base_amount, OrderType::Buy, price)
+        .await?
+        .value
+        .order_id;
+    contract.match_order_pair(id0, id1).await?;
+
+    Ok(())
+}
+
+mod success {
+
+    use super::*;
+    use spark_market_sdk::SetMaxPriceDeviationEvent;
+
+    #[tokio::test]
+    async fn sets_max_price_deviation() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, _, _, _, _assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let bps = 500;
+
+        // Assert precondition of disabled band
+        assert_eq!(contract.max_price_deviation_bps().await?.value, 0);
+
+        let response = contract.set_max_price_deviation_bps(bps).await?;
+
+        // Log should be emitted when the band is changed
+        let log = response
+            .decode_logs_with_type::<SetMaxPriceDeviationEvent>()
+            .unwrap();
+        let event = log.first().unwrap();
+        assert_eq!(*event, SetMaxPriceDeviationEvent { bps });
+
+        assert_eq!(contract.max_price_deviation_bps().await?.value, bps);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn accepts_any_price_before_first_trade() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        contract.set_max_price_deviation_bps(500).await?;
+        assert_eq!(contract.last_trade_price().await?.value, 0);
+
+        let contract = contract.with_account(&user.wallet).await?;
+        contract.deposit(1_000, assets.base.id).await?;
+        contract
+            .open_order(
+                1_000,
+                OrderType::Sell,
+                1_000_000 * 10_u64.pow(defaults.price_decimals),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn accepts_price_within_band() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let price = 70_000 * 10_u64.pow(defaults.price_decimals);
+        contract.set_max_price_deviation_bps(500).await?;
+        trade_once(
+            &contract,
+            &user0.wallet,
+            &user1.wallet,
+            assets.base.id,
+            assets.quote.id,
+            price,
+        )
+        .await?;
+        assert_eq!(contract.last_trade_price().await?.value, price);
+
+        // Exactly 5% above the last trade is still inside the band
+        let contract = contract.with_account(&user0.wallet).await?;
+        contract.deposit(1_000, assets.base.id).await?;
+        contract
+            .open_order(1_000, OrderType::Sell, price / 100 * 105)
+            .await?;
+
+        Ok(())
+    }
+}
+
+mod revert {
+
+    use super::*;
+
+    #[tokio::test]
+    #[should_panic(expected = "Unauthorized")]
+    async fn reverts_when_non_owner() {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, _) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        // Reverts
+        contract
+            .with_account(&user.wallet)
+            .await
+            .unwrap()
+            .set_max_price_deviation_bps(500)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "InvalidValueSame")]
+    async fn reverts_when_value_is_same() {
+        let defaults = Defaults::default();
+        let (contract, _owner, _, _, _, _) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        // Reverts
+        contract.set_max_price_deviation_bps(0).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "PriceOutOfBand")]
+    async fn reverts_when_price_outside_band() {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        let price = 70_000 * 10_u64.pow(defaults.price_decimals);
+        contract.set_max_price_deviation_bps(500).await.unwrap();
+        trade_once(
+            &contract,
+            &user0.wallet,
+            &user1.wallet,
+            assets.base.id,
+            assets.quote.id,
+            price,
+        )
+        .await
+        .unwrap();
+
+        let contract = contract.with_account(&user0.wallet).await.unwrap();
+        contract.deposit(1_000, assets.base.id).await.unwrap();
+
+        // Reverts
+        contract
+            .open_order(1_000, OrderType::Sell, price * 2)
+            .await
+            .unwrap();
+    }
+}
@@ -41,12 +41,7 @@ mod success {
             .decode_logs_with_type::<SetProtocolFeeEvent>()
             .unwrap();
         let event = log.first().unwrap();
-        assert_eq!(
-            *event,
-            SetProtocolFeeEvent {
-                protocol_fee: protocol_fee
-            }
-        );
+        assert_eq!(*event, SetProtocolFeeEvent { protocol_fee });
 
         Ok(())
     }
@@ -0,0 +1,173 @@
+use crate::setup::{create_account, setup, Defaults};
+use spark_market_sdk::{OrderType, ProtocolFee};
+
+mod success {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn sets_taker_fee_keeping_maker_fees() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, _, _, _, _assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        // Without a fee schedule the taker fee starts a zero based tier
+        assert_eq!(contract.taker_fee().await?, 0);
+        contract.set_taker_fee(25, 0).await?;
+        assert_eq!(contract.taker_fee().await?, 25);
+        assert_eq!(
+            contract.protocol_fee().await?.value,
+            vec![ProtocolFee {
+                maker_fee: 0,
+                taker_fee: 25,
+                volume_threshold: 0,
+            }]
+        );
+
+        let protocol_fee = vec![
+            ProtocolFee {
+                maker_fee: 10,
+                taker_fee: 15,
+                volume_threshold: 0,
+            },
+            ProtocolFee {
+                maker_fee: 8,
+                taker_fee: 13,
+                volume_threshold: 10000000000,
+            },
+        ];
+        contract.set_protocol_fee(protocol_fee).await?;
+        contract.set_taker_fee(30, 0).await?;
+
+        // Only the addressed tier changes, the higher volume tier keeps its discount
+        assert_eq!(
+            contract.protocol_fee().await?.value,
+            vec![
+                ProtocolFee {
+                    maker_fee: 10,
+                    taker_fee: 30,
+                    volume_threshold: 0,
+                },
+                ProtocolFee {
+                    maker_fee: 8,
+                    taker_fee: 13,
+                    volume_threshold: 10000000000,
+                },
+            ]
+        );
+
+        contract.set_taker_fee(11, 10000000000).await?;
+        assert_eq!(contract.protocol_fee().await?.value[0].taker_fee, 30);
+        assert_eq!(contract.protocol_fee().await?.value[1].taker_fee, 11);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn taker_pays_fee_to_protocol() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let taker_fee = 25;
+        contract.set_taker_fee(taker_fee, 0).await?;
+
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let price = 70_000 * 10_u64.pow(defaults.price_decimals);
+        let base_amount = 100_000_u64; // 0.001 BTC
+        let quote_amount = price / to_quote_scale * base_amount;
+        let fee_amount = quote_amount * taker_fee / 10_000;
+
+        let breakdown = contract
+            .with_account(&user1.wallet)
+            .await?
+            .quote_trade_breakdown(base_amount, price)
+            .await?;
+        assert_eq!(breakdown.quote, quote_amount);
+        assert_eq!(breakdown.maker_fee, 0);
+        assert_eq!(breakdown.taker_fee, fee_amount);
+        assert_eq!(breakdown.matcher_fee, 0);
+
+        contract
+            .with_account(&user0.wallet)
+            .await?
+            .deposit(base_amount, assets.base.id)
+            .await?;
+        contract
+            .with_account(&user1.wallet)
+            .await?
+            .deposit(quote_amount + fee_amount, assets.quote.id)
+            .await?;
+
+        // The resting sell is the maker, the later buy is the taker
+        let id0 = contract
+            .with_account(&user0.wallet)
+            .await?
+            .open_order(base_amount, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        let id1 = contract
+            .with_account(&user1.wallet)
+            .await?
+            .open_order(base_amount, OrderType::Buy, price)
+            .await?
+            .value
+            .order_id;
+
+        contract.match_order_pair(id0, id1).await?;
+
+        assert_eq!(
+            contract.account(user0.identity()).await?.value,
+            create_account(0, quote_amount, 0, 0)
+        );
+        assert_eq!(
+            contract.account(user1.identity()).await?.value,
+            create_account(base_amount, 0, 0, 0)
+        );
+        assert_eq!(
+            contract.account(owner.identity()).await?.value,
+            create_account(0, fee_amount, 0, 0)
+        );
+
+        Ok(())
+    }
+}
+
+mod revert {
+
+    use super::*;
+
+    #[tokio::test]
+    #[should_panic(expected = "No protocol fee tier starts at volume 5")]
+    async fn when_no_tier_starts_at_threshold() {
+        let defaults = Defaults::default();
+        let (contract, _owner, _, _, _, _assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        contract
+            .set_protocol_fee(vec![ProtocolFee {
+                maker_fee: 10,
+                taker_fee: 15,
+                volume_threshold: 0,
+            }])
+            .await
+            .unwrap();
+
+        contract.set_taker_fee(20, 5).await.unwrap();
+    }
+}
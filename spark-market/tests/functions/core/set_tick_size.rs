@@ -0,0 +1,127 @@
+use crate::setup::{setup, Defaults};
+use spark_market_sdk::OrderType;
+
+mod success {
+
+    use super::*;
+    use spark_market_sdk::SetTickSizeEvent;
+
+    #[tokio::test]
+    async fn sets_tick_size() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, _, _, _, _assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let tick = 10_u64.pow(defaults.price_decimals);
+
+        // Assert precondition of disabled grid
+        assert_eq!(contract.tick_size().await?.value, 0);
+
+        let response = contract.set_tick_size(tick).await?;
+
+        // Log should be emitted when the tick size is changed
+        let log = response
+            .decode_logs_with_type::<SetTickSizeEvent>()
+            .unwrap();
+        let event = log.first().unwrap();
+        assert_eq!(*event, SetTickSizeEvent { tick });
+
+        assert_eq!(contract.tick_size().await?.value, tick);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn accepts_price_on_tick() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let tick = 10_u64.pow(defaults.price_decimals);
+        contract.set_tick_size(tick).await?;
+
+        let contract = contract.with_account(&user.wallet).await?;
+        contract.deposit(1_000, assets.base.id).await?;
+        contract
+            .open_order(1_000, OrderType::Sell, 70_000 * tick)
+            .await?;
+
+        Ok(())
+    }
+}
+
+mod revert {
+
+    use super::*;
+
+    #[tokio::test]
+    #[should_panic(expected = "Unauthorized")]
+    async fn reverts_when_non_owner() {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, _) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        // Reverts
+        contract
+            .with_account(&user.wallet)
+            .await
+            .unwrap()
+            .set_tick_size(100)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "InvalidValueSame")]
+    async fn reverts_when_value_is_same() {
+        let defaults = Defaults::default();
+        let (contract, _owner, _, _, _, _) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        // Reverts
+        contract.set_tick_size(0).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "InvalidTick")]
+    async fn reverts_when_price_off_tick() {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        let tick = 10_u64.pow(defaults.price_decimals);
+        contract.set_tick_size(tick).await.unwrap();
+
+        let contract = contract.with_account(&user.wallet).await.unwrap();
+        contract.deposit(1_000, assets.base.id).await.unwrap();
+
+        // Reverts
+        contract
+            .open_order(1_000, OrderType::Sell, 70_000 * tick + 1)
+            .await
+            .unwrap();
+    }
+}
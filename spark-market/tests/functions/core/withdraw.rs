@@ -212,7 +212,8 @@ mod success {
 mod revert {
 
     use super::*;
-    use spark_market_sdk::AssetType;
+    use fuels::accounts::ViewOnlyAccount;
+    use spark_market_sdk::{AssetType, ZeroAmount};
 
     #[tokio::test]
     #[should_panic(expected = "InsufficientBalance")]
@@ -286,4 +287,30 @@ mod revert {
             .await
             .unwrap();
     }
+
+    #[tokio::test]
+    async fn when_amount_is_zero() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+        let provider = owner.wallet.try_provider()?;
+        contract.deposit(100, assets.base.id).await?;
+        let height = provider.latest_block_height().await?;
+
+        // Fails before a transaction is sent
+        let error = contract.withdraw(0, AssetType::Base).await.unwrap_err();
+        assert_eq!(
+            error.downcast::<ZeroAmount>()?,
+            ZeroAmount {
+                operation: "withdraw"
+            }
+        );
+        assert_eq!(provider.latest_block_height().await?, height);
+
+        Ok(())
+    }
 }
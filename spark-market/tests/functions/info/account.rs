@@ -41,4 +41,33 @@ mod success {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn returns_accounts_in_order() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let _ = contract.deposit(100, assets.base.id).await?;
+        let _ = contract
+            .with_account(&user.wallet)
+            .await?
+            .deposit(250, assets.quote.id)
+            .await?;
+
+        let accounts = contract
+            .accounts(vec![user.identity(), owner.identity()])
+            .await?;
+
+        assert_eq!(
+            accounts,
+            vec![create_account(0, 250, 0, 0), create_account(100, 0, 0, 0)]
+        );
+
+        Ok(())
+    }
 }
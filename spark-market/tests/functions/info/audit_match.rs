@@ -0,0 +1,122 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use spark_market_sdk::{AuditedFill, OrderType};
+
+    #[tokio::test]
+    async fn fills_in_price_time_priority() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let unit = 10_u64.pow(defaults.price_decimals);
+        let seller = contract.with_account(&user0.wallet).await?;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        seller.deposit(1_000_000, assets.base.id).await?;
+        buyer.deposit(1_000_000, assets.quote.id).await?;
+
+        // Opened oldest first
+        let mut makers = Vec::new();
+        for price in [71_000, 70_000, 71_000, 73_000] {
+            let maker = seller
+                .open_order(100, OrderType::Sell, price * unit)
+                .await?
+                .value
+                .order_id;
+            makers.push(maker);
+        }
+        let (older_71k, best, newer_71k, too_high) = (makers[0], makers[1], makers[2], makers[3]);
+        let taker = buyer
+            .open_order(250, OrderType::Buy, 72_000 * unit)
+            .await?
+            .value
+            .order_id;
+
+        // The order the makers are given in does not matter
+        let fills = contract
+            .audit_match(taker, vec![too_high, newer_71k, older_71k, best])
+            .await?;
+        assert_eq!(
+            fills,
+            vec![
+                AuditedFill {
+                    maker_id: best,
+                    price: 70_000 * unit,
+                    amount: 100,
+                },
+                AuditedFill {
+                    maker_id: older_71k,
+                    price: 71_000 * unit,
+                    amount: 100,
+                },
+                AuditedFill {
+                    maker_id: newer_71k,
+                    price: 71_000 * unit,
+                    amount: 50,
+                },
+            ]
+        );
+
+        // Nothing was matched
+        assert_eq!(contract.order(taker).await?.value.unwrap().amount, 250);
+        assert_eq!(contract.order(best).await?.value.unwrap().amount, 100);
+
+        // A maker that does not cross is never filled
+        assert!(contract
+            .audit_match(taker, vec![too_high])
+            .await?
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reports_the_execution_price() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let unit = 10_u64.pow(defaults.price_decimals);
+        let seller = contract.with_account(&user0.wallet).await?;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        seller.deposit(1_000_000, assets.base.id).await?;
+        buyer.deposit(1_000_000, assets.quote.id).await?;
+
+        // Resting buys set the price the later sell executes at
+        contract.set_maker_price_execution(true).await?;
+        let maker = buyer
+            .open_order(100, OrderType::Buy, 72_000 * unit)
+            .await?
+            .value
+            .order_id;
+        let taker = seller
+            .open_order(100, OrderType::Sell, 70_000 * unit)
+            .await?
+            .value
+            .order_id;
+
+        let fills = contract.audit_match(taker, vec![maker]).await?;
+        assert_eq!(
+            fills,
+            vec![AuditedFill {
+                maker_id: maker,
+                price: 72_000 * unit,
+                amount: 100,
+            }]
+        );
+
+        // The simulated price is the one the match settles at
+        let trade = contract.match_order_pair_execution(taker, maker).await?;
+        assert_eq!(trade.execution_price, fills[0].price);
+
+        Ok(())
+    }
+}
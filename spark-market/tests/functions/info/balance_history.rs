@@ -0,0 +1,82 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use fuels::accounts::ViewOnlyAccount;
+    use spark_market_sdk::{AssetType, BalanceChangeKind, OrderType};
+
+    #[tokio::test]
+    async fn replays_to_the_final_balance() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+        let provider = owner.wallet.try_provider()?;
+        let from_block = provider.latest_block_height().await?;
+
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let base_amount = 1_000_u64; // 0.00001 BTC
+        let price = 70_000_000_000_000_u64; // 70,000$ price
+        let quote_amount = price / to_quote_scale * base_amount;
+
+        let seller = contract.with_account(&user0.wallet).await?;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        seller.deposit(base_amount * 2, assets.base.id).await?;
+        buyer.deposit(quote_amount, assets.quote.id).await?;
+        let id0 = seller
+            .open_order(base_amount, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        let id1 = buyer
+            .open_order(base_amount, OrderType::Buy, price)
+            .await?
+            .value
+            .order_id;
+        contract.match_order_pair(id0, id1).await?;
+        seller.withdraw(quote_amount / 2, AssetType::Quote).await?;
+
+        let to_block = provider.latest_block_height().await?;
+        let history = contract
+            .balance_history(user0.identity(), from_block, to_block)
+            .await?;
+
+        // The trade credits quote and the locked base is spent, both in one event
+        let balances: Vec<_> = history
+            .iter()
+            .map(|change| (change.kind, change.liquid_base, change.liquid_quote))
+            .collect();
+        assert_eq!(
+            balances,
+            vec![
+                (BalanceChangeKind::Deposit, base_amount * 2, 0),
+                (BalanceChangeKind::OpenOrder, base_amount, 0),
+                (BalanceChangeKind::Trade, base_amount, quote_amount),
+                (BalanceChangeKind::Withdraw, base_amount, quote_amount / 2),
+            ]
+        );
+        assert!(history
+            .windows(2)
+            .all(|pair| pair[0].block_height <= pair[1].block_height));
+
+        let last = history.last().unwrap();
+        let account = contract.account(user0.identity()).await?.value;
+        assert_eq!(
+            (last.liquid_base, last.liquid_quote),
+            (account.liquid.base, account.liquid.quote)
+        );
+
+        // The counterparty's side of the same trade
+        let history = contract
+            .balance_history(user1.identity(), from_block, to_block)
+            .await?;
+        let last = history.last().unwrap();
+        assert_eq!(last.kind, BalanceChangeKind::Trade);
+        assert_eq!((last.liquid_base, last.liquid_quote), (base_amount, 0));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use fuels::{prelude::Provider, types::Bits256};
+    use spark_market_sdk::{BatchingClient, OrderType};
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+        runtime::Handle,
+        task::JoinSet,
+    };
+
+    // Relays connections to `node` and counts the HTTP requests sent through it, returning the
+    // address to connect to and the count
+    async fn counting_proxy(node: &str) -> anyhow::Result<(String, Arc<AtomicUsize>)> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = format!("127.0.0.1:{}", listener.local_addr()?.port());
+        let node = node
+            .trim_start_matches("http://")
+            .split('/')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let requests = Arc::new(AtomicUsize::new(0));
+
+        let counter = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((inbound, _)) = listener.accept().await {
+                let (node, counter) = (node.clone(), counter.clone());
+                tokio::spawn(async move {
+                    let Ok(outbound) = TcpStream::connect(&node).await else {
+                        return;
+                    };
+                    let (mut in_read, mut in_write) = inbound.into_split();
+                    let (mut out_read, mut out_write) = outbound.into_split();
+                    let upstream = async {
+                        let mut buffer = [0; 16 * 1024];
+                        loop {
+                            let read = match in_read.read(&mut buffer).await {
+                                Ok(0) | Err(_) => break,
+                                Ok(read) => read,
+                            };
+                            // Every GraphQL query starts with a POST request line
+                            let posts = buffer[..read]
+                                .windows(5)
+                                .filter(|window| *window == b"POST ")
+                                .count();
+                            counter.fetch_add(posts, Ordering::SeqCst);
+                            if out_write.write_all(&buffer[..read]).await.is_err() {
+                                break;
+                            }
+                        }
+                        let _ = out_write.shutdown().await;
+                    };
+                    let downstream = async {
+                        let _ = tokio::io::copy(&mut out_read, &mut in_write).await;
+                    };
+                    tokio::join!(upstream, downstream);
+                });
+            }
+        });
+
+        Ok((address, requests))
+    }
+
+    #[tokio::test]
+    async fn coalesces_concurrent_order_reads() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let price = 70_000_000_000_000_u64;
+        let _ = contract.deposit(100, assets.base.id).await?;
+
+        let mut ids = Vec::new();
+        for amount in 1..=10 {
+            ids.push(
+                contract
+                    .open_order(amount, OrderType::Sell, price)
+                    .await?
+                    .value
+                    .order_id,
+            );
+        }
+        // Half of the reads are for orders that do not exist
+        for byte in 1..=10 {
+            ids.push(Bits256([byte; 32]));
+        }
+
+        // Read through a proxy that counts what actually reaches the node
+        let (proxy, requests) = counting_proxy(owner.wallet.try_provider()?.url()).await?;
+        let mut wallet = owner.wallet.clone();
+        wallet.set_provider(Provider::connect(&proxy).await?);
+        let proxied = contract.with_account(&wallet).await?;
+        let window = Duration::from_millis(10);
+
+        // What a single read costs on its own
+        let single = BatchingClient::new(&proxied, window, &Handle::current());
+        let before = requests.load(Ordering::SeqCst);
+        single.order_by_id(ids[0]).await?;
+        let per_read = requests.load(Ordering::SeqCst) - before;
+        assert!(per_read > 0);
+
+        let client = BatchingClient::new(&proxied, window, &Handle::current());
+        let before = requests.load(Ordering::SeqCst);
+        let mut reads = JoinSet::new();
+        for (index, id) in ids.iter().enumerate() {
+            let client = client.clone();
+            let id = *id;
+            reads.spawn(async move { (index, client.order_by_id(id).await) });
+        }
+
+        let mut results = vec![None; ids.len()];
+        while let Some(read) = reads.join_next().await {
+            let (index, order) = read?;
+            results[index] = Some(order?);
+        }
+        let sent = requests.load(Ordering::SeqCst) - before;
+
+        for (id, result) in ids.iter().zip(results) {
+            assert_eq!(result.unwrap(), contract.order_by_id(*id).await?);
+        }
+        // Twenty reads reached the node as at most two multicalls
+        assert!(
+            sent <= 2 * per_read,
+            "{} requests for {} reads, {} per read",
+            sent,
+            ids.len(),
+            per_read
+        );
+        assert!(
+            client.round_trips() <= 2,
+            "{} round trips",
+            client.round_trips()
+        );
+
+        Ok(())
+    }
+}
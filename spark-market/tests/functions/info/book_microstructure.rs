@@ -0,0 +1,95 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use spark_market_sdk::OrderType;
+
+    #[tokio::test]
+    async fn reports_mid_price_depth_and_imbalance() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let unit = 10_u64.pow(defaults.price_decimals);
+        let seller = contract.with_account(&user0.wallet).await?;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        seller.deposit(1_000_000, assets.base.id).await?;
+        buyer.deposit(1_000_000_000, assets.quote.id).await?;
+
+        let mut orders = Vec::new();
+        // Bids: 300 at 69_000, 100 at 68_000, 100 at 67_000 (outside the top 2 levels)
+        for (amount, price) in [(200, 69_000), (100, 69_000), (100, 68_000), (100, 67_000)] {
+            orders.push(
+                buyer
+                    .open_order(amount, OrderType::Buy, price * unit)
+                    .await?
+                    .value
+                    .order_id,
+            );
+        }
+        // Asks: 100 at 71_000, 100 at 72_000
+        for (amount, price) in [(100, 71_000), (100, 72_000)] {
+            orders.push(
+                seller
+                    .open_order(amount, OrderType::Sell, price * unit)
+                    .await?
+                    .value
+                    .order_id,
+            );
+        }
+
+        let depth = contract.book_depth(orders.clone()).await?;
+        assert_eq!(
+            depth.bids,
+            vec![
+                (69_000 * unit, 300),
+                (68_000 * unit, 100),
+                (67_000 * unit, 100)
+            ]
+        );
+        assert_eq!(depth.asks, vec![(71_000 * unit, 100), (72_000 * unit, 100)]);
+
+        let book = contract.book_microstructure(orders, 2).await?;
+
+        assert_eq!(book.best_bid, Some(69_000 * unit));
+        assert_eq!(book.best_ask, Some(71_000 * unit));
+        assert_eq!(book.mid_price, Some(70_000 * unit));
+        assert_eq!(book.bid_depth, 400);
+        assert_eq!(book.ask_depth, 200);
+        // 400 / (400 + 200) = 66.66%
+        assert_eq!(book.imbalance_bps, Some(6_666));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn one_sided_book_has_no_mid_price() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let price = 70_000_000_000_000_u64;
+        let _ = contract.deposit(10, assets.base.id).await?;
+        let id = contract
+            .open_order(10, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+
+        let book = contract.book_microstructure(vec![id], 5).await?;
+
+        assert_eq!(book.best_bid, None);
+        assert_eq!(book.mid_price, None);
+        assert_eq!(book.ask_depth, 10);
+        assert_eq!(book.imbalance_bps, Some(0));
+
+        Ok(())
+    }
+}
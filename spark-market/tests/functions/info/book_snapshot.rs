@@ -0,0 +1,124 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use fuels::accounts::ViewOnlyAccount;
+    use spark_market_sdk::OrderType;
+
+    #[tokio::test]
+    async fn replayed_trades_match_the_next_snapshot() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+        let provider = owner.wallet.try_provider()?;
+
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let base_amount = 1_000_u64;
+        let price = 70_000_000_000_000_u64;
+
+        let seller = contract.with_account(&user0.wallet).await?;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        seller.deposit(2 * base_amount, assets.base.id).await?;
+        buyer
+            .deposit(2 * price / to_quote_scale * base_amount, assets.quote.id)
+            .await?;
+
+        let sell = seller
+            .open_order(2 * base_amount, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        let buy = buyer
+            .open_order(base_amount, OrderType::Buy, price)
+            .await?
+            .value
+            .order_id;
+        // Rests below the ask and is not touched by the trade
+        let low_buy = buyer
+            .open_order(base_amount, OrderType::Buy, price / 2)
+            .await?
+            .value
+            .order_id;
+        let orders = vec![sell, buy, low_buy];
+
+        let before = contract.book_snapshot(orders.clone()).await?;
+        assert_eq!(before.orders.len(), 3);
+
+        let from_block = provider.latest_block_height().await? + 1;
+        contract.match_order_pair(sell, buy).await?;
+        let to_block = provider.latest_block_height().await?;
+        let trades = contract.trade_events(from_block, to_block).await?;
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].trade_size, base_amount);
+
+        let replayed = before.apply_trades(&trades);
+        assert_eq!(
+            replayed.order(sell).unwrap().amount,
+            before.order(sell).unwrap().amount - trades[0].trade_size
+        );
+        assert!(replayed.order(buy).is_none());
+        assert_eq!(replayed.order(low_buy), before.order(low_buy));
+
+        let after = contract.book_snapshot(orders).await?;
+        assert_eq!(replayed, after);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn replay_drops_residuals_below_the_dust_threshold() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+        let provider = owner.wallet.try_provider()?;
+
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let base_amount = 1_000_u64;
+        let price = 70_000_000_000_000_u64;
+
+        // A fill of the buy leaves 100 of the sell, below the threshold
+        contract.set_dust_threshold(200).await?;
+
+        let seller = contract.with_account(&user0.wallet).await?;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        seller.deposit(base_amount + 100, assets.base.id).await?;
+        buyer
+            .deposit(price / to_quote_scale * base_amount, assets.quote.id)
+            .await?;
+
+        let sell = seller
+            .open_order(base_amount + 100, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        let buy = buyer
+            .open_order(base_amount, OrderType::Buy, price)
+            .await?
+            .value
+            .order_id;
+        let orders = vec![sell, buy];
+
+        let before = contract.book_snapshot(orders.clone()).await?;
+        assert_eq!(before.dust_threshold, 200);
+
+        let from_block = provider.latest_block_height().await? + 1;
+        contract.match_order_pair(sell, buy).await?;
+        let to_block = provider.latest_block_height().await?;
+        let trades = contract.trade_events(from_block, to_block).await?;
+
+        let replayed = before.apply_trades(&trades);
+        assert!(replayed.orders.is_empty());
+        assert_eq!(replayed, contract.book_snapshot(orders).await?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use spark_market_sdk::{CacheStats, CachedSparkMarket, OrderType};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn serves_reads_within_ttl_and_drops_cancelled_orders() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let price = 70_000_000_000_000_u64;
+        let _ = contract.deposit(100, assets.base.id).await?;
+        let order_id = contract
+            .open_order(100, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+
+        let cached = CachedSparkMarket::new(
+            contract.with_account(&owner.wallet).await?,
+            Duration::from_secs(60),
+        );
+
+        // The second read within the ttl does not reach the node
+        let order = cached.order_by_id(order_id).await?;
+        assert_eq!(order, contract.order_by_id(order_id).await?);
+        assert_eq!(cached.order_by_id(order_id).await?, order);
+        assert_eq!(cached.stats(), CacheStats { hits: 1, misses: 1 });
+
+        let config = cached.config().await?;
+        assert_eq!(cached.config().await?, config);
+        assert_eq!(cached.stats(), CacheStats { hits: 2, misses: 2 });
+
+        // Cancelling drops the cached order, so the next read sees it gone
+        cached.cancel_order(order_id).await?;
+        assert_eq!(cached.order_by_id(order_id).await?, None);
+        assert_eq!(cached.stats(), CacheStats { hits: 2, misses: 3 });
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn warmed_up_reads_need_no_requests() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let price = 70_000_000_000_000_u64;
+        let _ = contract.deposit(100, assets.base.id).await?;
+        let mut orders = Vec::new();
+        for step in 1..=2 {
+            let order_id = contract
+                .open_order(10, OrderType::Sell, price * step)
+                .await?
+                .value
+                .order_id;
+            orders.push(order_id);
+        }
+
+        let cached = CachedSparkMarket::new_with_warmup(
+            contract.with_account(&owner.wallet).await?,
+            Duration::from_secs(60),
+            orders.clone(),
+        )
+        .await?;
+        assert_eq!(cached.stats(), CacheStats::default());
+
+        // Both reads are served from the preloaded state
+        assert_eq!(cached.config().await?, contract.config().await?);
+        assert_eq!(
+            cached.book_depth().await?,
+            contract.book_depth(orders.clone()).await?
+        );
+        assert_eq!(cached.stats(), CacheStats { hits: 2, misses: 0 });
+
+        // A change made around the cache shows after a refresh
+        contract.cancel_order(orders[0]).await?;
+        cached.refresh().await?;
+        let book = cached.book().await?;
+        assert_eq!(book.orders.len(), 1);
+        assert_eq!(book.orders[0].0, orders[1]);
+        assert_eq!(cached.stats(), CacheStats { hits: 3, misses: 0 });
+
+        Ok(())
+    }
+    #[tokio::test]
+    async fn drops_state_read_while_a_call_is_in_flight() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let price = 70_000_000_000_000_u64;
+        let _ = contract.deposit(100, assets.base.id).await?;
+        let order_id = contract
+            .open_order(100, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+
+        let cached = CachedSparkMarket::new(
+            contract.with_account(&owner.wallet).await?,
+            Duration::from_secs(60),
+        );
+
+        // A read racing the cancellation may cache the order as it was before it committed
+        let (cancelled, read) =
+            tokio::join!(cached.cancel_order(order_id), cached.order_by_id(order_id));
+        cancelled?;
+        read?;
+        assert_eq!(cached.order_by_id(order_id).await?, None);
+
+        Ok(())
+    }
+}
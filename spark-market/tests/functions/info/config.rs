@@ -1,6 +1,7 @@
 mod success {
 
     use crate::setup::{setup, Defaults};
+    use spark_market_sdk::MarketConfig;
 
     #[tokio::test]
     async fn returns_config() -> anyhow::Result<()> {
@@ -13,16 +14,16 @@ mod success {
         .await?;
 
         assert_eq!(
-            contract.config().await?.value,
-            (
-                assets.base.id,
-                assets.base.decimals,
-                assets.quote.id,
-                assets.quote.decimals,
-                owner.address().into(),
-                defaults.price_decimals,
-                0xFAFBFC,
-            )
+            contract.config().await?,
+            MarketConfig {
+                base_asset: assets.base.id,
+                base_decimals: assets.base.decimals,
+                quote_asset: assets.quote.id,
+                quote_decimals: assets.quote.decimals,
+                owner: owner.address().into(),
+                price_decimals: defaults.price_decimals,
+                version: 0xFAFBFC,
+            }
         );
 
         Ok(())
@@ -0,0 +1,45 @@
+mod success {
+
+    use crate::setup::{assert_solvent, setup, Defaults};
+    use spark_market_sdk::{AssetType, OrderType};
+
+    #[tokio::test]
+    async fn held_balance_is_deposits_minus_withdrawals() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        // Assert precondition of an empty market
+        let held = contract.contract_balances().await?;
+        assert_eq!((held.base, held.quote), (0, 0));
+
+        let user0_contract = contract.with_account(&user0.wallet).await?;
+        let user1_contract = contract.with_account(&user1.wallet).await?;
+        user0_contract.deposit(5_000, assets.base.id).await?;
+        user1_contract.deposit(70_000_000, assets.quote.id).await?;
+        user0_contract
+            .open_order(1_000, OrderType::Sell, 70_000_000_000_000)
+            .await?;
+        user0_contract.withdraw(1_500, AssetType::Base).await?;
+        user1_contract
+            .withdraw(20_000_000, AssetType::Quote)
+            .await?;
+        user1_contract.deposit(2_500, assets.base.id).await?;
+
+        // Opening an order locks funds without moving them out of the contract
+        let held = contract.contract_balances().await?;
+        assert_eq!(held.base, 5_000 - 1_500 + 2_500);
+        assert_eq!(held.quote, 70_000_000 - 20_000_000);
+        assert_solvent(
+            &contract,
+            &[owner.identity(), user0.identity(), user1.identity()],
+        )
+        .await?;
+
+        Ok(())
+    }
+}
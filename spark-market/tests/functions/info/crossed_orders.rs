@@ -0,0 +1,59 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use spark_market_sdk::OrderType;
+
+    #[tokio::test]
+    async fn finds_crossed_but_unmatched_orders() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let unit = 10_u64.pow(defaults.price_decimals);
+        let seller = contract.with_account(&user0.wallet).await?;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        seller.deposit(1_000_000, assets.base.id).await?;
+        buyer.deposit(1_000_000_000, assets.quote.id).await?;
+
+        // Nobody matches the ask at 70_000 against the bid at 71_000
+        let ask = seller
+            .open_order(100, OrderType::Sell, 70_000 * unit)
+            .await?
+            .value
+            .order_id;
+        let bid = buyer
+            .open_order(100, OrderType::Buy, 71_000 * unit)
+            .await?
+            .value
+            .order_id;
+        // Neither crosses the other side
+        let high_ask = seller
+            .open_order(100, OrderType::Sell, 72_000 * unit)
+            .await?
+            .value
+            .order_id;
+        let low_bid = buyer
+            .open_order(100, OrderType::Buy, 69_000 * unit)
+            .await?
+            .value
+            .order_id;
+
+        let crossed = contract
+            .find_crossed_orders(vec![ask, bid, high_ask, low_bid])
+            .await?;
+        assert_eq!(crossed, vec![(bid, ask)]);
+
+        // Once matched the book is no longer crossed
+        contract.match_order_pair(ask, bid).await?;
+        assert!(contract
+            .find_crossed_orders(vec![ask, bid, high_ask, low_bid])
+            .await?
+            .is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use fuels::accounts::ViewOnlyAccount;
+    use spark_market_sdk::{FeeChangeKind, ProtocolFee};
+
+    #[tokio::test]
+    async fn returns_each_change_in_order() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _, _, _, _) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+        let provider = owner.wallet.try_provider()?;
+        let from_block = provider.latest_block_height().await?;
+
+        let protocol_fee = vec![ProtocolFee {
+            maker_fee: 10,
+            taker_fee: 15,
+            volume_threshold: 0,
+        }];
+        contract.set_matcher_fee(100).await?;
+        contract.set_protocol_fee(protocol_fee.clone()).await?;
+        contract.set_matcher_fee(250).await?;
+
+        let to_block = provider.latest_block_height().await?;
+        let history = contract.fee_change_history(from_block, to_block).await?;
+
+        let kinds: Vec<FeeChangeKind> = history.iter().map(|change| change.kind.clone()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                FeeChangeKind::Matcher {
+                    old_fee: 0,
+                    new_fee: 100,
+                },
+                FeeChangeKind::Protocol {
+                    old_fee: vec![],
+                    new_fee: protocol_fee,
+                },
+                FeeChangeKind::Matcher {
+                    old_fee: 100,
+                    new_fee: 250,
+                },
+            ]
+        );
+        assert!(history
+            .iter()
+            .all(|change| change.changed_by == owner.identity()));
+        assert!(history
+            .windows(2)
+            .all(|pair| pair[0].block_height <= pair[1].block_height));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use fuels::{accounts::ViewOnlyAccount, types::Identity};
+    use spark_market_sdk::OrderType;
+
+    #[tokio::test]
+    async fn flags_orders_matched_right_after_opening() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+        let provider = owner.wallet.try_provider()?;
+
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let base_amount = 1_000_u64;
+        let price = 70_000_000_000_000_u64;
+
+        let seller = contract.with_account(&user0.wallet).await?;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        seller.deposit(2 * base_amount, assets.base.id).await?;
+        buyer
+            .deposit(2 * price / to_quote_scale * base_amount, assets.quote.id)
+            .await?;
+        let from_block = provider.latest_block_height().await?;
+
+        // A sell rests on the book, then a buy lands and is matched against it in the next block
+        let resting = seller
+            .open_order(base_amount, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        provider.produce_blocks(5, None).await?;
+        let inserted = buyer
+            .open_order(base_amount, OrderType::Buy, price)
+            .await?
+            .value
+            .order_id;
+        let opened_block = provider.latest_block_height().await?;
+        contract.match_order_pair(resting, inserted).await?;
+        let matched_block = provider.latest_block_height().await?;
+
+        // Another buy waits long enough before it is matched
+        let slow_sell = seller
+            .open_order(base_amount, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        let slow_buy = buyer
+            .open_order(base_amount, OrderType::Buy, price)
+            .await?
+            .value
+            .order_id;
+        provider.produce_blocks(5, None).await?;
+        contract.match_order_pair(slow_sell, slow_buy).await?;
+        let to_block = provider.latest_block_height().await?;
+
+        let suspects = contract.frontrun_suspects(from_block, to_block, 1).await?;
+        assert_eq!(suspects.len(), 1);
+        let suspect = &suspects[0];
+        assert_eq!(suspect.order_id, inserted);
+        assert_eq!(
+            suspect.owner,
+            Identity::Address(user1.wallet.address().into())
+        );
+        assert_eq!(suspect.opened_block, opened_block);
+        assert_eq!(suspect.matched_block, matched_block);
+        assert_eq!(suspect.counterparty_id, resting);
+        assert_eq!(
+            suspect.counterparty,
+            Identity::Address(user0.wallet.address().into())
+        );
+        assert_eq!(suspect.trade_price, price);
+        assert_eq!(suspect.trade_size, base_amount);
+
+        // A wider window also catches the slow buy
+        let suspects = contract.frontrun_suspects(from_block, to_block, 10).await?;
+        assert_eq!(suspects.len(), 2);
+        assert_eq!(suspects[1].order_id, slow_buy);
+        assert_eq!(suspects[1].counterparty_id, slow_sell);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+mod success {
+
+    use spark_market_sdk::{
+        base_of_quote_amount, checked_sum, mul_div, quote_of_base_amount, quote_scale, RoundingMode,
+    };
+
+    #[test]
+    fn products_past_u64_still_fit() -> anyhow::Result<()> {
+        // 10^12 * 10^17 overflows a u64 but the quote, 10^29 / 10^11, does not
+        let (amount, price) = (1_000_000_000_000, 100_000_000_000_000_000);
+        assert_eq!(
+            quote_of_base_amount(amount, price, 8, 6, 9, RoundingMode::Floor)?,
+            1_000_000_000_000_000_000
+        );
+        assert_eq!(
+            base_of_quote_amount(
+                1_000_000_000_000_000_000,
+                price,
+                8,
+                6,
+                9,
+                RoundingMode::Floor
+            )?,
+            amount
+        );
+
+        assert_eq!(
+            mul_div(u64::MAX, u64::MAX, u64::MAX, RoundingMode::Floor)?,
+            u64::MAX
+        );
+        assert_eq!(
+            mul_div(u64::MAX, 3, 4, RoundingMode::Ceil)?,
+            13835058055282163712
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn scales_past_u64() -> anyhow::Result<()> {
+        // 10^20 does not fit in a u64 but is a valid divisor
+        assert_eq!(quote_scale(18, 6, 8)?, 100_000_000_000_000_000_000);
+        assert_eq!(
+            quote_of_base_amount(u64::MAX, u64::MAX, 18, 6, 8, RoundingMode::Floor)?,
+            3402823669209384634
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn sums_up_to_u64_max() -> anyhow::Result<()> {
+        assert_eq!(checked_sum(&[u64::MAX - 2, 1, 1])?, u64::MAX);
+        assert_eq!(checked_sum(&[])?, 0);
+
+        Ok(())
+    }
+}
+
+mod revert {
+
+    use spark_market_sdk::{
+        base_of_quote_amount, checked_sum, mul_div, quote_of_base_amount, quote_scale, RoundingMode,
+    };
+
+    #[test]
+    fn errors_instead_of_wrapping() {
+        let error = quote_of_base_amount(u64::MAX, u64::MAX, 8, 6, 9, RoundingMode::Floor)
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("AmountOverflow"));
+
+        let error = mul_div(u64::MAX, 2, 1, RoundingMode::Floor)
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("AmountOverflow"));
+
+        let error = checked_sum(&[u64::MAX, 1]).unwrap_err().to_string();
+        assert!(error.starts_with("AmountOverflow"));
+    }
+
+    #[test]
+    fn rejects_unsupported_decimals() {
+        // More quote decimals than base and price decimals together
+        let error = quote_scale(2, 12, 3).unwrap_err().to_string();
+        assert!(error.starts_with("UnsupportedDecimals"));
+
+        // 10^40 does not fit in a u128
+        assert!(quote_scale(30, 0, 10).is_err());
+    }
+
+    #[test]
+    fn rejects_zero_price() {
+        let error = base_of_quote_amount(1, 0, 8, 6, 9, RoundingMode::Floor)
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("DivisionByZero"));
+    }
+}
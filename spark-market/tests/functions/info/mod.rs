@@ -1,9 +1,31 @@
 mod account;
+mod audit_match;
+mod balance_history;
+mod batching_client;
+mod book_microstructure;
+mod book_snapshot;
+mod cached_market;
 mod config;
+mod contract_balances;
+mod crossed_orders;
+mod fee_change_history;
+mod frontrun_suspects;
+mod math;
 mod matcher_fee;
 mod order;
+mod order_aging;
+mod order_height;
 mod order_id;
+mod price;
+mod profitable_matches;
 mod protocol_fee;
 mod protocol_fee_user;
 mod protocol_fee_user_amount;
+mod quote_trade;
+mod recall_order_params;
+mod reconcile_locked;
+mod required_deposit;
+mod scaled_price;
+mod trade_activity;
 mod user_orders;
+mod verify_order_id;
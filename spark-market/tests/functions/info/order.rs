@@ -3,7 +3,7 @@ mod success {
     use crate::setup::{setup, Defaults};
     use fuels::accounts::ViewOnlyAccount;
     use fuels::types::Bits256;
-    use spark_market_sdk::OrderType;
+    use spark_market_sdk::{Order, OrderDetails, OrderType};
 
     #[tokio::test]
     async fn returns_none() -> anyhow::Result<()> {
@@ -35,7 +35,7 @@ mod success {
             .open_order(1, OrderType::Sell, 70_000_000_000_000_u64)
             .await?;
 
-        let order = contract.order(id.value).await?.value.unwrap();
+        let order = contract.order(id.value.order_id).await?.value.unwrap();
         let expected_id = contract
             .order_id(
                 order.order_type,
@@ -46,8 +46,114 @@ mod success {
             )
             .await?;
 
-        assert_eq!(id.value, expected_id.value);
+        assert_eq!(id.value.order_id, expected_id.value);
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn order_by_id_maps_fields() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let price = 70_000_000_000_000_u64;
+        let _ = contract.deposit(100, assets.base.id).await?;
+        let id = contract
+            .open_order(3, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+
+        let expected = OrderDetails {
+            id,
+            owner: owner.identity(),
+            order_type: OrderType::Sell,
+            price,
+            amount: 3,
+            block_height: owner.wallet.try_provider()?.latest_block_height().await?,
+            order_height: 0,
+        };
+
+        assert_eq!(contract.order_by_id(id).await?, Some(expected));
+        assert_eq!(contract.order_by_id(Bits256([0u8; 32])).await?, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn order_size_tracks_partial_fills() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let price = 70_000_000_000_000_u64;
+        let (original, filled) = (3_000_u64, 1_000_u64);
+
+        let seller = contract.with_account(&user0.wallet).await?;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        seller.deposit(original, assets.base.id).await?;
+        buyer
+            .deposit(price / to_quote_scale * filled, assets.quote.id)
+            .await?;
+        let sell_id = seller
+            .open_order(original, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        let buy_id = buyer
+            .open_order(filled, OrderType::Buy, price)
+            .await?
+            .value
+            .order_id;
+        contract.match_order_pair(sell_id, buy_id).await?;
+
+        let size = contract.order_size(sell_id).await?.unwrap();
+        assert_eq!(size.original, original);
+        assert!(size.remaining < size.original);
+        assert_eq!(size.original - size.remaining, filled);
+        assert_eq!(contract.order_size(buy_id).await?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn orders_compare_by_every_field() {
+        let order = Order::builder()
+            .amount(2)
+            .order_type(OrderType::Buy)
+            .price(70_000_000_000_000_u64)
+            .block_height(10)
+            .order_height(3)
+            .matcher_fee(5)
+            .protocol_fee(10, 15)
+            .build();
+
+        assert_eq!(order, order.clone());
+        assert_ne!(order, Order::builder().build());
+        assert_ne!(
+            order,
+            Order {
+                protocol_taker_fee: 16,
+                ..order.clone()
+            }
+        );
+        assert_ne!(
+            order,
+            Order {
+                order_type: OrderType::Sell,
+                ..order.clone()
+            }
+        );
+    }
 }
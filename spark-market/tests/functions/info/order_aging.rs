@@ -0,0 +1,102 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use fuels::accounts::ViewOnlyAccount;
+    use spark_market_sdk::OrderType;
+
+    #[tokio::test]
+    async fn reports_oldest_and_median_age() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+        let provider = owner.wallet.try_provider()?;
+
+        let price = 70_000_000_000_000_u64;
+        let _ = contract.deposit(10, assets.base.id).await?;
+
+        let mut orders = Vec::new();
+        for _ in 0..3 {
+            orders.push(
+                contract
+                    .open_order(1, OrderType::Sell, price)
+                    .await?
+                    .value
+                    .order_id,
+            );
+            provider.produce_blocks(5, None).await?;
+        }
+
+        let earliest = contract.order(orders[0]).await?.value.unwrap().block_height;
+        let tip = provider.latest_block_height().await?;
+
+        let aging = contract.order_aging(orders.clone(), 10).await?;
+
+        assert_eq!(aging.tip, tip);
+        assert_eq!(aging.oldest, tip - earliest);
+        let middle = contract.order(orders[1]).await?.value.unwrap().block_height;
+        assert_eq!(aging.median, tip - middle);
+        assert_eq!(aging.buckets.iter().map(|(_, count)| count).sum::<u64>(), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn skips_closed_orders() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+        let provider = owner.wallet.try_provider()?;
+
+        let price = 70_000_000_000_000_u64;
+        let _ = contract.deposit(10, assets.base.id).await?;
+
+        let cancelled = contract
+            .open_order(1, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        provider.produce_blocks(5, None).await?;
+        let open = contract
+            .open_order(1, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        let _ = contract.cancel_order(cancelled).await?;
+
+        let block_height = contract.order(open).await?.value.unwrap().block_height;
+        let aging = contract.order_aging(vec![cancelled, open], 10).await?;
+
+        assert_eq!(aging.oldest, aging.tip - block_height);
+        assert_eq!(aging.buckets, vec![(0, 1)]);
+
+        Ok(())
+    }
+}
+
+mod revert {
+
+    use crate::setup::{setup, Defaults};
+
+    #[tokio::test]
+    #[should_panic(expected = "No open orders to report on")]
+    async fn when_no_orders_are_open() {
+        let defaults = Defaults::default();
+        let (contract, _owner, _user, _, _, _assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await
+        .unwrap();
+
+        contract.order_aging(vec![], 10).await.unwrap();
+    }
+}
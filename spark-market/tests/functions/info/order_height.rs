@@ -0,0 +1,69 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use spark_market_sdk::OrderType;
+
+    #[tokio::test]
+    async fn assigns_incrementing_heights_within_a_session() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let price = 70_000_000_000_000_u64;
+        let trader = contract.with_account(&user.wallet).await?;
+        trader.deposit(3, assets.base.id).await?;
+
+        let mut ids = Vec::new();
+        let mut heights = Vec::new();
+        for _ in 0..3 {
+            let id = trader
+                .open_order(1, OrderType::Sell, price)
+                .await?
+                .value
+                .order_id;
+            heights.push(trader.order(id).await?.value.unwrap().order_height);
+            ids.push(id);
+        }
+
+        assert_eq!(heights[1], heights[0] + 1);
+        assert_eq!(heights[2], heights[1] + 1);
+        assert_ne!(ids[0], ids[1]);
+        assert_ne!(ids[1], ids[2]);
+        assert_ne!(ids[0], ids[2]);
+
+        // Every handle reads the same counter from the market
+        assert_eq!(trader.next_order_height().await?, heights[2] + 1);
+        assert_eq!(contract.next_order_height().await?, heights[2] + 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn follows_orders_of_other_accounts() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        assert_eq!(contract.next_order_height().await?, 0);
+
+        let other = contract.with_account(&user.wallet).await?;
+        other.deposit(1, assets.base.id).await?;
+        other
+            .open_order(1, OrderType::Sell, 70_000_000_000_000_u64)
+            .await?;
+
+        // The order was opened through another handle and still counts
+        assert_eq!(contract.order_height().await?.value, 1);
+        assert_eq!(contract.next_order_height().await?, 1);
+
+        Ok(())
+    }
+}
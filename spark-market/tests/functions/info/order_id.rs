@@ -1,4 +1,4 @@
-use crate::setup::{setup, Defaults};
+use crate::setup::{open_order_at_height, setup, Defaults};
 use spark_market_sdk::{/*AssetType,*/ OrderType};
 
 mod success {
@@ -39,6 +39,53 @@ mod success {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn order_at_fixed_height_matches_offline_id() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let price = 70_000_000_000_000_u64;
+        contract
+            .with_account(&user.wallet)
+            .await?
+            .deposit(1, assets.base.id)
+            .await?;
+        let (id, order_height) =
+            open_order_at_height(&contract, &user, 20, 1, OrderType::Sell, price).await?;
+
+        let order = contract.order(id).await?.value.unwrap();
+        assert_eq!(order.block_height, 20);
+        assert_eq!(order.order_height, order_height);
+        assert_eq!(
+            contract.order_id_offline(OrderType::Sell, user.identity(), price, order_height),
+            id
+        );
+
+        // The id is keyed by order height alone, the block the order landed in is not hashed
+        for block_height in [0, 20, u32::MAX] {
+            assert_eq!(
+                contract
+                    .order_id(
+                        OrderType::Sell,
+                        user.identity(),
+                        price,
+                        block_height,
+                        order_height
+                    )
+                    .await?
+                    .value,
+                id
+            );
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn accepts_buy_order() -> anyhow::Result<()> {
         // In this test we only care about the test not reverting with the correct asset
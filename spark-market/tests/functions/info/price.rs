@@ -0,0 +1,82 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use spark_market_sdk::{OrderType, Price};
+
+    #[test]
+    fn constructs_scaled_price() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+
+        let price = Price::from_human(70_000, defaults.price_decimals)?;
+        assert_eq!(price.scaled(), 70_000 * 10_u64.pow(defaults.price_decimals));
+        assert_eq!(price.decimals(), defaults.price_decimals);
+        assert_eq!(u64::from(price), price.scaled());
+
+        // An already scaled price is wrapped as is
+        assert_eq!(Price::new(price.scaled(), defaults.price_decimals)?, price);
+        assert_eq!(Price::from_human(1, 0)?.scaled(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_trips_human_price() -> anyhow::Result<()> {
+        for (value, decimals) in [(70_000, 9), (1, 0), (0, 9), (3_500, 6), (18, 18)] {
+            let price = Price::from_human(value, decimals)?;
+            assert_eq!(price.to_human(), value as f64);
+        }
+
+        // Fractions below a whole quote unit survive the conversion
+        assert_eq!(Price::new(1_500_000_000, 9)?.to_human(), 1.5);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn opens_order_at_market_scaled_price() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let price = contract.config().await?.price(70_000)?;
+        let user = contract.with_account(&user0.wallet).await?;
+        user.deposit(1_000, assets.base.id).await?;
+        let order_id = user
+            .open_order(1_000, OrderType::Sell, price.scaled())
+            .await?
+            .value
+            .order_id;
+
+        let order = contract.order(order_id).await?.value.unwrap();
+        assert_eq!(order.price, price.scaled());
+        assert_eq!(Price::new(order.price, defaults.price_decimals)?, price);
+
+        Ok(())
+    }
+}
+
+mod revert {
+
+    use spark_market_sdk::Price;
+
+    #[test]
+    fn from_human_errors_instead_of_wrapping() {
+        // 20_000_000_000 * 10^9 does not fit in a u64
+        let error = Price::from_human(20_000_000_000, 9).unwrap_err();
+        assert!(error.to_string().contains("PriceOverflow"));
+
+        // The scale itself overflows
+        let error = Price::from_human(1, 20).unwrap_err();
+        assert!(error.to_string().contains("PriceOverflow"));
+    }
+
+    #[test]
+    fn new_rejects_unscalable_decimals() {
+        let error = Price::new(1, 20).unwrap_err();
+        assert!(error.to_string().contains("PriceDecimalsTooLarge"));
+    }
+}
@@ -0,0 +1,64 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use spark_market_sdk::OrderType;
+
+    #[tokio::test]
+    async fn keeps_profitable_pairs_by_descending_profit() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, user1, _, _assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let seller = contract.with_account(&user0.wallet).await?;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        let (amount, price) = (1_000_u64, 70_000_000_000_000_u64);
+
+        // Each pair is opened under a different matcher fee, which the orders keep
+        let mut pairs = Vec::new();
+        for matcher_fee in [0, 1_000_000, 5_000_000] {
+            contract.set_matcher_fee(matcher_fee).await?;
+            let sell = seller
+                .deposit_and_open(OrderType::Sell, price, amount)
+                .await?
+                .value
+                .order_id;
+            let buy = buyer
+                .deposit_and_open(OrderType::Buy, price, amount)
+                .await?
+                .value
+                .order_id;
+            pairs.push((sell, buy));
+        }
+        // Orders on the same side never match
+        pairs.push((pairs[1].0, pairs[2].0));
+
+        let matches = contract
+            .profitable_matches(pairs.clone(), |fee| fee)
+            .await?;
+
+        // The pair without matcher fees cannot cover the fee of matching it
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].order_ids, pairs[2]);
+        assert_eq!(matches[0].reward, 2 * 5_000_000);
+        assert_eq!(matches[1].order_ids, pairs[1]);
+        assert_eq!(matches[1].reward, 2 * 1_000_000);
+        for profitable in &matches {
+            assert!(profitable.reward > profitable.cost);
+            assert_eq!(profitable.profit, profitable.reward - profitable.cost);
+        }
+
+        // A fee worth more than the smaller reward leaves only the larger one
+        let matches = contract
+            .profitable_matches(pairs.clone(), |_| 3_000_000)
+            .await?;
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].order_ids, pairs[2]);
+        assert_eq!(matches[0].profit, 10_000_000 - 3_000_000);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use spark_market_sdk::{quote_of_base_amount, RoundingMode};
+
+    #[tokio::test]
+    async fn rounds_payout_down() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, _user, _, _, _assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        // 1 unit of base at 1.5 quote units (scaled by 10^(8 + 9 - 6)) is not divisible
+        let price = 150_000_000_000_u64;
+
+        assert_eq!(contract.quote_trade(1, price).await?, 1);
+        assert_eq!(contract.quote_trade(3, price).await?, 4);
+        assert_eq!(contract.quote_trade(2, price).await?, 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rounding_modes_differ_on_remainder() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let price = 150_000_000_000_u64;
+
+        let convert = |amount, rounding| {
+            quote_of_base_amount(
+                amount,
+                price,
+                defaults.base_decimals,
+                defaults.quote_decimals,
+                defaults.price_decimals,
+                rounding,
+            )
+        };
+
+        assert_eq!(convert(1, RoundingMode::Floor)?, 1);
+        assert_eq!(convert(1, RoundingMode::Ceil)?, 2);
+
+        // No remainder, no rounding
+        assert_eq!(convert(2, RoundingMode::Floor)?, 3);
+        assert_eq!(convert(2, RoundingMode::Ceil)?, 3);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use fuels::types::Bits256;
+    use spark_market_sdk::{OrderParams, OrderType};
+
+    #[tokio::test]
+    async fn recalls_the_params_of_a_cancelled_order() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let base_amount = 100_000_u64; // 0.001 BTC
+        let price = 70_000_000_000_000_u64; // 70,000$ price
+
+        let user_contract = contract.with_account(&user.wallet).await?;
+        user_contract.deposit(base_amount, assets.base.id).await?;
+        let order_id = user_contract
+            .open_order(base_amount, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+
+        // The order is gone from the market but the session still knows what it was opened with
+        user_contract.cancel_order(order_id).await?;
+        assert!(contract.order(order_id).await?.value.is_none());
+
+        let expected = OrderParams {
+            owner: user.identity(),
+            order_type: OrderType::Sell,
+            price,
+            amount: base_amount,
+            deadline: None,
+        };
+        assert_eq!(
+            user_contract.recall_order_params(order_id),
+            Some(expected.clone())
+        );
+        // Handles made with with_account share the log
+        assert_eq!(contract.recall_order_params(order_id), Some(expected));
+        assert_eq!(contract.recall_order_params(Bits256([1; 32])), None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn drops_the_least_recently_used_order_beyond_capacity() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let base_amount = 100_000_u64; // 0.001 BTC
+        let price = 70_000_000_000_000_u64; // 70,000$ price
+
+        let user_contract = contract
+            .with_account(&user.wallet)
+            .await?
+            .with_order_log_capacity(2);
+        user_contract
+            .deposit(3 * base_amount, assets.base.id)
+            .await?;
+        let mut orders = Vec::new();
+        for step in 1..=2 {
+            orders.push(
+                user_contract
+                    .open_order(base_amount, OrderType::Sell, price * step)
+                    .await?
+                    .value
+                    .order_id,
+            );
+        }
+
+        // Recalling the first order makes the second the least recently used
+        assert!(user_contract.recall_order_params(orders[0]).is_some());
+        orders.push(
+            user_contract
+                .open_order(base_amount, OrderType::Sell, price * 3)
+                .await?
+                .value
+                .order_id,
+        );
+
+        assert!(user_contract.recall_order_params(orders[0]).is_some());
+        assert_eq!(user_contract.recall_order_params(orders[1]), None);
+        assert!(user_contract.recall_order_params(orders[2]).is_some());
+
+        Ok(())
+    }
+}
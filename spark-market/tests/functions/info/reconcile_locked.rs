@@ -0,0 +1,111 @@
+use crate::setup::{setup, Defaults};
+use spark_market_sdk::{expected_locked, OrderType, ProtocolFee};
+
+mod success {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn open_orders_add_up_to_locked_balance() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        contract
+            .set_protocol_fee(vec![ProtocolFee {
+                maker_fee: 10,
+                taker_fee: 20,
+                volume_threshold: 0,
+            }])
+            .await?;
+        contract.set_matcher_fee(100).await?;
+
+        let unit = 10_u64.pow(defaults.price_decimals);
+        let seller = contract.with_account(&user0.wallet).await?;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        seller.deposit(1_000_000, assets.base.id).await?;
+        buyer.deposit(1_000_000, assets.quote.id).await?;
+
+        let ask = seller
+            .open_order(40_000, OrderType::Sell, 70_000 * unit)
+            .await?
+            .value
+            .order_id;
+        seller
+            .open_order(100_000, OrderType::Sell, 72_000 * unit)
+            .await?;
+        let bid = buyer
+            .open_order(100_000, OrderType::Buy, 70_000 * unit)
+            .await?
+            .value
+            .order_id;
+        buyer
+            .open_order(50_000, OrderType::Buy, 69_000 * unit)
+            .await?;
+
+        // Fills the ask and leaves 60_000 of the bid with what is left of its matcher fee
+        contract.match_order_pair(ask, bid).await?;
+
+        for user in [user0.identity(), user1.identity()] {
+            let reconciliation = contract.reconcile_locked(user).await?;
+            assert!(reconciliation.is_balanced(), "{}", reconciliation);
+            assert_eq!(reconciliation.base_delta(), 0);
+            assert_eq!(reconciliation.quote_delta(), 0);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reports_delta_of_drifted_lock() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, user0, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let unit = 10_u64.pow(defaults.price_decimals);
+        let trader = contract.with_account(&user0.wallet).await?;
+        trader.deposit(1_000_000, assets.base.id).await?;
+        trader.deposit(1_000_000, assets.quote.id).await?;
+        trader
+            .open_order(40_000, OrderType::Sell, 70_000 * unit)
+            .await?;
+        let bid = trader
+            .open_order(100_000, OrderType::Buy, 69_000 * unit)
+            .await?
+            .value
+            .order_id;
+
+        let mut reconciliation = contract.reconcile_locked(user0.identity()).await?;
+        assert!(reconciliation.is_balanced());
+
+        // Seed drift by summing the orders as if the bid's lock had been released without it
+        let config = contract.config().await?;
+        let remaining = contract
+            .user_orders(user0.identity())
+            .await?
+            .value
+            .into_iter()
+            .filter(|order_id| *order_id != bid);
+        let mut orders = Vec::new();
+        for order_id in remaining {
+            orders.push(contract.order(order_id).await?.value.unwrap());
+        }
+        reconciliation.expected = expected_locked(&orders, 0, &config)?;
+
+        // 100_000 base at 69_000 is worth 69_000 quote
+        assert!(!reconciliation.is_balanced());
+        assert_eq!(reconciliation.base_delta(), 0);
+        assert_eq!(reconciliation.quote_delta(), 69_000);
+        assert!(reconciliation.to_string().contains("delta 69000"));
+
+        Ok(())
+    }
+}
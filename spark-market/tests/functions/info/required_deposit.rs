@@ -0,0 +1,79 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use spark_market_sdk::{OrderType, ProtocolFee};
+
+    #[tokio::test]
+    async fn sell_requires_base_amount() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let (asset, amount) = contract
+            .required_deposit(3, OrderType::Sell, 150_000_000_000_u64)
+            .await?;
+
+        assert_eq!(asset, assets.base.id);
+        assert_eq!(amount, 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn buy_rounds_deposit_up() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        // 1 unit of base at 1.5 quote units (scaled by 10^(8 + 9 - 6)) is not divisible
+        let price = 150_000_000_000_u64;
+        let (asset, amount) = contract.required_deposit(1, OrderType::Buy, price).await?;
+
+        assert_eq!(asset, assets.quote.id);
+        assert_eq!(amount, 2);
+
+        // The rounded up deposit is enough for the contract to lock
+        let _ = contract.deposit(amount, asset).await?;
+        let _ = contract.open_order(1, OrderType::Buy, price).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn buy_rounds_fees_up() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, _owner, _user, _, _, _assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let matcher_fee = 5;
+        let _ = contract.set_matcher_fee(matcher_fee).await?;
+        let _ = contract
+            .set_protocol_fee(vec![ProtocolFee {
+                maker_fee: 10,
+                taker_fee: 15,
+                volume_threshold: 0,
+            }])
+            .await?;
+
+        // Quote value of 1_001 is 1_001 * 15 / 10_000 = 1.5015 in protocol fee
+        let (_, amount) = contract
+            .required_deposit(1_001, OrderType::Buy, 100_000_000_000_u64)
+            .await?;
+
+        assert_eq!(amount, 1_001 + 2 + matcher_fee);
+
+        Ok(())
+    }
+}
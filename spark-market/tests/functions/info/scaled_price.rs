@@ -0,0 +1,33 @@
+mod success {
+
+    use crate::setup::Defaults;
+    use spark_market_sdk::scaled_price;
+
+    #[test]
+    fn scales_by_price_decimals() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+
+        assert_eq!(
+            scaled_price(70_000, defaults.price_decimals)?,
+            70_000 * 10_u64.pow(defaults.price_decimals)
+        );
+        assert_eq!(scaled_price(1, 0)?, 1);
+
+        Ok(())
+    }
+}
+
+mod revert {
+
+    use spark_market_sdk::scaled_price;
+
+    #[test]
+    fn errors_instead_of_wrapping() {
+        // 20_000_000_000 * 10^9 does not fit in a u64
+        let error = scaled_price(20_000_000_000, 9).unwrap_err();
+        assert!(error.to_string().contains("PriceOverflow"));
+
+        // The scale itself overflows
+        assert!(scaled_price(1, 20).is_err());
+    }
+}
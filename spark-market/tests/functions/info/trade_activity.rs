@@ -0,0 +1,123 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use fuels::accounts::ViewOnlyAccount;
+    use spark_market_sdk::OrderType;
+
+    #[tokio::test]
+    async fn tracks_last_trade_and_staleness() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+        let provider = owner.wallet.try_provider()?;
+
+        // Assert precondition of a market that never traded
+        assert_eq!(contract.last_trade_block(0).await?, None);
+        assert!(!contract.is_active_within(10).await?);
+
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let base_amount = 1_000_u64;
+        let price = 70_000_000_000_000_u64;
+
+        let seller = contract.with_account(&user0.wallet).await?;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        seller.deposit(base_amount, assets.base.id).await?;
+        buyer
+            .deposit(price / to_quote_scale * base_amount, assets.quote.id)
+            .await?;
+        let start = provider.latest_block_height().await?;
+        let id0 = seller
+            .open_order(base_amount, OrderType::Sell, price)
+            .await?
+            .value
+            .order_id;
+        let id1 = buyer
+            .open_order(base_amount, OrderType::Buy, price)
+            .await?
+            .value
+            .order_id;
+        contract.match_order_pair(id0, id1).await?;
+        let match_block = provider.latest_block_height().await?;
+
+        assert_eq!(contract.last_trade_block(start).await?, Some(match_block));
+        assert!(contract.is_active_within(10).await?);
+
+        provider.produce_blocks(50, None).await?;
+
+        assert_eq!(contract.last_trade_block(start).await?, Some(match_block));
+        // Blocks below the bound are never scanned
+        assert_eq!(contract.last_trade_block(match_block + 1).await?, None);
+        assert!(!contract.is_active_within(10).await?);
+        assert!(contract.is_active_within(51).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn trades_per_block_divides_trades_by_the_window() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, user0, user1, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+        let provider = owner.wallet.try_provider()?;
+
+        // Assert precondition of a market that never traded
+        let throughput = contract.trades_per_block(10).await?;
+        assert_eq!(throughput.trades, 0);
+        assert_eq!(throughput.per_block(), 0.0);
+        // A chain taller than the window, so it spans all of its blocks
+        provider.produce_blocks(30, None).await?;
+
+        let to_quote_scale =
+            10_u64.pow(defaults.price_decimals + defaults.base_decimals - defaults.quote_decimals);
+        let base_amount = 1_000_u64;
+        let price = 70_000_000_000_000_u64;
+        let matches = 3;
+
+        let seller = contract.with_account(&user0.wallet).await?;
+        let buyer = contract.with_account(&user1.wallet).await?;
+        seller
+            .deposit(base_amount * matches, assets.base.id)
+            .await?;
+        buyer
+            .deposit(
+                price / to_quote_scale * base_amount * matches,
+                assets.quote.id,
+            )
+            .await?;
+        for _ in 0..matches {
+            let id0 = seller
+                .open_order(base_amount, OrderType::Sell, price)
+                .await?
+                .value
+                .order_id;
+            let id1 = buyer
+                .open_order(base_amount, OrderType::Buy, price)
+                .await?
+                .value
+                .order_id;
+            contract.match_order_pair(id0, id1).await?;
+        }
+        provider.produce_blocks(5, None).await?;
+
+        let throughput = contract.trades_per_block(30).await?;
+        assert_eq!(throughput.trades, matches as usize);
+        assert_eq!(throughput.blocks, 30);
+        assert_eq!(throughput.per_block(), matches as f64 / 30.0);
+
+        // The trades have left a window of the latest blocks
+        let throughput = contract.trades_per_block(5).await?;
+        assert_eq!(throughput.trades, 0);
+        assert_eq!(throughput.per_block(), 0.0);
+
+        Ok(())
+    }
+}
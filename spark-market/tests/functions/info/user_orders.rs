@@ -1,6 +1,7 @@
 mod success {
 
     use crate::setup::{setup, Defaults};
+    use fuels::core::codec::DecoderConfig;
     use spark_market_sdk::{/*AssetType,*/ OrderType};
 
     #[tokio::test]
@@ -48,8 +49,104 @@ mod success {
         let mut orders = contract.user_orders(owner.identity()).await?.value;
 
         assert_eq!(2, orders.len());
-        assert_eq!(id2.value, orders.pop().unwrap());
-        assert_eq!(id1.value, orders.pop().unwrap());
+        assert_eq!(id2.value.order_id, orders.pop().unwrap());
+        assert_eq!(id1.value.order_id, orders.pop().unwrap());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn returns_orders_without_fallback() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let _ = contract.deposit(1000, assets.base.id).await?;
+        let id = contract
+            .open_order(2, OrderType::Sell, 70_000_000_000_000_u64)
+            .await?
+            .value
+            .order_id;
+
+        let orders = contract
+            .all_user_orders(owner.identity(), 10, DecoderConfig::default())
+            .await?;
+
+        assert_eq!(orders.orders, vec![id]);
+        assert_eq!(orders.warning, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_pagination_when_limit_exceeded() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let _ = contract.deposit(1000, assets.base.id).await?;
+        let mut expected = Vec::new();
+        for i in 0..7 {
+            expected.push(
+                contract
+                    .open_order(1, OrderType::Sell, 70_000_000_000_000_u64 + i)
+                    .await?
+                    .value
+                    .order_id,
+            );
+        }
+
+        // A vector of 7 ids exceeds the limit while a page of 3 does not
+        let decoder_config = DecoderConfig {
+            max_tokens: 5,
+            ..DecoderConfig::default()
+        };
+        let orders = contract
+            .all_user_orders(owner.identity(), 3, decoder_config)
+            .await?;
+
+        assert_eq!(orders.orders, expected);
+        assert!(orders.warning.is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn returns_page_of_orders() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let _ = contract.deposit(1000, assets.base.id).await?;
+        let mut expected = Vec::new();
+        for i in 0..3 {
+            expected.push(
+                contract
+                    .open_order(1, OrderType::Sell, 70_000_000_000_000_u64 + i)
+                    .await?
+                    .value
+                    .order_id,
+            );
+        }
+
+        let page = contract
+            .user_orders_paginated(owner.identity(), 1, 5)
+            .await?
+            .value;
+
+        assert_eq!(page, expected[1..].to_vec());
 
         Ok(())
     }
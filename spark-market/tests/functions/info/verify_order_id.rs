@@ -0,0 +1,81 @@
+mod success {
+
+    use crate::setup::{setup, Defaults};
+    use fuels::accounts::ViewOnlyAccount;
+    use spark_market_sdk::OrderType;
+
+    #[tokio::test]
+    async fn verifies_open_order() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, _user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let price = 70_000_000_000_000_u64;
+        let _ = contract.deposit(100, assets.base.id).await?;
+        let _ = contract.open_order(1, OrderType::Sell, price).await?;
+        let block_height = owner.wallet.try_provider()?.latest_block_height().await?;
+
+        assert!(
+            contract
+                .verify_order_id(OrderType::Sell, owner.identity(), price, block_height, 0)
+                .await?
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn fails_mismatched_order() -> anyhow::Result<()> {
+        let defaults = Defaults::default();
+        let (contract, owner, user, _, _, assets) = setup(
+            defaults.base_decimals,
+            defaults.quote_decimals,
+            defaults.price_decimals,
+        )
+        .await?;
+
+        let price = 70_000_000_000_000_u64;
+        let _ = contract.deposit(100, assets.base.id).await?;
+        let _ = contract.open_order(1, OrderType::Sell, price).await?;
+        let block_height = owner.wallet.try_provider()?.latest_block_height().await?;
+
+        // Different price computes an id with no open order
+        assert!(
+            !contract
+                .verify_order_id(
+                    OrderType::Sell,
+                    owner.identity(),
+                    price + 1,
+                    block_height,
+                    0
+                )
+                .await?
+        );
+
+        // Different owner computes an id with no open order
+        assert!(
+            !contract
+                .verify_order_id(OrderType::Sell, user.identity(), price, block_height, 0)
+                .await?
+        );
+
+        // Same id but the block height does not match the open order
+        assert!(
+            !contract
+                .verify_order_id(
+                    OrderType::Sell,
+                    owner.identity(),
+                    price,
+                    block_height + 1,
+                    0
+                )
+                .await?
+        );
+
+        Ok(())
+    }
+}
@@ -1,12 +1,12 @@
 use fuels::{
-    accounts::ViewOnlyAccount,
+    accounts::{Account as _, ViewOnlyAccount},
     prelude::{
-        launch_custom_provider_and_get_wallets, Address, AssetConfig, AssetId, WalletUnlocked,
-        WalletsConfig,
+        launch_custom_provider_and_get_wallets, Address, AssetConfig, AssetId, Provider,
+        TxPolicies, WalletUnlocked, WalletsConfig,
     },
-    types::Identity,
+    types::{Bits256, Identity},
 };
-use spark_market_sdk::{Account, Balance, SparkMarketContract};
+use spark_market_sdk::{Account, Balance, OrderType, SparkMarketContract};
 
 pub(crate) struct Assets {
     pub(crate) base: Asset,
@@ -71,6 +71,102 @@ pub(crate) fn create_account(
     }
 }
 
+/// Asserts the market holds exactly the base and quote credited to `accounts`, liquid or locked,
+/// so no trade created or destroyed funds
+pub(crate) async fn assert_solvent(
+    contract: &SparkMarketContract,
+    accounts: &[Identity],
+) -> anyhow::Result<()> {
+    let mut credited = create_account(0, 0, 0, 0);
+    for identity in accounts {
+        let account = contract.account(*identity).await?.value;
+        credited.liquid.base += account.liquid.base;
+        credited.liquid.quote += account.liquid.quote;
+        credited.locked.base += account.locked.base;
+        credited.locked.quote += account.locked.quote;
+    }
+
+    let held = contract.contract_balances().await?;
+
+    assert_eq!(held.base, credited.liquid.base + credited.locked.base);
+    assert_eq!(held.quote, credited.liquid.quote + credited.locked.quote);
+
+    Ok(())
+}
+
+/// Transfers each amount of an asset from `funder` to its wallet, one transfer at a time, and
+/// checks every wallet received it in full
+pub(crate) async fn fund(
+    funder: &WalletUnlocked,
+    wallets: &[(&WalletUnlocked, &Asset, u64)],
+) -> anyhow::Result<()> {
+    for (wallet, asset, amount) in wallets {
+        let before = wallet.get_asset_balance(&asset.id).await?;
+        funder
+            .transfer(wallet.address(), *amount, asset.id, TxPolicies::default())
+            .await?;
+        let after = wallet.get_asset_balance(&asset.id).await?;
+
+        let received = after.saturating_sub(before);
+        if received < *amount {
+            anyhow::bail!(
+                "Funding {} fell short: received {} of {} of asset {}",
+                wallet.address(),
+                received,
+                amount,
+                asset.id
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Produces blocks until `height` is the latest block, so the next transaction lands at
+/// `height + 1`
+pub(crate) async fn advance_blocks(provider: &Provider, height: u32) -> anyhow::Result<()> {
+    let latest = provider.latest_block_height().await?;
+    if latest > height {
+        anyhow::bail!(
+            "Block height {} has already passed, the chain is at {}",
+            height,
+            latest
+        );
+    }
+    if latest < height {
+        provider.produce_blocks(height - latest, None).await?;
+    }
+
+    Ok(())
+}
+
+/// Opens an order that lands in block `block_height`, returning its id together with the order
+/// height the market gave it. Both heights are then known up front, so the id is reproducible
+pub(crate) async fn open_order_at_height(
+    contract: &SparkMarketContract,
+    user: &User,
+    block_height: u32,
+    amount: u64,
+    order_type: OrderType,
+    price: u64,
+) -> anyhow::Result<(Bits256, u64)> {
+    let previous = block_height
+        .checked_sub(1)
+        .ok_or_else(|| anyhow::anyhow!("No order can land in the genesis block"))?;
+    advance_blocks(user.wallet.try_provider()?, previous).await?;
+    let order_height = contract.order_height().await?.value;
+
+    let order_id = contract
+        .with_account(&user.wallet)
+        .await?
+        .open_order(amount, order_type, price)
+        .await?
+        .value
+        .order_id;
+
+    Ok((order_id, order_height))
+}
+
 pub(crate) async fn setup(
     base_decimals: u32,
     quote_decimals: u32,